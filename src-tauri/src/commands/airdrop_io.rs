@@ -0,0 +1,665 @@
+use log::info;
+use tauri::State;
+use sqlx::{Acquire, Sqlite, SqlitePool, Transaction};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+use crate::commands::airdrops::normalize_url;
+use crate::models::{Airdrop, AirdropDailyTask, AirdropType};
+
+/// Import fields and the source column names recognised when no mapping is given.
+const IMPORT_FIELDS: &[(&str, &[&str])] = &[
+    ("name", &["name"]),
+    ("url", &["url", "link"]),
+    ("chain", &["chain"]),
+    ("wallet_address", &["wallet_address", "wallet"]),
+    ("type", &["type", "airdrop_type"]),
+    ("notes", &["notes"]),
+    ("active", &["active"]),
+    ("tasks", &["tasks", "daily_tasks"]),
+];
+
+const EXPORT_HEADER: [&str; 8] = [
+    "name",
+    "url",
+    "chain",
+    "wallet_address",
+    "type",
+    "notes",
+    "active",
+    "tasks",
+];
+
+#[derive(Debug, Deserialize)]
+pub struct ImportAirdropsRequest {
+    /// `csv` or `json`
+    pub format: String,
+    pub content: String,
+    /// Maps an import field (`name`, `url`, `chain`, ...) to a source column name.
+    pub column_mapping: Option<HashMap<String, String>>,
+    /// Separator used to split a text `tasks` column that is not a JSON
+    /// array, defaults to `;`.
+    pub task_separator: Option<String>,
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportRowStatus {
+    Created,
+    WouldCreate,
+    Duplicate,
+    Error,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ImportRowResult {
+    pub row: usize,
+    pub name: Option<String>,
+    pub url: Option<String>,
+    pub status: ImportRowStatus,
+    pub message: Option<String>,
+    pub airdrop_id: Option<i64>,
+    pub tasks: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ImportAirdropsReport {
+    pub dry_run: bool,
+    pub total_rows: usize,
+    pub created: usize,
+    pub duplicates: usize,
+    pub errors: usize,
+    pub rows: Vec<ImportRowResult>,
+}
+
+struct ParsedRow {
+    name: String,
+    url: String,
+    chain: Option<String>,
+    wallet_address: Option<String>,
+    airdrop_type_id: Option<i64>,
+    notes: Option<String>,
+    active: bool,
+    tasks: Vec<String>,
+}
+
+type Record = HashMap<String, serde_json::Value>;
+
+#[tauri::command]
+pub async fn import_airdrops(
+    state: State<'_, crate::AppState>,
+    req: ImportAirdropsRequest,
+) -> Result<ImportAirdropsReport, String> {
+    import_records(&state.db, &req).await
+}
+
+/// Imports every row it can. A row that fails to save is rolled back on its
+/// own and reported as an error; the rest of the import still goes through.
+pub(crate) async fn import_records(db: &SqlitePool, req: &ImportAirdropsRequest) -> Result<ImportAirdropsReport, String> {
+    info!(
+        "phase=airdrop_import_start format={} dry_run={}",
+        req.format, req.dry_run
+    );
+
+    let records = match req.format.to_lowercase().as_str() {
+        "csv" => parse_csv_records(&req.content)?,
+        "json" => parse_json_records(&req.content)?,
+        other => return Err(format!("Unsupported import format: {}", other)),
+    };
+
+    let mapping = req.column_mapping.clone().unwrap_or_default();
+    let separator = req.task_separator.clone().unwrap_or_else(|| ";".to_string());
+
+    let types = sqlx::query_as::<_, AirdropType>("SELECT * FROM airdrop_types")
+        .fetch_all(db)
+        .await
+        .map_err(|e| format!("Failed to fetch airdrop types: {}", e))?;
+    let type_ids: HashMap<String, i64> = types
+        .iter()
+        .map(|t| (t.name.trim().to_lowercase(), t.id))
+        .collect();
+
+    let existing_urls = sqlx::query_scalar::<_, String>("SELECT url FROM airdrops")
+        .fetch_all(db)
+        .await
+        .map_err(|e| format!("Failed to fetch airdrops: {}", e))?;
+    let existing: HashSet<String> = existing_urls.iter().map(|u| normalize_url(u)).collect();
+    let mut seen: HashMap<String, usize> = HashMap::new();
+
+    let mut tx = db
+        .begin()
+        .await
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+
+    let mut position = sqlx::query_scalar::<_, i64>(
+        "SELECT COALESCE(MAX(position), -1) + 1 FROM airdrops",
+    )
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(|e| format!("Failed to fetch airdrop positions: {}", e))?;
+
+    let mut rows = Vec::new();
+    for (idx, record) in records.into_iter().enumerate() {
+        let row = idx + 1;
+        let record = match record {
+            Ok(record) => record,
+            Err(e) => {
+                rows.push(error_row(row, None, None, e));
+                continue;
+            }
+        };
+
+        let raw_name = field_text(&record, &mapping, "name");
+        let raw_url = field_text(&record, &mapping, "url");
+        let parsed = match parse_row(&record, &mapping, &separator, &type_ids) {
+            Ok(parsed) => parsed,
+            Err(e) => {
+                rows.push(error_row(row, raw_name, raw_url, e));
+                continue;
+            }
+        };
+
+        let normalized = normalize_url(&parsed.url);
+        if existing.contains(&normalized) {
+            rows.push(ImportRowResult {
+                row,
+                name: Some(parsed.name),
+                url: Some(parsed.url),
+                status: ImportRowStatus::Duplicate,
+                message: Some("An airdrop with this URL already exists".to_string()),
+                airdrop_id: None,
+                tasks: parsed.tasks,
+            });
+            continue;
+        }
+        if let Some(first_row) = seen.get(&normalized) {
+            rows.push(ImportRowResult {
+                row,
+                name: Some(parsed.name),
+                url: Some(parsed.url),
+                status: ImportRowStatus::Duplicate,
+                message: Some(format!("Duplicate of row {} in this import", first_row)),
+                airdrop_id: None,
+                tasks: parsed.tasks,
+            });
+            continue;
+        }
+
+        if req.dry_run {
+            seen.insert(normalized, row);
+            rows.push(ImportRowResult {
+                row,
+                name: Some(parsed.name),
+                url: Some(parsed.url),
+                status: ImportRowStatus::WouldCreate,
+                message: None,
+                airdrop_id: None,
+                tasks: parsed.tasks,
+            });
+            continue;
+        }
+
+        // Each row gets a savepoint so a failed row leaves the others intact
+        let mut row_tx = tx
+            .begin()
+            .await
+            .map_err(|e| format!("Failed to start savepoint: {}", e))?;
+        let airdrop_id = match insert_row(&mut row_tx, &parsed, position).await {
+            Ok(airdrop_id) => {
+                row_tx
+                    .commit()
+                    .await
+                    .map_err(|e| format!("Failed to release savepoint: {}", e))?;
+                airdrop_id
+            }
+            Err(e) => {
+                row_tx
+                    .rollback()
+                    .await
+                    .map_err(|e| format!("Failed to roll back savepoint: {}", e))?;
+                rows.push(error_row(row, Some(parsed.name), Some(parsed.url), e));
+                continue;
+            }
+        };
+        position += 1;
+        seen.insert(normalized, row);
+
+        rows.push(ImportRowResult {
+            row,
+            name: Some(parsed.name),
+            url: Some(parsed.url),
+            status: ImportRowStatus::Created,
+            message: None,
+            airdrop_id: Some(airdrop_id),
+            tasks: parsed.tasks,
+        });
+    }
+
+    if req.dry_run {
+        tx.rollback()
+            .await
+            .map_err(|e| format!("Failed to roll back dry run: {}", e))?;
+    } else {
+        tx.commit()
+            .await
+            .map_err(|e| format!("Failed to commit import: {}", e))?;
+    }
+
+    let created = rows
+        .iter()
+        .filter(|r| matches!(r.status, ImportRowStatus::Created | ImportRowStatus::WouldCreate))
+        .count();
+    let duplicates = rows
+        .iter()
+        .filter(|r| matches!(r.status, ImportRowStatus::Duplicate))
+        .count();
+    let errors = rows
+        .iter()
+        .filter(|r| matches!(r.status, ImportRowStatus::Error))
+        .count();
+    let report = ImportAirdropsReport {
+        dry_run: req.dry_run,
+        total_rows: rows.len(),
+        created,
+        duplicates,
+        errors,
+        rows,
+    };
+
+    info!(
+        "phase=airdrop_import_done rows={} created={} duplicates={} errors={}",
+        report.total_rows, report.created, report.duplicates, report.errors
+    );
+    Ok(report)
+}
+
+async fn insert_row(tx: &mut Transaction<'_, Sqlite>, parsed: &ParsedRow, position: i64) -> Result<i64, String> {
+    let now = Utc::now().to_rfc3339();
+    let airdrop_id = sqlx::query(
+        r#"
+        INSERT INTO airdrops (name, url, airdrop_type_id, chain, wallet_address, position, notes, active, created_at, updated_at)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(&parsed.name)
+    .bind(&parsed.url)
+    .bind(parsed.airdrop_type_id)
+    .bind(&parsed.chain)
+    .bind(&parsed.wallet_address)
+    .bind(position)
+    .bind(&parsed.notes)
+    .bind(if parsed.active { 1 } else { 0 })
+    .bind(&now)
+    .bind(&now)
+    .execute(&mut **tx)
+    .await
+    .map_err(|e| format!("Failed to import airdrop: {}", e))?
+    .last_insert_rowid();
+
+    for (order, title) in parsed.tasks.iter().enumerate() {
+        sqlx::query(
+            r#"
+            INSERT INTO airdrop_daily_tasks (airdrop_id, title, "order", done_dates, created_at, updated_at)
+            VALUES (?, ?, ?, '[]', ?, ?)
+            "#,
+        )
+        .bind(airdrop_id)
+        .bind(title)
+        .bind(order as i64)
+        .bind(&now)
+        .bind(&now)
+        .execute(&mut **tx)
+        .await
+        .map_err(|e| format!("Failed to import daily task: {}", e))?;
+    }
+
+    Ok(airdrop_id)
+}
+
+#[tauri::command]
+pub async fn export_airdrops_csv(state: State<'_, crate::AppState>) -> Result<String, String> {
+    export_csv(&state.db).await
+}
+
+/// Writes every airdrop as a CSV row. The `tasks` cell holds a JSON array of
+/// titles so titles containing the separator survive a round trip.
+pub(crate) async fn export_csv(db: &SqlitePool) -> Result<String, String> {
+    let airdrops = sqlx::query_as::<_, Airdrop>(
+        "SELECT * FROM airdrops ORDER BY position ASC, created_at ASC",
+    )
+    .fetch_all(db)
+    .await
+    .map_err(|e| format!("Failed to fetch airdrops: {}", e))?;
+
+    let types = sqlx::query_as::<_, AirdropType>("SELECT * FROM airdrop_types")
+        .fetch_all(db)
+        .await
+        .map_err(|e| format!("Failed to fetch airdrop types: {}", e))?;
+    let type_names: HashMap<i64, String> = types.into_iter().map(|t| (t.id, t.name)).collect();
+
+    let tasks = sqlx::query_as::<_, AirdropDailyTask>(
+        "SELECT * FROM airdrop_daily_tasks ORDER BY airdrop_id ASC, \"order\" ASC",
+    )
+    .fetch_all(db)
+    .await
+    .map_err(|e| format!("Failed to fetch daily tasks: {}", e))?;
+    let mut tasks_by_airdrop: HashMap<i64, Vec<String>> = HashMap::new();
+    for task in tasks {
+        tasks_by_airdrop
+            .entry(task.airdrop_id)
+            .or_default()
+            .push(task.title);
+    }
+
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer
+        .write_record(EXPORT_HEADER)
+        .map_err(|e| format!("Failed to write CSV header: {}", e))?;
+
+    for airdrop in &airdrops {
+        let type_name = airdrop
+            .airdrop_type_id
+            .and_then(|id| type_names.get(&id))
+            .cloned()
+            .unwrap_or_default();
+        let tasks = match tasks_by_airdrop.get(&airdrop.id) {
+            Some(titles) => serde_json::to_string(titles).map_err(|e| format!("Failed to write tasks: {}", e))?,
+            None => String::new(),
+        };
+
+        writer
+            .write_record([
+                airdrop.name.as_str(),
+                airdrop.url.as_str(),
+                airdrop.chain.as_deref().unwrap_or(""),
+                airdrop.wallet_address.as_deref().unwrap_or(""),
+                type_name.as_str(),
+                airdrop.notes.as_deref().unwrap_or(""),
+                if airdrop.active { "true" } else { "false" },
+                tasks.as_str(),
+            ])
+            .map_err(|e| format!("Failed to write CSV row: {}", e))?;
+    }
+
+    let bytes = writer
+        .into_inner()
+        .map_err(|e| format!("Failed to finish CSV export: {}", e))?;
+    let csv = String::from_utf8(bytes).map_err(|e| format!("Invalid CSV output: {}", e))?;
+
+    info!("phase=airdrop_export_ok rows={}", airdrops.len());
+    Ok(csv)
+}
+
+fn parse_csv_records(content: &str) -> Result<Vec<Result<Record, String>>, String> {
+    let mut reader = csv::ReaderBuilder::new()
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_reader(content.as_bytes());
+
+    let headers: Vec<String> = reader
+        .headers()
+        .map_err(|e| format!("Failed to read CSV header: {}", e))?
+        .iter()
+        .map(|h| h.to_lowercase())
+        .collect();
+
+    let records = reader
+        .records()
+        .map(|record| {
+            let record = record.map_err(|e| format!("Malformed CSV row: {}", e))?;
+            Ok(headers
+                .iter()
+                .cloned()
+                .zip(record.iter().map(|v| serde_json::Value::String(v.to_string())))
+                .collect())
+        })
+        .collect();
+
+    Ok(records)
+}
+
+fn parse_json_records(content: &str) -> Result<Vec<Result<Record, String>>, String> {
+    let value: serde_json::Value =
+        serde_json::from_str(content).map_err(|e| format!("Invalid JSON: {}", e))?;
+    let items = match value {
+        serde_json::Value::Array(items) => items,
+        _ => return Err("JSON import must be an array of objects".to_string()),
+    };
+
+    Ok(items
+        .into_iter()
+        .map(|item| match item {
+            serde_json::Value::Object(map) => Ok(map
+                .into_iter()
+                .map(|(key, value)| (key.to_lowercase(), value))
+                .collect()),
+            _ => Err("Row is not a JSON object".to_string()),
+        })
+        .collect())
+}
+
+fn field_value<'a>(
+    record: &'a Record,
+    mapping: &HashMap<String, String>,
+    field: &str,
+) -> Option<&'a serde_json::Value> {
+    if let Some(column) = mapping.get(field) {
+        return record.get(&column.to_lowercase());
+    }
+
+    IMPORT_FIELDS
+        .iter()
+        .find(|(name, _)| *name == field)
+        .and_then(|(_, columns)| columns.iter().find_map(|column| record.get(*column)))
+}
+
+fn field_text(record: &Record, mapping: &HashMap<String, String>, field: &str) -> Option<String> {
+    let text = match field_value(record, mapping, field)? {
+        serde_json::Value::Null => return None,
+        serde_json::Value::String(s) => s.trim().to_string(),
+        other => other.to_string(),
+    };
+
+    if text.is_empty() {
+        None
+    } else {
+        Some(text)
+    }
+}
+
+fn parse_row(
+    record: &Record,
+    mapping: &HashMap<String, String>,
+    separator: &str,
+    type_ids: &HashMap<String, i64>,
+) -> Result<ParsedRow, String> {
+    let name = field_text(record, mapping, "name").ok_or("Missing name")?;
+    let url = field_text(record, mapping, "url").ok_or("Missing url")?;
+
+    let active = match field_value(record, mapping, "active") {
+        None | Some(serde_json::Value::Null) => true,
+        Some(serde_json::Value::Bool(b)) => *b,
+        Some(_) => {
+            let text = field_text(record, mapping, "active").unwrap_or_default();
+            match text.to_lowercase().as_str() {
+                "" | "true" | "yes" | "y" | "1" => true,
+                "false" | "no" | "n" | "0" => false,
+                other => return Err(format!("Invalid active value: {}", other)),
+            }
+        }
+    };
+
+    let airdrop_type_id = match field_text(record, mapping, "type") {
+        Some(type_name) => Some(
+            *type_ids
+                .get(&type_name.to_lowercase())
+                .ok_or_else(|| format!("Unknown airdrop type: {}", type_name))?,
+        ),
+        None => None,
+    };
+
+    let tasks = match field_value(record, mapping, "tasks") {
+        Some(serde_json::Value::Array(items)) => items
+            .iter()
+            .map(|item| match item {
+                serde_json::Value::String(s) => s.trim().to_string(),
+                other => other.to_string(),
+            })
+            .filter(|title| !title.is_empty())
+            .collect(),
+        // Exports write a JSON array; hand-written files use the separator
+        Some(serde_json::Value::String(s)) => match serde_json::from_str::<Vec<String>>(s) {
+            Ok(titles) => titles
+                .into_iter()
+                .map(|title| title.trim().to_string())
+                .filter(|title| !title.is_empty())
+                .collect(),
+            Err(_) => s
+                .split(separator)
+                .map(|title| title.trim().to_string())
+                .filter(|title| !title.is_empty())
+                .collect(),
+        },
+        None | Some(serde_json::Value::Null) => Vec::new(),
+        Some(_) => return Err("Invalid tasks value".to_string()),
+    };
+
+    Ok(ParsedRow {
+        name,
+        url,
+        chain: field_text(record, mapping, "chain"),
+        wallet_address: field_text(record, mapping, "wallet_address"),
+        airdrop_type_id,
+        notes: field_text(record, mapping, "notes"),
+        active,
+        tasks,
+    })
+}
+
+fn error_row(row: usize, name: Option<String>, url: Option<String>, message: String) -> ImportRowResult {
+    ImportRowResult {
+        row,
+        name,
+        url,
+        status: ImportRowStatus::Error,
+        message: Some(message),
+        airdrop_id: None,
+        tasks: Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::memory_db;
+
+    fn request(format: &str, content: &str) -> ImportAirdropsRequest {
+        ImportAirdropsRequest {
+            format: format.to_string(),
+            content: content.to_string(),
+            column_mapping: None,
+            task_separator: None,
+            dry_run: false,
+        }
+    }
+
+    async fn task_titles(db: &SqlitePool) -> Vec<(String, Vec<String>)> {
+        let airdrops = sqlx::query_as::<_, (i64, String)>("SELECT id, name FROM airdrops ORDER BY position")
+            .fetch_all(db)
+            .await
+            .unwrap();
+        let mut titles = Vec::new();
+        for (id, name) in airdrops {
+            let tasks = sqlx::query_scalar::<_, String>(
+                "SELECT title FROM airdrop_daily_tasks WHERE airdrop_id = ? ORDER BY \"order\"",
+            )
+            .bind(id)
+            .fetch_all(db)
+            .await
+            .unwrap();
+            titles.push((name, tasks));
+        }
+        titles
+    }
+
+    #[test]
+    fn urls_normalize_for_duplicate_detection() {
+        assert_eq!(normalize_url("https://www.Example.xyz/quests/"), "example.xyz/quests");
+        assert_eq!(normalize_url("example.xyz/quests#top"), "example.xyz/quests");
+        assert_eq!(
+            normalize_url("http://example.xyz/q?utm_source=x&ref=abc&utm_medium=y"),
+            "example.xyz/q?ref=abc"
+        );
+        assert_eq!(normalize_url("https://example.xyz:8443/"), "example.xyz:8443");
+        assert_eq!(normalize_url("  not a url/  "), "not a url");
+    }
+
+    #[tokio::test]
+    async fn csv_export_round_trips_through_import() {
+        let source = memory_db().await;
+        let json = r#"[
+            {"name": "Bridge, Swap", "url": "https://bridge.example.xyz", "chain": "base", "notes": "Say \"gm\"",
+             "tasks": ["Swap; then bridge", "Claim, daily"]},
+            {"name": "Testnet", "url": "https://testnet.example.xyz", "active": false, "tasks": []}
+        ]"#;
+        let imported = import_records(&source, &request("json", json)).await.unwrap();
+        assert_eq!(imported.created, 2);
+
+        let csv = export_csv(&source).await.unwrap();
+        let target = memory_db().await;
+        let report = import_records(&target, &request("csv", &csv)).await.unwrap();
+        assert_eq!((report.created, report.errors), (2, 0));
+
+        assert_eq!(
+            task_titles(&target).await,
+            vec![
+                (
+                    "Bridge, Swap".to_string(),
+                    vec!["Swap; then bridge".to_string(), "Claim, daily".to_string()]
+                ),
+                ("Testnet".to_string(), Vec::new()),
+            ]
+        );
+        assert_eq!(export_csv(&target).await.unwrap(), csv);
+    }
+
+    #[tokio::test]
+    async fn text_task_cells_still_split_on_the_separator() {
+        let db = memory_db().await;
+        let csv = "name,url,tasks\nQuest,https://quest.example.xyz,Follow | Retweet\n";
+        let req = ImportAirdropsRequest {
+            task_separator: Some("|".to_string()),
+            ..request("csv", csv)
+        };
+
+        let report = import_records(&db, &req).await.unwrap();
+        assert_eq!(report.rows[0].tasks, vec!["Follow", "Retweet"]);
+    }
+
+    #[tokio::test]
+    async fn a_row_that_fails_to_save_does_not_abort_the_import() {
+        let db = memory_db().await;
+        sqlx::query(
+            "CREATE TRIGGER reject_boom BEFORE INSERT ON airdrops WHEN NEW.name = 'Boom' BEGIN SELECT RAISE(ABORT, 'rejected'); END",
+        )
+        .execute(&db)
+        .await
+        .unwrap();
+        let csv = "name,url,tasks\nFirst,https://one.example.xyz,a\nBoom,https://boom.example.xyz,b\nLast,https://last.example.xyz,c\n";
+
+        let report = import_records(&db, &request("csv", csv)).await.unwrap();
+        assert_eq!((report.created, report.errors), (2, 1));
+        assert!(matches!(report.rows[1].status, ImportRowStatus::Error));
+        assert!(report.rows[1].message.as_deref().unwrap().contains("rejected"));
+
+        let names: Vec<String> = task_titles(&db).await.into_iter().map(|(name, _)| name).collect();
+        assert_eq!(names, vec!["First", "Last"]);
+        let orphaned: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM airdrop_daily_tasks WHERE title = 'b'")
+            .fetch_one(&db)
+            .await
+            .unwrap();
+        assert_eq!(orphaned, 0);
+    }
+}
@@ -1,10 +1,11 @@
 use log::info;
 use tauri::State;
 use sqlx::SqlitePool;
-use chrono::Utc;
+use chrono::{NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 
-use crate::models::{Airdrop, AirdropType, AirdropDailyTask};
+use crate::commands::proofs::{proof_files, remove_proof_files, ProofScope};
+use crate::models::{Airdrop, AirdropType, AirdropDailyTask, TaskCompletion};
 
 #[derive(Debug, Deserialize)]
 pub struct CreateAirdropRequest {
@@ -27,12 +28,28 @@ pub struct UpdateAirdropRequest {
     pub wallet_address: Option<String>,
     pub notes: Option<String>,
     pub active: Option<bool>,
+    pub expected_value: Option<f64>,
+    pub confidence: Option<f64>,
+    /// `YYYY-MM-DD`; an empty string clears it
+    pub snapshot_date: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
 pub struct CreateAirdropTypeRequest {
     pub name: String,
     pub default_tasks: serde_json::Value,
+    pub reset_policy: Option<String>,
+    pub recommended_chains: Option<Vec<String>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateAirdropTypeRequest {
+    pub id: i64,
+    pub name: Option<String>,
+    /// Changing the tasks bumps the type's version
+    pub default_tasks: Option<serde_json::Value>,
+    pub reset_policy: Option<String>,
+    pub recommended_chains: Option<Vec<String>>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -47,12 +64,24 @@ pub async fn delete_airdrop_daily_task(
     state: State<'_, crate::AppState>,
     id: i64,
 ) -> Result<(), String> {
+    let mut tx = state
+        .db
+        .begin()
+        .await
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+    let files = proof_files(&mut *tx, ProofScope::Task(id)).await?;
+
     sqlx::query("DELETE FROM airdrop_daily_tasks WHERE id = ?")
         .bind(id)
-        .execute(&state.db)
+        .execute(&mut *tx)
         .await
         .map_err(|e| format!("Failed to delete daily task: {}", e))?;
 
+    tx.commit()
+        .await
+        .map_err(|e| format!("Failed to commit daily task deletion: {}", e))?;
+    remove_proof_files(&state.data_dir, &files);
+
     Ok(())
 }
 
@@ -137,6 +166,15 @@ pub async fn update_airdrop(
     if req.active.is_some() {
         updates.push("active = ?");
     }
+    if req.expected_value.is_some() {
+        updates.push("expected_value = ?");
+    }
+    if req.confidence.is_some() {
+        updates.push("confidence = ?");
+    }
+    if req.snapshot_date.is_some() {
+        updates.push("snapshot_date = ?");
+    }
     updates.push("updated_at = ?");
 
     let query = format!(
@@ -166,6 +204,25 @@ pub async fn update_airdrop(
     if let Some(active) = &req.active {
         q = q.bind(if *active { 1 } else { 0 });
     }
+    if let Some(expected_value) = req.expected_value {
+        q = q.bind(expected_value);
+    }
+    if let Some(confidence) = req.confidence {
+        if !(0.0..=1.0).contains(&confidence) {
+            return Err("Confidence must be between 0 and 1".to_string());
+        }
+        q = q.bind(confidence);
+    }
+    if let Some(snapshot_date) = &req.snapshot_date {
+        let snapshot_date = snapshot_date.trim();
+        if snapshot_date.is_empty() {
+            q = q.bind(None::<String>);
+        } else {
+            NaiveDate::parse_from_str(snapshot_date, "%Y-%m-%d")
+                .map_err(|_| format!("Invalid snapshot date: {}", snapshot_date))?;
+            q = q.bind(Some(snapshot_date.to_string()));
+        }
+    }
     q = q.bind(Utc::now().to_rfc3339());
     q = q.bind(req.id);
 
@@ -219,12 +276,24 @@ pub async fn reorder_airdrops(
 
 #[tauri::command]
 pub async fn delete_airdrop(state: State<'_, crate::AppState>, id: i64) -> Result<(), String> {
+    let mut tx = state
+        .db
+        .begin()
+        .await
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+    let files = proof_files(&mut *tx, ProofScope::Airdrop(id)).await?;
+
     sqlx::query("DELETE FROM airdrops WHERE id = ?")
         .bind(id)
-        .execute(&state.db)
+        .execute(&mut *tx)
         .await
         .map_err(|e| format!("Failed to delete airdrop: {}", e))?;
 
+    tx.commit()
+        .await
+        .map_err(|e| format!("Failed to commit airdrop deletion: {}", e))?;
+    remove_proof_files(&state.data_dir, &files);
+
     Ok(())
 }
 
@@ -248,15 +317,18 @@ pub async fn create_airdrop_type(
     req: CreateAirdropTypeRequest,
 ) -> Result<i64, String> {
     let now = Utc::now();
+    let reset_policy = validate_reset_policy(req.reset_policy.as_deref().unwrap_or("daily"))?;
 
     sqlx::query(
         r#"
-        INSERT INTO airdrop_types (name, default_tasks, created_at, updated_at)
-        VALUES (?, ?, ?, ?)
+        INSERT INTO airdrop_types (name, default_tasks, reset_policy, recommended_chains, created_at, updated_at)
+        VALUES (?, ?, ?, ?, ?, ?)
         "#,
     )
     .bind(&req.name)
     .bind(serde_json::to_string(&req.default_tasks).unwrap())
+    .bind(reset_policy)
+    .bind(serde_json::to_string(&req.recommended_chains.clone().unwrap_or_default()).unwrap())
     .bind(now.to_rfc3339())
     .bind(now.to_rfc3339())
     .execute(&state.db)
@@ -271,6 +343,63 @@ pub async fn create_airdrop_type(
     Ok(id)
 }
 
+#[tauri::command]
+pub async fn update_airdrop_type(
+    state: State<'_, crate::AppState>,
+    req: UpdateAirdropTypeRequest,
+) -> Result<(), String> {
+    let reset_policy = req.reset_policy.as_deref().map(validate_reset_policy).transpose()?;
+
+    let mut updates = Vec::new();
+    if req.name.is_some() {
+        updates.push("name = ?");
+    }
+    if req.default_tasks.is_some() {
+        updates.push("default_tasks = ?");
+        updates.push("version = version + 1");
+    }
+    if reset_policy.is_some() {
+        updates.push("reset_policy = ?");
+    }
+    if req.recommended_chains.is_some() {
+        updates.push("recommended_chains = ?");
+    }
+    updates.push("updated_at = ?");
+
+    let query = format!("UPDATE airdrop_types SET {} WHERE id = ?", updates.join(", "));
+
+    let mut q = sqlx::query(&query);
+    if let Some(name) = &req.name {
+        q = q.bind(name);
+    }
+    if let Some(default_tasks) = &req.default_tasks {
+        q = q.bind(serde_json::to_string(default_tasks).unwrap());
+    }
+    if let Some(reset_policy) = reset_policy {
+        q = q.bind(reset_policy);
+    }
+    if let Some(chains) = &req.recommended_chains {
+        q = q.bind(serde_json::to_string(chains).unwrap());
+    }
+    q = q.bind(Utc::now().to_rfc3339());
+    q = q.bind(req.id);
+
+    q.execute(&state.db)
+        .await
+        .map_err(|e| format!("Failed to update airdrop type: {}", e))?;
+
+    Ok(())
+}
+
+pub(crate) fn validate_reset_policy(policy: &str) -> Result<&'static str, String> {
+    match policy.trim().to_lowercase().as_str() {
+        "daily" => Ok("daily"),
+        "weekly" => Ok("weekly"),
+        "once" => Ok("once"),
+        other => Err(format!("Unsupported reset policy: {}", other)),
+    }
+}
+
 #[tauri::command]
 pub async fn list_airdrop_daily_tasks(
     state: State<'_, crate::AppState>,
@@ -322,8 +451,10 @@ pub async fn mark_task_done_today(
     state: State<'_, crate::AppState>,
     task_id: i64,
     airdrop_id: i64,
-) -> Result<(), String> {
-    let today = Utc::now().format("%Y-%m-%d").to_string();
+    wallet_id: Option<i64>,
+) -> Result<i64, String> {
+    let now = Utc::now();
+    let today = now.format("%Y-%m-%d").to_string();
     let today_for_log = today.clone(); // Clone for logging before moving
 
     // Get current done_dates
@@ -339,24 +470,226 @@ pub async fn mark_task_done_today(
         .unwrap_or_else(|_| Vec::new());
 
     if !done_dates.contains(&today) {
-        done_dates.push(today);
+        done_dates.push(today.clone());
     }
 
     sqlx::query(
         "UPDATE airdrop_daily_tasks SET done_dates = ?, updated_at = ? WHERE id = ?",
     )
     .bind(serde_json::to_string(&done_dates).unwrap())
-    .bind(Utc::now().to_rfc3339())
+    .bind(now.to_rfc3339())
     .bind(task_id)
     .execute(&state.db)
     .await
     .map_err(|e| format!("Failed to update task: {}", e))?;
 
+    // Record the completion with its wallet, defaulting to the airdrop's wallet.
+    let wallet_id = match wallet_id {
+        Some(wallet_id) => Some(wallet_id),
+        None => sqlx::query_scalar::<_, i64>(
+            r#"
+            SELECT w.id FROM wallets w
+            JOIN airdrops a ON LOWER(TRIM(a.wallet_address)) = LOWER(w.address)
+            WHERE a.id = ?
+            LIMIT 1
+            "#,
+        )
+        .bind(task.airdrop_id)
+        .fetch_optional(&state.db)
+        .await
+        .map_err(|e| format!("Failed to resolve wallet: {}", e))?,
+    };
+
+    let already_recorded = sqlx::query_scalar::<_, i64>(
+        "SELECT id FROM task_completions WHERE task_id = ? AND completed_on = ? AND wallet_id IS ?",
+    )
+    .bind(task_id)
+    .bind(&today)
+    .bind(wallet_id)
+    .fetch_optional(&state.db)
+    .await
+    .map_err(|e| format!("Failed to check completion: {}", e))?;
+
+    let completion_id = match already_recorded {
+        Some(id) => id,
+        None => sqlx::query(
+            r#"
+            INSERT INTO task_completions (task_id, airdrop_id, wallet_id, completed_on, completed_at)
+            VALUES (?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(task_id)
+        .bind(task.airdrop_id)
+        .bind(wallet_id)
+        .bind(&today)
+        .bind(now.to_rfc3339())
+        .execute(&state.db)
+        .await
+        .map_err(|e| format!("Failed to record completion: {}", e))?
+        .last_insert_rowid(),
+    };
+
     info!(
         "phase=airdrop_task_done airdrop_id={} task_id={} date={}",
         airdrop_id, task_id, today_for_log
     );
 
+    Ok(completion_id)
+}
+
+#[tauri::command]
+pub async fn list_task_completions(
+    state: State<'_, crate::AppState>,
+    airdrop_id: i64,
+) -> Result<Vec<TaskCompletion>, String> {
+    let completions = sqlx::query_as::<_, TaskCompletion>(
+        "SELECT * FROM task_completions WHERE airdrop_id = ? ORDER BY completed_at DESC",
+    )
+    .bind(airdrop_id)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|e| format!("Failed to fetch completions: {}", e))?;
+
+    Ok(completions)
+}
+
+/// Removes a completion with its proofs, and un-marks the day on the task
+/// when no other completion covers it.
+#[tauri::command]
+pub async fn delete_task_completion(
+    state: State<'_, crate::AppState>,
+    id: i64,
+) -> Result<(), String> {
+    let completion = sqlx::query_as::<_, TaskCompletion>(
+        "SELECT * FROM task_completions WHERE id = ?",
+    )
+    .bind(id)
+    .fetch_one(&state.db)
+    .await
+    .map_err(|e| format!("Failed to fetch completion: {}", e))?;
+
+    let mut tx = state
+        .db
+        .begin()
+        .await
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+    let files = proof_files(&mut *tx, ProofScope::Completion(id)).await?;
+
+    sqlx::query("DELETE FROM task_completions WHERE id = ?")
+        .bind(id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to delete completion: {}", e))?;
+
+    tx.commit()
+        .await
+        .map_err(|e| format!("Failed to commit completion deletion: {}", e))?;
+    remove_proof_files(&state.data_dir, &files);
+
+    let remaining = sqlx::query_scalar::<_, i64>(
+        "SELECT COUNT(*) FROM task_completions WHERE task_id = ? AND completed_on = ?",
+    )
+    .bind(completion.task_id)
+    .bind(&completion.completed_on)
+    .fetch_one(&state.db)
+    .await
+    .map_err(|e| format!("Failed to count completions: {}", e))?;
+
+    if remaining == 0 {
+        let task = sqlx::query_as::<_, AirdropDailyTask>(
+            "SELECT * FROM airdrop_daily_tasks WHERE id = ?",
+        )
+        .bind(completion.task_id)
+        .fetch_one(&state.db)
+        .await
+        .map_err(|e| format!("Failed to fetch task: {}", e))?;
+
+        let mut done_dates: Vec<String> = serde_json::from_value(task.done_dates)
+            .unwrap_or_else(|_| Vec::new());
+        done_dates.retain(|d| d != &completion.completed_on);
+
+        sqlx::query(
+            "UPDATE airdrop_daily_tasks SET done_dates = ?, updated_at = ? WHERE id = ?",
+        )
+        .bind(serde_json::to_string(&done_dates).unwrap())
+        .bind(Utc::now().to_rfc3339())
+        .bind(completion.task_id)
+        .execute(&state.db)
+        .await
+        .map_err(|e| format!("Failed to update task: {}", e))?;
+    }
+
+    info!(
+        "phase=airdrop_task_completion_deleted task_id={} completion_id={}",
+        completion.task_id, id
+    );
     Ok(())
 }
 
+/// Normalizes a campaign URL for duplicate detection: lowercases the host,
+/// drops `www.`, the fragment, trailing slashes and `utm_*` tracking params.
+pub(crate) fn normalize_url(raw: &str) -> String {
+    let trimmed = raw.trim();
+    let with_scheme = if trimmed.contains("://") {
+        trimmed.to_string()
+    } else {
+        format!("https://{}", trimmed)
+    };
+
+    let parsed = match url::Url::parse(&with_scheme) {
+        Ok(parsed) => parsed,
+        Err(_) => return trimmed.trim_end_matches('/').to_lowercase(),
+    };
+
+    let mut host = parsed
+        .host_str()
+        .unwrap_or_default()
+        .trim_start_matches("www.")
+        .to_lowercase();
+    if let Some(port) = parsed.port() {
+        host = format!("{}:{}", host, port);
+    }
+    let path = parsed.path().trim_end_matches('/');
+    let query: Vec<String> = parsed
+        .query_pairs()
+        .filter(|(key, _)| !key.starts_with("utm_"))
+        .map(|(key, value)| format!("{}={}", key, value))
+        .collect();
+
+    if query.is_empty() {
+        format!("{}{}", host, path)
+    } else {
+        format!("{}{}?{}", host, path, query.join("&"))
+    }
+}
+
+/// Task titles from an airdrop type's `default_tasks`, which holds either
+/// plain strings or objects with a `title`.
+pub(crate) fn default_task_titles(default_tasks: &serde_json::Value) -> Vec<String> {
+    default_task_entries(default_tasks)
+        .into_iter()
+        .map(|(title, _)| title)
+        .collect()
+}
+
+/// The entries of `default_tasks` that have a title, each with its title and
+/// the entry as stored.
+pub(crate) fn default_task_entries(default_tasks: &serde_json::Value) -> Vec<(String, &serde_json::Value)> {
+    default_tasks
+        .as_array()
+        .map(|tasks| {
+            tasks
+                .iter()
+                .filter_map(|task| {
+                    let title = match task {
+                        serde_json::Value::String(title) => title.as_str(),
+                        other => other.get("title").and_then(|t| t.as_str())?,
+                    };
+                    Some((title.trim(), task))
+                })
+                .filter(|(title, _)| !title.is_empty())
+                .map(|(title, task)| (title.to_string(), task))
+                .collect()
+        })
+        .unwrap_or_default()
+}
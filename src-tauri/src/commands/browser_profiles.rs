@@ -0,0 +1,439 @@
+use log::info;
+use tauri::{AppHandle, State};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use tauri_plugin_shell::ShellExt;
+
+use crate::models::{Airdrop, BrowserProfile};
+
+const URL_PLACEHOLDER: &str = "{url}";
+const PROFILE_PLACEHOLDER: &str = "{profile}";
+const KINDS: [&str; 3] = ["chromium", "firefox", "custom"];
+
+#[derive(Debug, Deserialize)]
+pub struct CreateBrowserProfileRequest {
+    pub name: String,
+    /// `chromium`, `firefox` or `custom`
+    pub kind: String,
+    pub executable: String,
+    pub profile: String,
+    /// Required for `custom`; defaults to the kind's usual arguments otherwise
+    pub args_template: Option<Vec<String>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateBrowserProfileRequest {
+    pub id: i64,
+    pub name: Option<String>,
+    pub executable: Option<String>,
+    pub profile: Option<String>,
+    pub args_template: Option<Vec<String>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct OpenAirdropResult {
+    pub browser_profile_id: Option<i64>,
+    pub executable: Option<String>,
+    pub args: Vec<String>,
+}
+
+#[tauri::command]
+pub async fn list_browser_profiles(state: State<'_, crate::AppState>) -> Result<Vec<BrowserProfile>, String> {
+    let profiles = sqlx::query_as::<_, BrowserProfile>("SELECT * FROM browser_profiles ORDER BY name ASC")
+        .fetch_all(&state.db)
+        .await
+        .map_err(|e| format!("Failed to fetch browser profiles: {}", e))?;
+
+    Ok(profiles)
+}
+
+#[tauri::command]
+pub async fn create_browser_profile(
+    state: State<'_, crate::AppState>,
+    req: CreateBrowserProfileRequest,
+) -> Result<i64, String> {
+    let kind = req.kind.trim().to_lowercase();
+    if !KINDS.contains(&kind.as_str()) {
+        return Err(format!("Unknown browser kind: {}", req.kind));
+    }
+    let args_template = match req.args_template {
+        Some(args) => args,
+        None => default_args_template(&kind)
+            .ok_or_else(|| format!("An argument template is required for {} browsers", kind))?,
+    };
+    validate_executable(&req.executable)?;
+    validate_profile(&req.profile)?;
+    validate_args_template(&args_template)?;
+    let now = Utc::now();
+
+    let id = sqlx::query(
+        r#"
+        INSERT INTO browser_profiles (name, kind, executable, profile, args_template, created_at, updated_at)
+        VALUES (?, ?, ?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(req.name.trim())
+    .bind(&kind)
+    .bind(req.executable.trim())
+    .bind(req.profile.trim())
+    .bind(serde_json::to_string(&args_template).unwrap())
+    .bind(now.to_rfc3339())
+    .bind(now.to_rfc3339())
+    .execute(&state.db)
+    .await
+    .map_err(|e| format!("Failed to create browser profile: {}", e))?
+    .last_insert_rowid();
+
+    info!("phase=browser_profile_create_ok id={} kind={}", id, kind);
+    Ok(id)
+}
+
+#[tauri::command]
+pub async fn update_browser_profile(
+    state: State<'_, crate::AppState>,
+    req: UpdateBrowserProfileRequest,
+) -> Result<(), String> {
+    if let Some(executable) = &req.executable {
+        validate_executable(executable)?;
+    }
+    if let Some(profile) = &req.profile {
+        validate_profile(profile)?;
+    }
+    if let Some(args_template) = &req.args_template {
+        validate_args_template(args_template)?;
+    }
+
+    let mut updates = Vec::new();
+    if req.name.is_some() {
+        updates.push("name = ?");
+    }
+    if req.executable.is_some() {
+        updates.push("executable = ?");
+    }
+    if req.profile.is_some() {
+        updates.push("profile = ?");
+    }
+    if req.args_template.is_some() {
+        updates.push("args_template = ?");
+    }
+    updates.push("updated_at = ?");
+
+    let query = format!("UPDATE browser_profiles SET {} WHERE id = ?", updates.join(", "));
+
+    let mut q = sqlx::query(&query);
+    if let Some(name) = &req.name {
+        q = q.bind(name.trim());
+    }
+    if let Some(executable) = &req.executable {
+        q = q.bind(executable.trim());
+    }
+    if let Some(profile) = &req.profile {
+        q = q.bind(profile.trim());
+    }
+    if let Some(args_template) = &req.args_template {
+        q = q.bind(serde_json::to_string(args_template).unwrap());
+    }
+    q = q.bind(Utc::now().to_rfc3339());
+    q = q.bind(req.id);
+
+    q.execute(&state.db)
+        .await
+        .map_err(|e| format!("Failed to update browser profile: {}", e))?;
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn delete_browser_profile(state: State<'_, crate::AppState>, id: i64) -> Result<(), String> {
+    sqlx::query("DELETE FROM browser_profiles WHERE id = ?")
+        .bind(id)
+        .execute(&state.db)
+        .await
+        .map_err(|e| format!("Failed to delete browser profile: {}", e))?;
+
+    Ok(())
+}
+
+/// Maps an airdrop to a browser profile; `None` falls back to its wallet's profile.
+#[tauri::command]
+pub async fn set_airdrop_browser_profile(
+    state: State<'_, crate::AppState>,
+    airdrop_id: i64,
+    browser_profile_id: Option<i64>,
+) -> Result<(), String> {
+    sqlx::query("UPDATE airdrops SET browser_profile_id = ?, updated_at = ? WHERE id = ?")
+        .bind(browser_profile_id)
+        .bind(Utc::now().to_rfc3339())
+        .bind(airdrop_id)
+        .execute(&state.db)
+        .await
+        .map_err(|e| format!("Failed to set airdrop browser profile: {}", e))?;
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn set_wallet_browser_profile(
+    state: State<'_, crate::AppState>,
+    wallet_id: i64,
+    browser_profile_id: Option<i64>,
+) -> Result<(), String> {
+    sqlx::query("UPDATE wallets SET browser_profile_id = ?, updated_at = ? WHERE id = ?")
+        .bind(browser_profile_id)
+        .bind(Utc::now().to_rfc3339())
+        .bind(wallet_id)
+        .execute(&state.db)
+        .await
+        .map_err(|e| format!("Failed to set wallet browser profile: {}", e))?;
+
+    Ok(())
+}
+
+/// Opens the airdrop's URL in the browser profile mapped to the airdrop, or
+/// else to its wallet. Refuses when the two mappings disagree, and only uses
+/// the system browser when `use_default_browser` is set.
+#[tauri::command]
+pub async fn open_airdrop(
+    app: AppHandle,
+    state: State<'_, crate::AppState>,
+    airdrop_id: i64,
+    use_default_browser: Option<bool>,
+) -> Result<OpenAirdropResult, String> {
+    let airdrop = sqlx::query_as::<_, Airdrop>("SELECT * FROM airdrops WHERE id = ?")
+        .bind(airdrop_id)
+        .fetch_optional(&state.db)
+        .await
+        .map_err(|e| format!("Failed to fetch airdrop: {}", e))?
+        .ok_or_else(|| format!("Airdrop {} not found", airdrop_id))?;
+    let url = validate_launch_url(&airdrop.url)?;
+
+    let wallet_profile_id = match airdrop.wallet_address.as_deref().map(str::trim) {
+        Some(address) if !address.is_empty() => sqlx::query_scalar::<_, Option<i64>>(
+            "SELECT browser_profile_id FROM wallets WHERE LOWER(address) = LOWER(?)",
+        )
+        .bind(address)
+        .fetch_optional(&state.db)
+        .await
+        .map_err(|e| format!("Failed to fetch wallet: {}", e))?
+        .flatten(),
+        _ => None,
+    };
+
+    let profile_id = match (airdrop.browser_profile_id, wallet_profile_id) {
+        (Some(a), Some(w)) if a != w => {
+            return Err(format!(
+                "{} is mapped to a different browser profile than its wallet; fix the mapping before opening it",
+                airdrop.name
+            ));
+        }
+        (Some(id), _) | (None, Some(id)) => Some(id),
+        (None, None) => None,
+    };
+
+    let Some(profile_id) = profile_id else {
+        if !use_default_browser.unwrap_or(false) {
+            return Err(format!("{} has no browser profile mapped", airdrop.name));
+        }
+        open_in_default_browser(&app, &url)?;
+        info!("phase=open_airdrop_ok id={} profile=default", airdrop_id);
+        return Ok(OpenAirdropResult {
+            browser_profile_id: None,
+            executable: None,
+            args: vec![url],
+        });
+    };
+
+    let profile = sqlx::query_as::<_, BrowserProfile>("SELECT * FROM browser_profiles WHERE id = ?")
+        .bind(profile_id)
+        .fetch_optional(&state.db)
+        .await
+        .map_err(|e| format!("Failed to fetch browser profile: {}", e))?
+        .ok_or_else(|| format!("Browser profile {} not found", profile_id))?;
+
+    let args = render_args(&profile, &url)?;
+    app.shell()
+        .command(&profile.executable)
+        .args(&args)
+        .spawn()
+        .map_err(|e| format!("Failed to launch {}: {}", profile.executable, e))?;
+
+    info!("phase=open_airdrop_ok id={} profile_id={}", airdrop_id, profile.id);
+    Ok(OpenAirdropResult {
+        browser_profile_id: Some(profile.id),
+        executable: Some(profile.executable),
+        args,
+    })
+}
+
+fn default_args_template(kind: &str) -> Option<Vec<String>> {
+    let args: &[&str] = match kind {
+        "chromium" => &["--profile-directory={profile}", "--new-window", URL_PLACEHOLDER],
+        "firefox" => &["-P", PROFILE_PLACEHOLDER, "-new-tab", URL_PLACEHOLDER],
+        _ => return None,
+    };
+    Some(args.iter().map(|a| a.to_string()).collect())
+}
+
+/// Each template entry becomes exactly one argument and nothing goes through
+/// a shell, so validation only has to keep placeholders well-formed.
+fn validate_args_template(args: &[String]) -> Result<(), String> {
+    let mut has_url = false;
+    for arg in args {
+        let mut rest = arg.as_str();
+        while let Some(start) = rest.find('{') {
+            let end = rest[start..]
+                .find('}')
+                .map(|end| start + end)
+                .ok_or_else(|| format!("Unclosed placeholder in argument: {}", arg))?;
+            match &rest[start..=end] {
+                URL_PLACEHOLDER => has_url = true,
+                PROFILE_PLACEHOLDER => {}
+                other => return Err(format!("Unknown placeholder {} in argument: {}", other, arg)),
+            }
+            rest = &rest[end + 1..];
+        }
+        if arg.contains('\0') {
+            return Err("Arguments cannot contain NUL characters".to_string());
+        }
+    }
+    if !has_url {
+        return Err(format!("The argument template must include {}", URL_PLACEHOLDER));
+    }
+    Ok(())
+}
+
+fn validate_executable(executable: &str) -> Result<(), String> {
+    let executable = executable.trim();
+    if executable.is_empty() {
+        return Err("Browser executable is required".to_string());
+    }
+    let path = Path::new(executable);
+    // Bare names (e.g. `firefox`) are resolved from PATH at launch.
+    if path.components().count() > 1 && !path.is_file() {
+        return Err(format!("Browser executable not found: {}", executable));
+    }
+    Ok(())
+}
+
+/// Profiles are substituted into arguments, so a leading dash could smuggle
+/// in an extra browser flag and a `{url}` would be replaced by the URL.
+fn validate_profile(profile: &str) -> Result<(), String> {
+    let profile = profile.trim();
+    if profile.is_empty() {
+        return Err("Profile is required".to_string());
+    }
+    if profile.starts_with('-') || profile.contains(URL_PLACEHOLDER) || profile.chars().any(|c| c.is_control()) {
+        return Err(format!("Invalid profile name: {}", profile));
+    }
+    Ok(())
+}
+
+fn validate_launch_url(raw: &str) -> Result<String, String> {
+    let url = url::Url::parse(raw.trim()).map_err(|e| format!("Invalid airdrop URL: {}", e))?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(format!("Only http(s) URLs can be opened: {}", raw));
+    }
+    Ok(url.to_string())
+}
+
+fn render_args(profile: &BrowserProfile, url: &str) -> Result<Vec<String>, String> {
+    validate_args_template(&profile.args_template)?;
+    validate_profile(&profile.profile)?;
+    Ok(profile
+        .args_template
+        .iter()
+        .map(|arg| {
+            arg.replace(PROFILE_PLACEHOLDER, profile.profile.trim())
+                .replace(URL_PLACEHOLDER, url)
+        })
+        .collect())
+}
+
+fn open_in_default_browser(app: &AppHandle, url: &str) -> Result<(), String> {
+    let (program, args): (&str, Vec<&str>) = if cfg!(target_os = "windows") {
+        ("explorer", vec![url])
+    } else if cfg!(target_os = "macos") {
+        ("open", vec![url])
+    } else {
+        ("xdg-open", vec![url])
+    };
+    app.shell()
+        .command(program)
+        .args(args)
+        .spawn()
+        .map_err(|e| format!("Failed to open default browser: {}", e))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn template(args: &[&str]) -> Vec<String> {
+        args.iter().map(|a| a.to_string()).collect()
+    }
+
+    fn profile(name: &str, args: &[&str]) -> BrowserProfile {
+        BrowserProfile {
+            id: 1,
+            name: "Farming".to_string(),
+            kind: "custom".to_string(),
+            executable: "browser".to_string(),
+            profile: name.to_string(),
+            args_template: template(args),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn templates_need_known_placeholders_and_a_url() {
+        assert!(validate_args_template(&default_args_template("chromium").unwrap()).is_ok());
+        assert!(validate_args_template(&default_args_template("firefox").unwrap()).is_ok());
+
+        let err = validate_args_template(&template(&["--user={user}", "{url}"])).unwrap_err();
+        assert!(err.contains("Unknown placeholder {user}"), "{}", err);
+        assert!(validate_args_template(&template(&["--profile={profile", "{url}"])).is_err());
+        assert!(validate_args_template(&template(&["-P", "{profile}"])).is_err());
+        assert!(validate_args_template(&[]).is_err());
+    }
+
+    #[test]
+    fn profiles_cannot_inject_flags_or_placeholders() {
+        assert!(validate_profile("Profile 1").is_ok());
+        assert!(validate_profile("  ").is_err());
+        assert!(validate_profile("--incognito").is_err());
+        assert!(validate_profile("evil{url}").is_err());
+        assert!(validate_profile("line\nbreak").is_err());
+    }
+
+    #[test]
+    fn rendered_values_stay_single_arguments() {
+        let chromium = BrowserProfile {
+            args_template: default_args_template("chromium").unwrap(),
+            ..profile("Profile 2", &[])
+        };
+        assert_eq!(
+            render_args(&chromium, "https://example.com/?a=1&b=two words").unwrap(),
+            template(&[
+                "--profile-directory=Profile 2",
+                "--new-window",
+                "https://example.com/?a=1&b=two words",
+            ])
+        );
+
+        // Quotes are passed through as-is; nothing goes through a shell
+        let firefox = profile(r#"Bob's "main""#, &["-P", "{profile}", "{url}"]);
+        assert_eq!(
+            render_args(&firefox, "https://example.com/{profile}").unwrap(),
+            template(&["-P", r#"Bob's "main""#, "https://example.com/{profile}"])
+        );
+    }
+
+    #[test]
+    fn rendering_rechecks_stored_profiles() {
+        assert!(render_args(&profile("-safe-mode", &["-P", "{profile}", "{url}"]), "https://example.com").is_err());
+        assert!(render_args(&profile("main", &[]), "https://example.com").is_err());
+    }
+}
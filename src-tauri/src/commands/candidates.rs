@@ -0,0 +1,557 @@
+use log::{info, warn};
+use tauri::{AppHandle, Emitter, State};
+use sqlx::{Sqlite, SqlitePool, Transaction};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use crate::commands::airdrops::{default_task_titles, normalize_url};
+use crate::models::{Airdrop, AirdropCandidate, AirdropType, CandidateSource};
+
+/// How often the background poller refreshes every candidate source.
+const CANDIDATE_POLL_INTERVAL: Duration = Duration::from_secs(60 * 60);
+const CANDIDATE_TIMEOUT: Duration = Duration::from_secs(20);
+
+#[derive(Debug, Deserialize)]
+pub struct CreateCandidateSourceRequest {
+    pub name: String,
+    pub location: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AcceptAirdropCandidateRequest {
+    pub id: i64,
+    pub airdrop_type_id: Option<i64>,
+    /// Overrides for the candidate's own values
+    pub name: Option<String>,
+    pub chain: Option<String>,
+    pub wallet_address: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CandidateFetchResult {
+    pub source_id: i64,
+    pub new_candidates: usize,
+    pub duplicates: usize,
+    pub error: Option<String>,
+}
+
+#[derive(Debug)]
+struct ParsedCandidate {
+    name: String,
+    url: String,
+    chain: Option<String>,
+    description: Option<String>,
+    tasks: Vec<String>,
+}
+
+#[tauri::command]
+pub async fn list_candidate_sources(state: State<'_, crate::AppState>) -> Result<Vec<CandidateSource>, String> {
+    let sources = sqlx::query_as::<_, CandidateSource>("SELECT * FROM candidate_sources ORDER BY name ASC")
+        .fetch_all(&state.db)
+        .await
+        .map_err(|e| format!("Failed to fetch candidate sources: {}", e))?;
+
+    Ok(sources)
+}
+
+#[tauri::command]
+pub async fn create_candidate_source(
+    state: State<'_, crate::AppState>,
+    req: CreateCandidateSourceRequest,
+) -> Result<i64, String> {
+    let location = req.location.trim();
+    if location.is_empty() {
+        return Err("Source location is required".to_string());
+    }
+    let now = Utc::now();
+
+    let id = sqlx::query(
+        r#"
+        INSERT INTO candidate_sources (name, location, created_at, updated_at)
+        VALUES (?, ?, ?, ?)
+        "#,
+    )
+    .bind(req.name.trim())
+    .bind(location)
+    .bind(now.to_rfc3339())
+    .bind(now.to_rfc3339())
+    .execute(&state.db)
+    .await
+    .map_err(|e| format!("Failed to create candidate source: {}", e))?
+    .last_insert_rowid();
+
+    info!("phase=candidate_source_create_ok id={}", id);
+    Ok(id)
+}
+
+#[tauri::command]
+pub async fn delete_candidate_source(state: State<'_, crate::AppState>, id: i64) -> Result<(), String> {
+    sqlx::query("DELETE FROM candidate_sources WHERE id = ?")
+        .bind(id)
+        .execute(&state.db)
+        .await
+        .map_err(|e| format!("Failed to delete candidate source: {}", e))?;
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn refresh_candidate_sources(
+    state: State<'_, crate::AppState>,
+    source_id: Option<i64>,
+) -> Result<Vec<CandidateFetchResult>, String> {
+    fetch_candidates(&state.db, source_id).await
+}
+
+#[tauri::command]
+pub async fn list_airdrop_candidates(
+    state: State<'_, crate::AppState>,
+    status: Option<String>,
+) -> Result<Vec<AirdropCandidate>, String> {
+    let candidates = sqlx::query_as::<_, AirdropCandidate>(
+        "SELECT * FROM airdrop_candidates WHERE (? IS NULL OR status = ?) ORDER BY created_at DESC",
+    )
+    .bind(&status)
+    .bind(&status)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|e| format!("Failed to fetch candidates: {}", e))?;
+
+    Ok(candidates)
+}
+
+/// Turns a candidate into an airdrop, seeding its daily tasks from the chosen
+/// type's defaults followed by any tasks listed with the candidate. When the
+/// URL is already tracked, the candidate is linked to that airdrop instead.
+#[tauri::command]
+pub async fn accept_airdrop_candidate(
+    state: State<'_, crate::AppState>,
+    req: AcceptAirdropCandidateRequest,
+) -> Result<i64, String> {
+    accept_candidate(&state.db, &req).await
+}
+
+pub(crate) async fn accept_candidate(db: &SqlitePool, req: &AcceptAirdropCandidateRequest) -> Result<i64, String> {
+    let candidate = sqlx::query_as::<_, AirdropCandidate>("SELECT * FROM airdrop_candidates WHERE id = ?")
+        .bind(req.id)
+        .fetch_optional(db)
+        .await
+        .map_err(|e| format!("Failed to fetch candidate: {}", e))?
+        .ok_or_else(|| format!("Candidate {} not found", req.id))?;
+    if candidate.status == "accepted" {
+        return Err(format!("{} was already accepted", candidate.name));
+    }
+
+    let mut tasks = Vec::new();
+    if let Some(type_id) = req.airdrop_type_id {
+        let airdrop_type = sqlx::query_as::<_, AirdropType>("SELECT * FROM airdrop_types WHERE id = ?")
+            .bind(type_id)
+            .fetch_optional(db)
+            .await
+            .map_err(|e| format!("Failed to fetch airdrop type: {}", e))?
+            .ok_or_else(|| format!("Airdrop type {} not found", type_id))?;
+        tasks.extend(default_task_titles(&airdrop_type.default_tasks));
+    }
+    let mut seen: HashSet<String> = tasks.iter().map(|t| t.to_lowercase()).collect();
+    for task in &candidate.tasks {
+        if seen.insert(task.to_lowercase()) {
+            tasks.push(task.clone());
+        }
+    }
+
+    let mut tx = db
+        .begin()
+        .await
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+
+    // Claim the candidate first so the write lock is held while checking for
+    // an airdrop that already tracks its URL
+    let now = Utc::now().to_rfc3339();
+    let claimed = sqlx::query("UPDATE airdrop_candidates SET status = 'accepted', updated_at = ? WHERE id = ? AND status != 'accepted'")
+        .bind(&now)
+        .bind(req.id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to update candidate: {}", e))?
+        .rows_affected();
+    if claimed == 0 {
+        return Err(format!("{} was already accepted", candidate.name));
+    }
+
+    let normalized = normalize_url(&candidate.url);
+    let tracked = sqlx::query_as::<_, (i64, String)>("SELECT id, url FROM airdrops ORDER BY id ASC")
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to fetch airdrops: {}", e))?
+        .into_iter()
+        .find(|(_, url)| normalize_url(url) == normalized)
+        .map(|(id, _)| id);
+    if let Some(airdrop_id) = tracked {
+        link_candidate(&mut tx, req.id, airdrop_id).await?;
+        tx.commit()
+            .await
+            .map_err(|e| format!("Failed to accept candidate: {}", e))?;
+
+        info!("phase=candidate_accept_linked id={} airdrop_id={}", req.id, airdrop_id);
+        return Ok(airdrop_id);
+    }
+
+    let position = sqlx::query_scalar::<_, i64>("SELECT COALESCE(MAX(position), -1) + 1 FROM airdrops")
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to read airdrop positions: {}", e))?;
+
+    let airdrop_id = sqlx::query(
+        r#"
+        INSERT INTO airdrops (name, url, airdrop_type_id, chain, wallet_address, position, notes, active, created_at, updated_at)
+        VALUES (?, ?, ?, ?, ?, ?, ?, 1, ?, ?)
+        "#,
+    )
+    .bind(req.name.as_deref().unwrap_or(&candidate.name))
+    .bind(&candidate.url)
+    .bind(req.airdrop_type_id)
+    .bind(req.chain.as_ref().or(candidate.chain.as_ref()))
+    .bind(&req.wallet_address)
+    .bind(position)
+    .bind(&candidate.description)
+    .bind(&now)
+    .bind(&now)
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| format!("Failed to create airdrop: {}", e))?
+    .last_insert_rowid();
+
+    for (order, title) in tasks.iter().enumerate() {
+        sqlx::query(
+            r#"
+            INSERT INTO airdrop_daily_tasks (airdrop_id, title, "order", done_dates, created_at, updated_at)
+            VALUES (?, ?, ?, '[]', ?, ?)
+            "#,
+        )
+        .bind(airdrop_id)
+        .bind(title)
+        .bind(order as i64)
+        .bind(&now)
+        .bind(&now)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to create daily task: {}", e))?;
+    }
+
+    link_candidate(&mut tx, req.id, airdrop_id).await?;
+
+    tx.commit()
+        .await
+        .map_err(|e| format!("Failed to accept candidate: {}", e))?;
+
+    info!("phase=candidate_accept_ok id={} airdrop_id={}", req.id, airdrop_id);
+    Ok(airdrop_id)
+}
+
+async fn link_candidate(tx: &mut Transaction<'_, Sqlite>, candidate_id: i64, airdrop_id: i64) -> Result<(), String> {
+    sqlx::query("UPDATE airdrop_candidates SET airdrop_id = ? WHERE id = ?")
+        .bind(airdrop_id)
+        .bind(candidate_id)
+        .execute(&mut **tx)
+        .await
+        .map_err(|e| format!("Failed to update candidate: {}", e))?;
+
+    Ok(())
+}
+
+/// Dismissed candidates stay in the table so the same lead isn't offered again.
+#[tauri::command]
+pub async fn dismiss_airdrop_candidate(state: State<'_, crate::AppState>, id: i64) -> Result<(), String> {
+    sqlx::query("UPDATE airdrop_candidates SET status = 'dismissed', updated_at = ? WHERE id = ? AND status = 'new'")
+        .bind(Utc::now().to_rfc3339())
+        .bind(id)
+        .execute(&state.db)
+        .await
+        .map_err(|e| format!("Failed to dismiss candidate: {}", e))?;
+
+    Ok(())
+}
+
+pub(crate) fn spawn_candidate_poller(app: AppHandle, db: SqlitePool) {
+    tauri::async_runtime::spawn(async move {
+        let mut interval = tokio::time::interval(CANDIDATE_POLL_INTERVAL);
+        loop {
+            interval.tick().await;
+            match fetch_candidates(&db, None).await {
+                Ok(results) => {
+                    let fresh: Vec<CandidateFetchResult> =
+                        results.into_iter().filter(|r| r.new_candidates > 0).collect();
+                    if !fresh.is_empty() {
+                        let _ = app.emit("airdrop-candidates-new", fresh);
+                    }
+                }
+                Err(e) => warn!("phase=candidate_poll_failed error={}", e),
+            }
+        }
+    });
+}
+
+/// Reads one source (or all sources) and queues candidates whose normalized
+/// URL matches neither an existing airdrop nor an earlier candidate.
+pub(crate) async fn fetch_candidates(
+    db: &SqlitePool,
+    source_id: Option<i64>,
+) -> Result<Vec<CandidateFetchResult>, String> {
+    let sources = sqlx::query_as::<_, CandidateSource>(
+        "SELECT * FROM candidate_sources WHERE (? IS NULL OR id = ?)",
+    )
+    .bind(source_id)
+    .bind(source_id)
+    .fetch_all(db)
+    .await
+    .map_err(|e| format!("Failed to fetch candidate sources: {}", e))?;
+
+    let client = reqwest::Client::builder()
+        .timeout(CANDIDATE_TIMEOUT)
+        .build()
+        .map_err(|e| format!("Failed to build HTTP client: {}", e))?;
+
+    let mut results = Vec::with_capacity(sources.len());
+    for source in &sources {
+        let now = Utc::now().to_rfc3339();
+        let parsed = match read_source(&client, &source.location).await {
+            Ok(body) => parse_candidates(&body),
+            Err(e) => Err(e),
+        };
+
+        let result = match parsed {
+            Ok(candidates) => {
+                let (new_candidates, duplicates) = store_candidates(db, source.id, candidates).await?;
+                CandidateFetchResult {
+                    source_id: source.id,
+                    new_candidates,
+                    duplicates,
+                    error: None,
+                }
+            }
+            Err(e) => {
+                warn!("phase=candidate_fetch_failed source_id={} error={}", source.id, e);
+                CandidateFetchResult {
+                    source_id: source.id,
+                    new_candidates: 0,
+                    duplicates: 0,
+                    error: Some(e),
+                }
+            }
+        };
+
+        sqlx::query("UPDATE candidate_sources SET last_fetched_at = ?, last_error = ?, updated_at = ? WHERE id = ?")
+            .bind(&now)
+            .bind(&result.error)
+            .bind(&now)
+            .bind(source.id)
+            .execute(db)
+            .await
+            .map_err(|e| format!("Failed to update candidate source: {}", e))?;
+
+        info!(
+            "phase=candidate_fetch_done source_id={} new={} duplicates={}",
+            source.id, result.new_candidates, result.duplicates
+        );
+        results.push(result);
+    }
+
+    Ok(results)
+}
+
+async fn store_candidates(
+    db: &SqlitePool,
+    source_id: i64,
+    candidates: Vec<ParsedCandidate>,
+) -> Result<(usize, usize), String> {
+    let airdrops = sqlx::query_as::<_, Airdrop>("SELECT * FROM airdrops")
+        .fetch_all(db)
+        .await
+        .map_err(|e| format!("Failed to fetch airdrops: {}", e))?;
+    let tracked: HashSet<String> = airdrops.iter().map(|a| normalize_url(&a.url)).collect();
+
+    let mut new_candidates = 0;
+    let mut duplicates = 0;
+    for candidate in candidates {
+        let normalized = normalize_url(&candidate.url);
+        if tracked.contains(&normalized) {
+            duplicates += 1;
+            continue;
+        }
+
+        let now = Utc::now().to_rfc3339();
+        let inserted = sqlx::query(
+            r#"
+            INSERT OR IGNORE INTO airdrop_candidates (source_id, name, url, normalized_url, chain, description, tasks, status, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, 'new', ?, ?)
+            "#,
+        )
+        .bind(source_id)
+        .bind(&candidate.name)
+        .bind(&candidate.url)
+        .bind(&normalized)
+        .bind(&candidate.chain)
+        .bind(&candidate.description)
+        .bind(serde_json::to_string(&candidate.tasks).unwrap())
+        .bind(&now)
+        .bind(&now)
+        .execute(db)
+        .await
+        .map_err(|e| format!("Failed to store candidate: {}", e))?
+        .rows_affected();
+
+        if inserted > 0 {
+            new_candidates += 1;
+        } else {
+            duplicates += 1;
+        }
+    }
+
+    Ok((new_candidates, duplicates))
+}
+
+async fn read_source(client: &reqwest::Client, location: &str) -> Result<String, String> {
+    let location = location.trim();
+    if location.starts_with("http://") || location.starts_with("https://") {
+        let response = client
+            .get(location)
+            .send()
+            .await
+            .map_err(|e| format!("Request failed: {}", e))?;
+        if !response.status().is_success() {
+            return Err(format!("Source returned HTTP {}", response.status()));
+        }
+        return response
+            .text()
+            .await
+            .map_err(|e| format!("Failed to read response: {}", e));
+    }
+
+    let path = if location.starts_with("file://") {
+        url::Url::parse(location)
+            .ok()
+            .and_then(|u| u.to_file_path().ok())
+            .ok_or_else(|| format!("Invalid file URL: {}", location))?
+    } else {
+        PathBuf::from(location)
+    };
+    tokio::fs::read_to_string(&path)
+        .await
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))
+}
+
+/// Accepts a bare array or an object wrapping it under `candidates`,
+/// `airdrops` or `items`. Entries without a name and URL are skipped.
+fn parse_candidates(body: &str) -> Result<Vec<ParsedCandidate>, String> {
+    let value: serde_json::Value =
+        serde_json::from_str(body).map_err(|e| format!("Invalid candidate JSON: {}", e))?;
+    let entries = match &value {
+        serde_json::Value::Array(entries) => entries,
+        other => ["candidates", "airdrops", "items"]
+            .iter()
+            .find_map(|key| other.get(key).and_then(|v| v.as_array()))
+            .ok_or("Expected a JSON array of candidates")?,
+    };
+
+    let text = |entry: &serde_json::Value, keys: &[&str]| {
+        keys.iter()
+            .find_map(|key| entry.get(key).and_then(|v| v.as_str()))
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+            .map(str::to_string)
+    };
+
+    Ok(entries
+        .iter()
+        .filter_map(|entry| {
+            let url = text(entry, &["url", "link", "website"])?;
+            let name = text(entry, &["name", "title"])?;
+            let tasks = entry
+                .get("tasks")
+                .map(default_task_titles)
+                .unwrap_or_default();
+            Some(ParsedCandidate {
+                name,
+                url,
+                chain: text(entry, &["chain", "network"]),
+                description: text(entry, &["description", "notes", "summary"]),
+                tasks,
+            })
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::memory_db;
+
+    async fn seed_candidate(db: &SqlitePool, url: &str) -> i64 {
+        let now = Utc::now().to_rfc3339();
+        sqlx::query(
+            "INSERT INTO airdrop_candidates (name, url, normalized_url, tasks, created_at, updated_at) VALUES ('Lead', ?, ?, '[\"Bridge\"]', ?, ?)",
+        )
+        .bind(url)
+        .bind(normalize_url(url))
+        .bind(&now)
+        .bind(&now)
+        .execute(db)
+        .await
+        .unwrap()
+        .last_insert_rowid()
+    }
+
+    fn accept(id: i64) -> AcceptAirdropCandidateRequest {
+        AcceptAirdropCandidateRequest { id, airdrop_type_id: None, name: None, chain: None, wallet_address: None }
+    }
+
+    async fn airdrop_count(db: &SqlitePool) -> i64 {
+        sqlx::query_scalar("SELECT COUNT(*) FROM airdrops").fetch_one(db).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn accepting_creates_an_airdrop_with_the_candidate_tasks() {
+        let db = memory_db().await;
+        let id = seed_candidate(&db, "https://lead.example/app").await;
+
+        let airdrop_id = accept_candidate(&db, &accept(id)).await.unwrap();
+
+        let titles: Vec<String> = sqlx::query_scalar("SELECT title FROM airdrop_daily_tasks WHERE airdrop_id = ?")
+            .bind(airdrop_id)
+            .fetch_all(&db)
+            .await
+            .unwrap();
+        assert_eq!(titles, vec!["Bridge"]);
+        let err = accept_candidate(&db, &accept(id)).await.unwrap_err();
+        assert!(err.contains("already accepted"), "{}", err);
+        assert_eq!(airdrop_count(&db).await, 1);
+    }
+
+    #[tokio::test]
+    async fn accepting_a_tracked_url_links_the_existing_airdrop() {
+        let db = memory_db().await;
+        let now = Utc::now().to_rfc3339();
+        let existing = sqlx::query("INSERT INTO airdrops (name, url, created_at, updated_at) VALUES ('Lead', 'https://www.lead.example/app/', ?, ?)")
+            .bind(&now)
+            .bind(&now)
+            .execute(&db)
+            .await
+            .unwrap()
+            .last_insert_rowid();
+        let id = seed_candidate(&db, "https://lead.example/app").await;
+
+        let airdrop_id = accept_candidate(&db, &accept(id)).await.unwrap();
+
+        assert_eq!(airdrop_id, existing);
+        assert_eq!(airdrop_count(&db).await, 1);
+        let candidate = sqlx::query_as::<_, AirdropCandidate>("SELECT * FROM airdrop_candidates WHERE id = ?")
+            .bind(id)
+            .fetch_one(&db)
+            .await
+            .unwrap();
+        assert_eq!((candidate.status.as_str(), candidate.airdrop_id), ("accepted", Some(existing)));
+    }
+}
@@ -0,0 +1,259 @@
+use log::info;
+use tauri::State;
+use chrono::Utc;
+use serde::Deserialize;
+
+use crate::commands::transactions::{is_evm_address, MAX_TOKEN_DECIMALS};
+use crate::models::{Chain, ChainToken};
+use crate::rpc;
+
+#[derive(Debug, Deserialize)]
+pub struct CreateChainRequest {
+    pub name: String,
+    pub chain_id: Option<i64>,
+    pub rpc_url: String,
+    pub native_symbol: Option<String>,
+    pub gas_threshold_gwei: Option<f64>,
+    pub gas_alerts_enabled: Option<bool>,
+    pub min_native_balance: Option<f64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateChainRequest {
+    pub id: i64,
+    pub name: Option<String>,
+    pub chain_id: Option<i64>,
+    pub rpc_url: Option<String>,
+    pub native_symbol: Option<String>,
+    /// A negative threshold clears it
+    pub gas_threshold_gwei: Option<f64>,
+    pub gas_alerts_enabled: Option<bool>,
+    /// A negative minimum clears it
+    pub min_native_balance: Option<f64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateChainTokenRequest {
+    pub chain_id: i64,
+    pub symbol: String,
+    pub address: String,
+    /// Read from the contract when omitted
+    pub decimals: Option<u32>,
+}
+
+#[tauri::command]
+pub async fn list_chains(state: State<'_, crate::AppState>) -> Result<Vec<Chain>, String> {
+    let chains = sqlx::query_as::<_, Chain>("SELECT * FROM chains ORDER BY name ASC")
+        .fetch_all(&state.db)
+        .await
+        .map_err(|e| format!("Failed to fetch chains: {}", e))?;
+
+    Ok(chains)
+}
+
+#[tauri::command]
+pub async fn create_chain(
+    state: State<'_, crate::AppState>,
+    req: CreateChainRequest,
+) -> Result<i64, String> {
+    let rpc_url = validate_rpc_url(&req.rpc_url)?;
+    let now = Utc::now();
+
+    let id = sqlx::query(
+        r#"
+        INSERT INTO chains (name, chain_id, rpc_url, native_symbol, gas_threshold_gwei, gas_alerts_enabled, min_native_balance, created_at, updated_at)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(req.name.trim())
+    .bind(req.chain_id)
+    .bind(&rpc_url)
+    .bind(req.native_symbol.as_deref().unwrap_or("ETH"))
+    .bind(req.gas_threshold_gwei)
+    .bind(if req.gas_alerts_enabled.unwrap_or(true) { 1 } else { 0 })
+    .bind(req.min_native_balance)
+    .bind(now.to_rfc3339())
+    .bind(now.to_rfc3339())
+    .execute(&state.db)
+    .await
+    .map_err(|e| format!("Failed to create chain: {}", e))?
+    .last_insert_rowid();
+
+    info!("phase=chain_create_ok id={}", id);
+    Ok(id)
+}
+
+#[tauri::command]
+pub async fn update_chain(
+    state: State<'_, crate::AppState>,
+    req: UpdateChainRequest,
+) -> Result<(), String> {
+    let rpc_url = req.rpc_url.as_deref().map(validate_rpc_url).transpose()?;
+
+    let mut updates = Vec::new();
+    if req.name.is_some() {
+        updates.push("name = ?");
+    }
+    if req.chain_id.is_some() {
+        updates.push("chain_id = ?");
+    }
+    if rpc_url.is_some() {
+        updates.push("rpc_url = ?");
+    }
+    if req.native_symbol.is_some() {
+        updates.push("native_symbol = ?");
+    }
+    if req.gas_threshold_gwei.is_some() {
+        updates.push("gas_threshold_gwei = ?");
+    }
+    if req.gas_alerts_enabled.is_some() {
+        updates.push("gas_alerts_enabled = ?");
+    }
+    if req.min_native_balance.is_some() {
+        updates.push("min_native_balance = ?");
+    }
+    updates.push("updated_at = ?");
+
+    let query = format!("UPDATE chains SET {} WHERE id = ?", updates.join(", "));
+
+    let mut q = sqlx::query(&query);
+    if let Some(name) = &req.name {
+        q = q.bind(name.trim());
+    }
+    if let Some(chain_id) = req.chain_id {
+        q = q.bind(chain_id);
+    }
+    if let Some(rpc_url) = &rpc_url {
+        q = q.bind(rpc_url);
+    }
+    if let Some(native_symbol) = &req.native_symbol {
+        q = q.bind(native_symbol);
+    }
+    if let Some(threshold) = req.gas_threshold_gwei {
+        q = q.bind(Some(threshold).filter(|t| *t >= 0.0));
+    }
+    if let Some(enabled) = req.gas_alerts_enabled {
+        q = q.bind(if enabled { 1 } else { 0 });
+    }
+    if let Some(minimum) = req.min_native_balance {
+        q = q.bind(Some(minimum).filter(|m| *m >= 0.0));
+    }
+    q = q.bind(Utc::now().to_rfc3339());
+    q = q.bind(req.id);
+
+    q.execute(&state.db)
+        .await
+        .map_err(|e| format!("Failed to update chain: {}", e))?;
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn delete_chain(state: State<'_, crate::AppState>, id: i64) -> Result<(), String> {
+    sqlx::query("DELETE FROM chains WHERE id = ?")
+        .bind(id)
+        .execute(&state.db)
+        .await
+        .map_err(|e| format!("Failed to delete chain: {}", e))?;
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn list_chain_tokens(
+    state: State<'_, crate::AppState>,
+    chain_id: Option<i64>,
+) -> Result<Vec<ChainToken>, String> {
+    let tokens = match chain_id {
+        Some(chain_id) => {
+            sqlx::query_as::<_, ChainToken>(
+                "SELECT * FROM chain_tokens WHERE chain_id = ? ORDER BY symbol ASC",
+            )
+            .bind(chain_id)
+            .fetch_all(&state.db)
+            .await
+        }
+        None => {
+            sqlx::query_as::<_, ChainToken>("SELECT * FROM chain_tokens ORDER BY chain_id ASC, symbol ASC")
+                .fetch_all(&state.db)
+                .await
+        }
+    }
+    .map_err(|e| format!("Failed to fetch tokens: {}", e))?;
+
+    Ok(tokens)
+}
+
+#[tauri::command]
+pub async fn create_chain_token(
+    state: State<'_, crate::AppState>,
+    req: CreateChainTokenRequest,
+) -> Result<i64, String> {
+    let address = req.address.trim().to_lowercase();
+    if !is_evm_address(&address) {
+        return Err(format!("Invalid token address: {}", req.address));
+    }
+
+    let decimals = match req.decimals {
+        Some(decimals) if decimals > MAX_TOKEN_DECIMALS => {
+            return Err(format!("Token decimals must be between 0 and {}", MAX_TOKEN_DECIMALS));
+        }
+        Some(decimals) => decimals,
+        None => {
+            let rpc_url = sqlx::query_scalar::<_, String>("SELECT rpc_url FROM chains WHERE id = ?")
+                .bind(req.chain_id)
+                .fetch_optional(&state.db)
+                .await
+                .map_err(|e| format!("Failed to fetch chain: {}", e))?
+                .ok_or_else(|| format!("Chain {} not found", req.chain_id))?;
+            rpc::erc20_decimals(&rpc::client()?, &rpc_url, &address).await?
+        }
+    };
+
+    let id = sqlx::query(
+        r#"
+        INSERT INTO chain_tokens (chain_id, symbol, address, decimals, created_at)
+        VALUES (?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(req.chain_id)
+    .bind(req.symbol.trim())
+    .bind(&address)
+    .bind(decimals)
+    .bind(Utc::now().to_rfc3339())
+    .execute(&state.db)
+    .await
+    .map_err(|e| format!("Failed to create token: {}", e))?
+    .last_insert_rowid();
+
+    info!("phase=chain_token_create_ok id={} chain_id={}", id, req.chain_id);
+    Ok(id)
+}
+
+#[tauri::command]
+pub async fn delete_chain_token(state: State<'_, crate::AppState>, id: i64) -> Result<(), String> {
+    sqlx::query("DELETE FROM chain_tokens WHERE id = ?")
+        .bind(id)
+        .execute(&state.db)
+        .await
+        .map_err(|e| format!("Failed to delete token: {}", e))?;
+
+    Ok(())
+}
+
+/// Looks up a configured chain by its name, case-insensitively.
+pub(crate) async fn chain_by_name(db: &sqlx::SqlitePool, name: &str) -> Result<Option<Chain>, String> {
+    sqlx::query_as::<_, Chain>("SELECT * FROM chains WHERE LOWER(name) = LOWER(?)")
+        .bind(name.trim())
+        .fetch_optional(db)
+        .await
+        .map_err(|e| format!("Failed to fetch chain: {}", e))
+}
+
+fn validate_rpc_url(raw: &str) -> Result<String, String> {
+    let url = url::Url::parse(raw.trim()).map_err(|e| format!("Invalid RPC URL: {}", e))?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err("RPC URLs must be http or https".to_string());
+    }
+    Ok(url.to_string())
+}
@@ -0,0 +1,267 @@
+use log::info;
+use tauri::State;
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::Deserialize;
+
+use crate::models::{AirdropClaim, DisposalLot, TokenDisposal};
+
+#[derive(Debug, Deserialize)]
+pub struct CreateAirdropClaimRequest {
+    pub airdrop_id: Option<i64>,
+    pub wallet_id: Option<i64>,
+    pub token: String,
+    pub amount: f64,
+    pub fair_value: f64,
+    /// Defaults to USD
+    pub currency: Option<String>,
+    /// RFC 3339 or `YYYY-MM-DD`
+    pub received_at: String,
+    pub tx_hash: Option<String>,
+    pub notes: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateTokenDisposalRequest {
+    pub token: String,
+    pub amount: f64,
+    pub proceeds: f64,
+    pub fee: Option<f64>,
+    pub currency: Option<String>,
+    /// RFC 3339 or `YYYY-MM-DD`
+    pub disposed_at: String,
+    pub tx_hash: Option<String>,
+    pub notes: Option<String>,
+    /// Claim lots sold, for specific-ID matching
+    pub lots: Option<Vec<DisposalLotInput>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DisposalLotInput {
+    pub claim_id: i64,
+    pub amount: f64,
+}
+
+#[tauri::command]
+pub async fn list_airdrop_claims(
+    state: State<'_, crate::AppState>,
+    airdrop_id: Option<i64>,
+) -> Result<Vec<AirdropClaim>, String> {
+    let claims = sqlx::query_as::<_, AirdropClaim>(
+        "SELECT * FROM airdrop_claims WHERE (? IS NULL OR airdrop_id = ?) ORDER BY received_at ASC",
+    )
+    .bind(airdrop_id)
+    .bind(airdrop_id)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|e| format!("Failed to fetch claims: {}", e))?;
+
+    Ok(claims)
+}
+
+#[tauri::command]
+pub async fn create_airdrop_claim(
+    state: State<'_, crate::AppState>,
+    req: CreateAirdropClaimRequest,
+) -> Result<i64, String> {
+    if !is_positive(req.amount) || !is_non_negative(req.fair_value) {
+        return Err("Claim amount must be positive and its value not negative".to_string());
+    }
+    let received_at = parse_date_or_datetime(&req.received_at)?;
+
+    let id = sqlx::query(
+        r#"
+        INSERT INTO airdrop_claims (airdrop_id, wallet_id, token, amount, fair_value, currency, received_at, tx_hash, notes, created_at)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(req.airdrop_id)
+    .bind(req.wallet_id)
+    .bind(normalize_symbol(&req.token))
+    .bind(req.amount)
+    .bind(req.fair_value)
+    .bind(currency_or_default(req.currency.as_deref()))
+    .bind(received_at.to_rfc3339())
+    .bind(&req.tx_hash)
+    .bind(&req.notes)
+    .bind(Utc::now().to_rfc3339())
+    .execute(&state.db)
+    .await
+    .map_err(|e| format!("Failed to create claim: {}", e))?
+    .last_insert_rowid();
+
+    info!("phase=airdrop_claim_create_ok id={}", id);
+    Ok(id)
+}
+
+#[tauri::command]
+pub async fn delete_airdrop_claim(state: State<'_, crate::AppState>, id: i64) -> Result<(), String> {
+    sqlx::query("DELETE FROM airdrop_claims WHERE id = ?")
+        .bind(id)
+        .execute(&state.db)
+        .await
+        .map_err(|e| format!("Failed to delete claim: {}", e))?;
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn list_token_disposals(state: State<'_, crate::AppState>) -> Result<Vec<TokenDisposal>, String> {
+    let disposals = sqlx::query_as::<_, TokenDisposal>("SELECT * FROM token_disposals ORDER BY disposed_at ASC")
+        .fetch_all(&state.db)
+        .await
+        .map_err(|e| format!("Failed to fetch disposals: {}", e))?;
+
+    Ok(disposals)
+}
+
+#[tauri::command]
+pub async fn create_token_disposal(
+    state: State<'_, crate::AppState>,
+    req: CreateTokenDisposalRequest,
+) -> Result<i64, String> {
+    if !is_positive(req.amount)
+        || !is_non_negative(req.proceeds)
+        || req.fee.is_some_and(|f| !is_non_negative(f))
+    {
+        return Err("Disposal amount must be positive and proceeds and fee not negative".to_string());
+    }
+    let disposed_at = parse_date_or_datetime(&req.disposed_at)?;
+    let token = normalize_symbol(&req.token);
+    let lots = req.lots.unwrap_or_default();
+    let assigned: f64 = lots.iter().map(|l| l.amount).sum();
+    if lots.iter().any(|l| !is_positive(l.amount)) || assigned > req.amount * (1.0 + f64::EPSILON) {
+        return Err("Lot amounts must be positive and add up to at most the amount sold".to_string());
+    }
+
+    let mut tx = state
+        .db
+        .begin()
+        .await
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+
+    for lot in &lots {
+        let claim = sqlx::query_as::<_, AirdropClaim>("SELECT * FROM airdrop_claims WHERE id = ?")
+            .bind(lot.claim_id)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(|e| format!("Failed to fetch claim: {}", e))?
+            .ok_or_else(|| format!("Claim {} not found", lot.claim_id))?;
+        if claim.token != token {
+            return Err(format!("Claim {} is for {}, not {}", claim.id, claim.token, token));
+        }
+        if claim.received_at > disposed_at {
+            return Err(format!("Claim {} was received after this sale", claim.id));
+        }
+        let already_sold = sqlx::query_scalar::<_, f64>(
+            "SELECT COALESCE(SUM(amount), 0) FROM disposal_lots WHERE claim_id = ?",
+        )
+        .bind(claim.id)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to fetch claim lots: {}", e))?;
+        if already_sold + lot.amount > claim.amount * (1.0 + f64::EPSILON) {
+            return Err(format!(
+                "Claim {} only has {} {} left",
+                claim.id,
+                claim.amount - already_sold,
+                claim.token
+            ));
+        }
+    }
+
+    let id = sqlx::query(
+        r#"
+        INSERT INTO token_disposals (token, amount, proceeds, fee, currency, disposed_at, tx_hash, notes, created_at)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(&token)
+    .bind(req.amount)
+    .bind(req.proceeds)
+    .bind(req.fee.unwrap_or(0.0))
+    .bind(currency_or_default(req.currency.as_deref()))
+    .bind(disposed_at.to_rfc3339())
+    .bind(&req.tx_hash)
+    .bind(&req.notes)
+    .bind(Utc::now().to_rfc3339())
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| format!("Failed to create disposal: {}", e))?
+    .last_insert_rowid();
+
+    for lot in &lots {
+        sqlx::query("INSERT INTO disposal_lots (disposal_id, claim_id, amount) VALUES (?, ?, ?)")
+            .bind(id)
+            .bind(lot.claim_id)
+            .bind(lot.amount)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| format!("Failed to assign lot: {}", e))?;
+    }
+
+    tx.commit()
+        .await
+        .map_err(|e| format!("Failed to create disposal: {}", e))?;
+
+    info!("phase=token_disposal_create_ok id={} lots={}", id, lots.len());
+    Ok(id)
+}
+
+#[tauri::command]
+pub async fn delete_token_disposal(state: State<'_, crate::AppState>, id: i64) -> Result<(), String> {
+    sqlx::query("DELETE FROM token_disposals WHERE id = ?")
+        .bind(id)
+        .execute(&state.db)
+        .await
+        .map_err(|e| format!("Failed to delete disposal: {}", e))?;
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn list_disposal_lots(
+    state: State<'_, crate::AppState>,
+    disposal_id: i64,
+) -> Result<Vec<DisposalLot>, String> {
+    let lots = sqlx::query_as::<_, DisposalLot>("SELECT * FROM disposal_lots WHERE disposal_id = ?")
+        .bind(disposal_id)
+        .fetch_all(&state.db)
+        .await
+        .map_err(|e| format!("Failed to fetch disposal lots: {}", e))?;
+
+    Ok(lots)
+}
+
+/// Accepts RFC 3339 timestamps or plain `YYYY-MM-DD` dates (midnight UTC).
+pub(crate) fn parse_date_or_datetime(raw: &str) -> Result<DateTime<Utc>, String> {
+    let raw = raw.trim();
+    DateTime::parse_from_rfc3339(raw)
+        .map(|d| d.with_timezone(&Utc))
+        .ok()
+        .or_else(|| {
+            NaiveDate::parse_from_str(raw, "%Y-%m-%d")
+                .ok()
+                .and_then(|d| d.and_hms_opt(0, 0, 0))
+                .map(|d| d.and_utc())
+        })
+        .ok_or_else(|| format!("Invalid date: {}", raw))
+}
+
+fn normalize_symbol(token: &str) -> String {
+    token.trim().to_uppercase()
+}
+
+fn currency_or_default(currency: Option<&str>) -> String {
+    currency
+        .map(|c| c.trim().to_uppercase())
+        .filter(|c| !c.is_empty())
+        .unwrap_or_else(|| "USD".to_string())
+}
+
+fn is_positive(value: f64) -> bool {
+    value.is_finite() && value > 0.0
+}
+
+fn is_non_negative(value: f64) -> bool {
+    value.is_finite() && value >= 0.0
+}
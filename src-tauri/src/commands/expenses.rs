@@ -0,0 +1,88 @@
+use log::info;
+use tauri::State;
+use chrono::{NaiveDate, Utc};
+use serde::Deserialize;
+
+use crate::models::AirdropExpense;
+
+#[derive(Debug, Deserialize)]
+pub struct CreateAirdropExpenseRequest {
+    pub airdrop_id: i64,
+    pub amount: f64,
+    /// Defaults to USD
+    pub currency: Option<String>,
+    pub description: Option<String>,
+    /// `YYYY-MM-DD`; defaults to today
+    pub spent_on: Option<String>,
+}
+
+#[tauri::command]
+pub async fn list_airdrop_expenses(
+    state: State<'_, crate::AppState>,
+    airdrop_id: Option<i64>,
+) -> Result<Vec<AirdropExpense>, String> {
+    let expenses = sqlx::query_as::<_, AirdropExpense>(
+        "SELECT * FROM airdrop_expenses WHERE (? IS NULL OR airdrop_id = ?) ORDER BY spent_on DESC, id DESC",
+    )
+    .bind(airdrop_id)
+    .bind(airdrop_id)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|e| format!("Failed to fetch expenses: {}", e))?;
+
+    Ok(expenses)
+}
+
+#[tauri::command]
+pub async fn create_airdrop_expense(
+    state: State<'_, crate::AppState>,
+    req: CreateAirdropExpenseRequest,
+) -> Result<i64, String> {
+    if !req.amount.is_finite() || req.amount < 0.0 {
+        return Err("Expense amount must be a positive number".to_string());
+    }
+    let now = Utc::now();
+    let spent_on = match req.spent_on.as_deref().map(str::trim).filter(|d| !d.is_empty()) {
+        Some(date) => NaiveDate::parse_from_str(date, "%Y-%m-%d")
+            .map_err(|_| format!("Invalid expense date: {}", date))?
+            .to_string(),
+        None => now.format("%Y-%m-%d").to_string(),
+    };
+    let currency = req
+        .currency
+        .as_deref()
+        .map(|c| c.trim().to_uppercase())
+        .filter(|c| !c.is_empty())
+        .unwrap_or_else(|| "USD".to_string());
+
+    let id = sqlx::query(
+        r#"
+        INSERT INTO airdrop_expenses (airdrop_id, amount, currency, description, spent_on, created_at)
+        VALUES (?, ?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(req.airdrop_id)
+    .bind(req.amount)
+    .bind(&currency)
+    .bind(&req.description)
+    .bind(&spent_on)
+    .bind(now.to_rfc3339())
+    .execute(&state.db)
+    .await
+    .map_err(|e| format!("Failed to create expense: {}", e))?
+    .last_insert_rowid();
+
+    info!("phase=airdrop_expense_create_ok id={} airdrop_id={}", id, req.airdrop_id);
+    Ok(id)
+}
+
+#[tauri::command]
+pub async fn delete_airdrop_expense(state: State<'_, crate::AppState>, id: i64) -> Result<(), String> {
+    sqlx::query("DELETE FROM airdrop_expenses WHERE id = ?")
+        .bind(id)
+        .execute(&state.db)
+        .await
+        .map_err(|e| format!("Failed to delete expense: {}", e))?;
+
+    Ok(())
+}
@@ -0,0 +1,793 @@
+use log::{info, warn};
+use tauri::{AppHandle, Emitter, State};
+use sqlx::SqlitePool;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+use crate::commands::settings::{read_setting, write_setting};
+use crate::models::{AirdropFeed, FeedItem};
+
+pub(crate) const FEED_SETTINGS_KEY: &str = "feed_settings";
+const FEED_TIMEOUT: Duration = Duration::from_secs(20);
+const SUMMARY_MAX_CHARS: usize = 500;
+/// Tags that separate words; inline tags such as `<strong>` do not.
+const BLOCK_TAGS: [&str; 24] = [
+    "address", "article", "blockquote", "br", "dd", "div", "dl", "dt", "footer", "h1", "h2", "h3", "h4", "h5",
+    "h6", "header", "hr", "li", "ol", "p", "pre", "section", "td", "ul",
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FeedSettings {
+    /// How often the background poller refreshes every feed
+    pub poll_interval_minutes: u64,
+}
+
+impl Default for FeedSettings {
+    fn default() -> Self {
+        Self { poll_interval_minutes: 30 }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateAirdropFeedRequest {
+    pub airdrop_id: i64,
+    pub url: String,
+    pub title: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MarkFeedItemsReadRequest {
+    /// Marks these items read; when omitted, `airdrop_id` selects the items instead.
+    pub item_ids: Option<Vec<i64>>,
+    pub airdrop_id: Option<i64>,
+    pub read: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FeedPollResult {
+    pub feed_id: i64,
+    pub airdrop_id: i64,
+    pub new_items: usize,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct FeedUnreadCount {
+    pub airdrop_id: i64,
+    pub unread: i64,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct ParsedFeed {
+    pub title: Option<String>,
+    pub items: Vec<ParsedFeedItem>,
+}
+
+#[derive(Debug)]
+pub(crate) struct ParsedFeedItem {
+    pub guid: String,
+    pub title: String,
+    pub link: Option<String>,
+    pub summary: Option<String>,
+    pub published_at: Option<DateTime<Utc>>,
+}
+
+#[tauri::command]
+pub async fn list_airdrop_feeds(
+    state: State<'_, crate::AppState>,
+    airdrop_id: Option<i64>,
+) -> Result<Vec<AirdropFeed>, String> {
+    let feeds = match airdrop_id {
+        Some(airdrop_id) => {
+            sqlx::query_as::<_, AirdropFeed>(
+                "SELECT * FROM airdrop_feeds WHERE airdrop_id = ? ORDER BY created_at ASC",
+            )
+            .bind(airdrop_id)
+            .fetch_all(&state.db)
+            .await
+        }
+        None => {
+            sqlx::query_as::<_, AirdropFeed>("SELECT * FROM airdrop_feeds ORDER BY created_at ASC")
+                .fetch_all(&state.db)
+                .await
+        }
+    }
+    .map_err(|e| format!("Failed to fetch feeds: {}", e))?;
+
+    Ok(feeds)
+}
+
+#[tauri::command]
+pub async fn create_airdrop_feed(
+    state: State<'_, crate::AppState>,
+    req: CreateAirdropFeedRequest,
+) -> Result<i64, String> {
+    let url = req.url.trim();
+    url::Url::parse(url).map_err(|e| format!("Invalid feed URL: {}", e))?;
+    let now = Utc::now();
+
+    let id = sqlx::query(
+        r#"
+        INSERT INTO airdrop_feeds (airdrop_id, url, title, created_at, updated_at)
+        VALUES (?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(req.airdrop_id)
+    .bind(url)
+    .bind(&req.title)
+    .bind(now.to_rfc3339())
+    .bind(now.to_rfc3339())
+    .execute(&state.db)
+    .await
+    .map_err(|e| format!("Failed to create feed: {}", e))?
+    .last_insert_rowid();
+
+    info!("phase=feed_create_ok id={} airdrop_id={}", id, req.airdrop_id);
+    Ok(id)
+}
+
+#[tauri::command]
+pub async fn delete_airdrop_feed(state: State<'_, crate::AppState>, id: i64) -> Result<(), String> {
+    sqlx::query("DELETE FROM airdrop_feeds WHERE id = ?")
+        .bind(id)
+        .execute(&state.db)
+        .await
+        .map_err(|e| format!("Failed to delete feed: {}", e))?;
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn poll_airdrop_feeds(
+    state: State<'_, crate::AppState>,
+    feed_id: Option<i64>,
+) -> Result<Vec<FeedPollResult>, String> {
+    poll_feeds(&state.db, feed_id).await
+}
+
+#[tauri::command]
+pub async fn list_feed_items(
+    state: State<'_, crate::AppState>,
+    airdrop_id: Option<i64>,
+    unread_only: Option<bool>,
+) -> Result<Vec<FeedItem>, String> {
+    let mut query = "SELECT * FROM feed_items WHERE 1 = 1".to_string();
+    if airdrop_id.is_some() {
+        query.push_str(" AND airdrop_id = ?");
+    }
+    if unread_only.unwrap_or(false) {
+        query.push_str(" AND read = 0");
+    }
+    query.push_str(" ORDER BY COALESCE(published_at, created_at) DESC");
+
+    let mut q = sqlx::query_as::<_, FeedItem>(&query);
+    if let Some(airdrop_id) = airdrop_id {
+        q = q.bind(airdrop_id);
+    }
+
+    let items = q
+        .fetch_all(&state.db)
+        .await
+        .map_err(|e| format!("Failed to fetch feed items: {}", e))?;
+
+    Ok(items)
+}
+
+#[tauri::command]
+pub async fn feed_unread_counts(
+    state: State<'_, crate::AppState>,
+) -> Result<Vec<FeedUnreadCount>, String> {
+    unread_counts(&state.db).await
+}
+
+#[tauri::command]
+pub async fn mark_feed_items_read(
+    state: State<'_, crate::AppState>,
+    req: MarkFeedItemsReadRequest,
+) -> Result<(), String> {
+    mark_items_read(&state.db, &req).await
+}
+
+pub(crate) async fn unread_counts(db: &SqlitePool) -> Result<Vec<FeedUnreadCount>, String> {
+    sqlx::query_as::<_, FeedUnreadCount>(
+        "SELECT airdrop_id, COUNT(*) AS unread FROM feed_items WHERE read = 0 GROUP BY airdrop_id",
+    )
+    .fetch_all(db)
+    .await
+    .map_err(|e| format!("Failed to count unread feed items: {}", e))
+}
+
+pub(crate) async fn mark_items_read(db: &SqlitePool, req: &MarkFeedItemsReadRequest) -> Result<(), String> {
+    let read = if req.read.unwrap_or(true) { 1 } else { 0 };
+
+    if let Some(item_ids) = &req.item_ids {
+        let mut tx = db
+            .begin()
+            .await
+            .map_err(|e| format!("Failed to start transaction: {}", e))?;
+
+        for id in item_ids {
+            sqlx::query("UPDATE feed_items SET read = ? WHERE id = ?")
+                .bind(read)
+                .bind(id)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| format!("Failed to mark feed item: {}", e))?;
+        }
+
+        tx.commit()
+            .await
+            .map_err(|e| format!("Failed to commit feed item update: {}", e))?;
+    } else if let Some(airdrop_id) = req.airdrop_id {
+        sqlx::query("UPDATE feed_items SET read = ? WHERE airdrop_id = ?")
+            .bind(read)
+            .bind(airdrop_id)
+            .execute(db)
+            .await
+            .map_err(|e| format!("Failed to mark feed items: {}", e))?;
+    } else {
+        return Err("Either item_ids or airdrop_id is required".to_string());
+    }
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_feed_settings(state: State<'_, crate::AppState>) -> Result<FeedSettings, String> {
+    read_setting(&state.db, FEED_SETTINGS_KEY).await
+}
+
+#[tauri::command]
+pub async fn update_feed_settings(
+    state: State<'_, crate::AppState>,
+    settings: FeedSettings,
+) -> Result<(), String> {
+    if settings.poll_interval_minutes < 1 {
+        return Err("Feed poll interval must be at least one minute".to_string());
+    }
+    write_setting(&state.db, FEED_SETTINGS_KEY, &settings).await
+}
+
+/// Polls every feed on the configured interval and emits `feed-items-new`
+/// when new entries arrive. The interval is re-read after every poll.
+pub(crate) fn spawn_feed_poller(app: AppHandle, db: SqlitePool) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            match poll_feeds(&db, None).await {
+                Ok(results) => {
+                    let fresh: Vec<FeedPollResult> =
+                        results.into_iter().filter(|r| r.new_items > 0).collect();
+                    if !fresh.is_empty() {
+                        let _ = app.emit("feed-items-new", fresh);
+                    }
+                }
+                Err(e) => warn!("phase=feed_poll_failed error={}", e),
+            }
+
+            let settings = read_setting::<FeedSettings>(&db, FEED_SETTINGS_KEY)
+                .await
+                .unwrap_or_else(|e| {
+                    warn!("phase=feed_settings_failed error={}", e);
+                    FeedSettings::default()
+                });
+            let minutes = settings.poll_interval_minutes.max(1);
+            tokio::time::sleep(Duration::from_secs(minutes * 60)).await;
+        }
+    });
+}
+
+/// Fetches one feed (or all feeds) and stores entries not seen before.
+pub(crate) async fn poll_feeds(
+    db: &SqlitePool,
+    feed_id: Option<i64>,
+) -> Result<Vec<FeedPollResult>, String> {
+    let feeds = match feed_id {
+        Some(feed_id) => {
+            sqlx::query_as::<_, AirdropFeed>("SELECT * FROM airdrop_feeds WHERE id = ?")
+                .bind(feed_id)
+                .fetch_all(db)
+                .await
+        }
+        None => {
+            sqlx::query_as::<_, AirdropFeed>("SELECT * FROM airdrop_feeds")
+                .fetch_all(db)
+                .await
+        }
+    }
+    .map_err(|e| format!("Failed to fetch feeds: {}", e))?;
+
+    let client = reqwest::Client::builder()
+        .timeout(FEED_TIMEOUT)
+        .user_agent(concat!("PraetorDesk/", env!("CARGO_PKG_VERSION")))
+        .build()
+        .map_err(|e| format!("Failed to build HTTP client: {}", e))?;
+
+    let mut results = Vec::with_capacity(feeds.len());
+    for feed in feeds {
+        let outcome = match fetch_feed(&client, &feed.url).await {
+            Ok(parsed) => store_feed_items(db, &feed, &parsed).await,
+            Err(e) => Err(e),
+        };
+        let now = Utc::now().to_rfc3339();
+
+        let result = match outcome {
+            Ok(new_items) => {
+                sqlx::query(
+                    "UPDATE airdrop_feeds SET last_polled_at = ?, last_error = NULL, updated_at = ? WHERE id = ?",
+                )
+                .bind(&now)
+                .bind(&now)
+                .bind(feed.id)
+                .execute(db)
+                .await
+                .map_err(|e| format!("Failed to update feed: {}", e))?;
+
+                FeedPollResult {
+                    feed_id: feed.id,
+                    airdrop_id: feed.airdrop_id,
+                    new_items,
+                    error: None,
+                }
+            }
+            Err(e) => {
+                warn!("phase=feed_poll_error feed_id={} error={}", feed.id, e);
+                sqlx::query(
+                    "UPDATE airdrop_feeds SET last_polled_at = ?, last_error = ?, updated_at = ? WHERE id = ?",
+                )
+                .bind(&now)
+                .bind(&e)
+                .bind(&now)
+                .bind(feed.id)
+                .execute(db)
+                .await
+                .map_err(|e| format!("Failed to update feed: {}", e))?;
+
+                FeedPollResult {
+                    feed_id: feed.id,
+                    airdrop_id: feed.airdrop_id,
+                    new_items: 0,
+                    error: Some(e),
+                }
+            }
+        };
+        results.push(result);
+    }
+
+    let total_new: usize = results.iter().map(|r| r.new_items).sum();
+    info!("phase=feed_poll_done feeds={} new_items={}", results.len(), total_new);
+    Ok(results)
+}
+
+async fn fetch_feed(client: &reqwest::Client, url: &str) -> Result<ParsedFeed, String> {
+    let response = client
+        .get(url)
+        .send()
+        .await
+        .map_err(|e| format!("Feed request failed: {}", e))?
+        .error_for_status()
+        .map_err(|e| format!("Feed request failed: {}", e))?;
+
+    let body = response
+        .text()
+        .await
+        .map_err(|e| format!("Failed to read feed: {}", e))?;
+
+    parse_feed(&body)
+}
+
+async fn store_feed_items(
+    db: &SqlitePool,
+    feed: &AirdropFeed,
+    parsed: &ParsedFeed,
+) -> Result<usize, String> {
+    let now = Utc::now().to_rfc3339();
+    let mut new_items = 0;
+
+    for item in &parsed.items {
+        let inserted = sqlx::query(
+            r#"
+            INSERT OR IGNORE INTO feed_items (feed_id, airdrop_id, guid, title, link, summary, published_at, read, created_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, 0, ?)
+            "#,
+        )
+        .bind(feed.id)
+        .bind(feed.airdrop_id)
+        .bind(&item.guid)
+        .bind(&item.title)
+        .bind(&item.link)
+        .bind(&item.summary)
+        .bind(item.published_at.map(|d| d.to_rfc3339()))
+        .bind(&now)
+        .execute(db)
+        .await
+        .map_err(|e| format!("Failed to store feed item: {}", e))?;
+
+        new_items += inserted.rows_affected() as usize;
+    }
+
+    if feed.title.is_none() && parsed.title.is_some() {
+        sqlx::query("UPDATE airdrop_feeds SET title = ? WHERE id = ?")
+            .bind(&parsed.title)
+            .bind(feed.id)
+            .execute(db)
+            .await
+            .map_err(|e| format!("Failed to update feed title: {}", e))?;
+    }
+
+    Ok(new_items)
+}
+
+/// Parses RSS 2.0, RSS 1.0 (RDF), Atom, JSON Feed or a plain JSON changelog array.
+pub(crate) fn parse_feed(body: &str) -> Result<ParsedFeed, String> {
+    let trimmed = body.trim_start_matches('\u{feff}').trim();
+    if trimmed.starts_with('{') || trimmed.starts_with('[') {
+        parse_json_feed(trimmed)
+    } else {
+        parse_xml_feed(trimmed)
+    }
+}
+
+fn parse_xml_feed(body: &str) -> Result<ParsedFeed, String> {
+    let doc = roxmltree::Document::parse(body).map_err(|e| format!("Invalid feed XML: {}", e))?;
+    let root = doc.root_element();
+
+    let mut feed = ParsedFeed::default();
+    match root.tag_name().name() {
+        "feed" => {
+            feed.title = child_text(root, "title");
+            for entry in root.children().filter(|n| is_tag(n, "entry")) {
+                let link = entry
+                    .children()
+                    .filter(|n| is_tag(n, "link"))
+                    .find(|n| n.attribute("rel").is_none_or(|rel| rel == "alternate"))
+                    .and_then(|n| n.attribute("href"))
+                    .map(|href| href.to_string());
+                let published = child_text(entry, "published").or_else(|| child_text(entry, "updated"));
+                let summary = child_text(entry, "summary").or_else(|| child_text(entry, "content"));
+                push_item(
+                    &mut feed,
+                    child_text(entry, "id"),
+                    child_text(entry, "title"),
+                    link,
+                    summary,
+                    published,
+                );
+            }
+        }
+        "rss" | "RDF" => {
+            let channel = root.children().find(|n| is_tag(n, "channel"));
+            feed.title = channel.and_then(|c| child_text(c, "title"));
+            // RSS 2.0 nests items in the channel, RSS 1.0 puts them beside it.
+            let items = root
+                .descendants()
+                .filter(|n| is_tag(n, "item"));
+            for item in items {
+                let published = child_text(item, "pubDate").or_else(|| child_text(item, "date"));
+                push_item(
+                    &mut feed,
+                    child_text(item, "guid"),
+                    child_text(item, "title"),
+                    child_text(item, "link"),
+                    child_text(item, "description"),
+                    published,
+                );
+            }
+        }
+        other => return Err(format!("Unsupported feed format: <{}>", other)),
+    }
+
+    Ok(feed)
+}
+
+fn parse_json_feed(body: &str) -> Result<ParsedFeed, String> {
+    let value: serde_json::Value =
+        serde_json::from_str(body).map_err(|e| format!("Invalid feed JSON: {}", e))?;
+
+    let mut feed = ParsedFeed::default();
+    let entries = match &value {
+        serde_json::Value::Array(entries) => entries.clone(),
+        serde_json::Value::Object(map) => {
+            feed.title = map.get("title").and_then(|t| t.as_str()).map(|t| t.to_string());
+            map.get("items")
+                .or_else(|| map.get("entries"))
+                .and_then(|items| items.as_array())
+                .cloned()
+                .ok_or("JSON feed has no items array")?
+        }
+        _ => return Err("Unsupported JSON feed".to_string()),
+    };
+
+    for entry in &entries {
+        let text = |keys: &[&str]| {
+            keys.iter().find_map(|key| match entry.get(*key) {
+                Some(serde_json::Value::String(s)) if !s.trim().is_empty() => Some(s.trim().to_string()),
+                Some(serde_json::Value::Number(n)) => Some(n.to_string()),
+                _ => None,
+            })
+        };
+        push_item(
+            &mut feed,
+            text(&["id", "guid", "version"]),
+            text(&["title", "name", "version"]),
+            text(&["url", "link", "external_url"]),
+            text(&["summary", "content_text", "description", "notes", "content_html"]),
+            text(&["date_published", "published_at", "published", "date", "date_modified"]),
+        );
+    }
+
+    Ok(feed)
+}
+
+fn push_item(
+    feed: &mut ParsedFeed,
+    guid: Option<String>,
+    title: Option<String>,
+    link: Option<String>,
+    summary: Option<String>,
+    published: Option<String>,
+) {
+    let published_at = published.as_deref().and_then(parse_feed_date);
+    let title = match title.or_else(|| link.clone()) {
+        Some(title) => title,
+        None => return,
+    };
+    let guid = guid
+        .or_else(|| link.clone())
+        .unwrap_or_else(|| format!("{}|{}", title, published.unwrap_or_default()));
+
+    feed.items.push(ParsedFeedItem {
+        guid,
+        title,
+        link,
+        summary: summary.map(|s| summarize(&s)).filter(|s| !s.is_empty()),
+        published_at,
+    });
+}
+
+fn child_text(node: roxmltree::Node, name: &str) -> Option<String> {
+    node.children()
+        .find(|n| is_tag(n, name))
+        .map(|n| {
+            n.descendants()
+                .filter(|d| d.is_text())
+                .filter_map(|d| d.text())
+                .collect::<String>()
+                .trim()
+                .to_string()
+        })
+        .filter(|text| !text.is_empty())
+}
+
+fn parse_feed_date(raw: &str) -> Option<DateTime<Utc>> {
+    let raw = raw.trim();
+    DateTime::parse_from_rfc3339(raw)
+        .or_else(|_| DateTime::parse_from_rfc2822(raw))
+        .map(|d| d.with_timezone(&Utc))
+        .ok()
+        .or_else(|| {
+            chrono::NaiveDate::parse_from_str(raw, "%Y-%m-%d")
+                .ok()
+                .and_then(|d| d.and_hms_opt(0, 0, 0))
+                .map(|d| d.and_utc())
+        })
+}
+
+/// Strips markup, decodes entities and collapses whitespace so summaries stay
+/// short plain text.
+fn summarize(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut tag: Option<String> = None;
+    for c in html.chars() {
+        match (&mut tag, c) {
+            (None, '<') => tag = Some(String::new()),
+            (Some(inner), '>') => {
+                let name: String = inner
+                    .trim_start_matches('/')
+                    .chars()
+                    .take_while(|c| c.is_ascii_alphanumeric())
+                    .collect::<String>()
+                    .to_ascii_lowercase();
+                if BLOCK_TAGS.contains(&name.as_str()) {
+                    text.push(' ');
+                }
+                tag = None;
+            }
+            (Some(inner), _) => inner.push(c),
+            (None, _) => text.push(c),
+        }
+    }
+
+    let collapsed = decode_entities(&text).split_whitespace().collect::<Vec<_>>().join(" ");
+    if collapsed.chars().count() > SUMMARY_MAX_CHARS {
+        let cut: String = collapsed.chars().take(SUMMARY_MAX_CHARS).collect();
+        format!("{}…", cut.trim_end())
+    } else {
+        collapsed
+    }
+}
+
+/// Decodes the named entities common in feed HTML plus numeric ones; anything
+/// else is kept as written.
+fn decode_entities(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let decoded = rest.find(';').filter(|end| *end <= 10).and_then(|end| {
+            let entity = &rest[1..end];
+            let c = match entity {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "nbsp" => Some(' '),
+                _ => entity
+                    .strip_prefix("#x")
+                    .or_else(|| entity.strip_prefix("#X"))
+                    .map(|hex| u32::from_str_radix(hex, 16))
+                    .or_else(|| entity.strip_prefix('#').map(|dec| dec.parse::<u32>()))
+                    .and_then(|n| n.ok())
+                    .and_then(char::from_u32),
+            };
+            c.map(|c| (c, end))
+        });
+        match decoded {
+            Some((c, end)) => {
+                out.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+fn is_tag(node: &roxmltree::Node, name: &str) -> bool {
+    node.is_element() && node.tag_name().name() == name
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{http_stand_in, memory_db, StandInReply};
+
+    const RSS: &str = include_str!("../../tests/fixtures/feeds/rss.xml");
+    const ATOM: &str = include_str!("../../tests/fixtures/feeds/atom.xml");
+    const JSON_FEED: &str = include_str!("../../tests/fixtures/feeds/feed.json");
+    const CHANGELOG: &str = include_str!("../../tests/fixtures/feeds/changelog.json");
+
+    #[test]
+    fn parses_rss() {
+        let feed = parse_feed(RSS).unwrap();
+        assert_eq!(feed.title.as_deref(), Some("Project Blog"));
+        assert_eq!(feed.items.len(), 3);
+
+        let first = &feed.items[0];
+        assert_eq!(first.guid, "post-102");
+        assert_eq!(first.title, "Season 2 snapshot announced");
+        assert_eq!(first.summary.as_deref(), Some("The snapshot for Season 2 happens on Friday."));
+        assert_eq!(first.published_at.unwrap().to_rfc3339(), "2024-09-03T14:00:00+00:00");
+
+        // Without a guid the link identifies the entry
+        assert_eq!(feed.items[1].guid, "https://blog.example.xyz/bridge-quest");
+        assert_eq!(feed.items[1].published_at.unwrap().to_rfc3339(), "2024-09-02T07:30:00+00:00");
+        assert_eq!(feed.items[2].title, "https://blog.example.xyz/untitled");
+    }
+
+    #[test]
+    fn parses_atom() {
+        let feed = parse_feed(ATOM).unwrap();
+        assert_eq!(feed.title.as_deref(), Some("Mirror: Example DAO"));
+        assert_eq!(feed.items.len(), 2);
+
+        let first = &feed.items[0];
+        assert_eq!(first.guid, "urn:uuid:1225c695-cfb8-4ebb-aaaa-80da344efa6a");
+        assert_eq!(first.link.as_deref(), Some("https://mirror.xyz/example/abc"));
+        assert_eq!(first.summary.as_deref(), Some("Points now double for LPs."));
+        assert_eq!(first.published_at.unwrap().to_rfc3339(), "2024-09-04T18:30:02+00:00");
+
+        let second = &feed.items[1];
+        assert_eq!(second.link.as_deref(), Some("https://mirror.xyz/example/gov"));
+        assert_eq!(second.summary.as_deref(), Some("Join us on Thursday."));
+        assert_eq!(second.published_at.unwrap().to_rfc3339(), "2024-09-01T10:00:00+00:00");
+    }
+
+    #[test]
+    fn parses_json_feed_and_changelog() {
+        let feed = parse_feed(JSON_FEED).unwrap();
+        assert_eq!(feed.title.as_deref(), Some("Example Changelog"));
+        assert_eq!(feed.items[0].guid, "2024-09-06-claim");
+        assert_eq!(feed.items[0].summary.as_deref(), Some("Claims open Monday."));
+        assert_eq!(feed.items[1].guid, "17");
+        assert_eq!(feed.items[1].title, "https://example.xyz/news/17");
+        assert_eq!(feed.items[1].published_at.unwrap().to_rfc3339(), "2024-09-05T00:00:00+00:00");
+
+        let changelog = parse_feed(CHANGELOG).unwrap();
+        let versions: Vec<&str> = changelog.items.iter().map(|i| i.guid.as_str()).collect();
+        assert_eq!(versions, vec!["1.4.0", "1.3.2"]);
+        assert_eq!(changelog.items[0].summary.as_deref(), Some("Adds testnet faucet quests."));
+    }
+
+    #[test]
+    fn summaries_space_block_tags_only_and_decode_entities() {
+        assert_eq!(summarize("<p>Claims open <strong>Monday</strong>.</p>"), "Claims open Monday.");
+        assert_eq!(summarize("<p>Step one</p><p>Step two</p>"), "Step one Step two");
+        assert_eq!(summarize("Line<br/>break <UL><LI>a</LI><li>b</li></UL>"), "Line break a b");
+        assert_eq!(
+            summarize("Tom &amp; Jerry&#39;s &quot;quest&quot; &#x2014; 5&nbsp;&lt;&nbsp;6"),
+            "Tom & Jerry's \"quest\" \u{2014} 5 < 6"
+        );
+        // Stray ampersands and unknown entities are left alone
+        assert_eq!(summarize("R&D &bogus; &#xZZ;"), "R&D &bogus; &#xZZ;");
+    }
+
+    #[test]
+    fn rejects_unknown_documents() {
+        assert!(parse_feed("<html><body>not a feed</body></html>").is_err());
+        assert!(parse_feed("{\"no\": \"items\"}").is_err());
+    }
+
+    #[tokio::test]
+    async fn polling_dedupes_entries_and_tracks_unread() {
+        let base = http_stand_in(|request| match request.path.as_str() {
+            "/rss" => StandInReply::ok(RSS),
+            "/atom" => StandInReply::ok(ATOM),
+            _ => StandInReply::status(500),
+        })
+        .await;
+        let db = memory_db().await;
+        let now = Utc::now().to_rfc3339();
+        let airdrop_id = sqlx::query("INSERT INTO airdrops (name, url, created_at, updated_at) VALUES ('x', 'https://example.xyz', ?, ?)")
+            .bind(&now)
+            .bind(&now)
+            .execute(&db)
+            .await
+            .unwrap()
+            .last_insert_rowid();
+        for path in ["/rss", "/atom", "/broken"] {
+            sqlx::query("INSERT INTO airdrop_feeds (airdrop_id, url, created_at, updated_at) VALUES (?, ?, ?, ?)")
+                .bind(airdrop_id)
+                .bind(format!("{}{}", base, path))
+                .bind(&now)
+                .bind(&now)
+                .execute(&db)
+                .await
+                .unwrap();
+        }
+
+        let first = poll_feeds(&db, None).await.unwrap();
+        let new_items: Vec<usize> = first.iter().map(|r| r.new_items).collect();
+        assert_eq!(new_items, vec![3, 2, 0]);
+        assert!(first[2].error.is_some());
+
+        // Entries seen before are not stored again
+        let second = poll_feeds(&db, None).await.unwrap();
+        assert!(second.iter().all(|r| r.new_items == 0));
+        let counts = unread_counts(&db).await.unwrap();
+        assert_eq!((counts[0].airdrop_id, counts[0].unread), (airdrop_id, 5));
+
+        let ids: Vec<i64> = sqlx::query_scalar("SELECT id FROM feed_items ORDER BY id LIMIT 2")
+            .fetch_all(&db)
+            .await
+            .unwrap();
+        let req = MarkFeedItemsReadRequest { item_ids: Some(ids), airdrop_id: None, read: None };
+        mark_items_read(&db, &req).await.unwrap();
+        assert_eq!(unread_counts(&db).await.unwrap()[0].unread, 3);
+
+        let req = MarkFeedItemsReadRequest { item_ids: None, airdrop_id: Some(airdrop_id), read: Some(true) };
+        mark_items_read(&db, &req).await.unwrap();
+        assert!(unread_counts(&db).await.unwrap().is_empty());
+
+        // Read state survives another poll
+        poll_feeds(&db, None).await.unwrap();
+        assert!(unread_counts(&db).await.unwrap().is_empty());
+    }
+}
@@ -0,0 +1,658 @@
+use log::{info, warn};
+use tauri::{AppHandle, Emitter, State};
+use sqlx::SqlitePool;
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+use tauri_plugin_notification::NotificationExt;
+
+use crate::commands::settings::{read_setting, write_setting};
+use crate::models::{FocusInterruption, FocusSession};
+
+pub(crate) const FOCUS_SETTINGS_KEY: &str = "focus_settings";
+const TICK_INTERVAL: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FocusSettings {
+    pub focus_minutes: i64,
+    pub short_break_minutes: i64,
+    pub long_break_minutes: i64,
+    /// Every Nth completed focus interval is followed by a long break
+    pub long_break_every: i64,
+    /// Start the next focus interval right after a break instead of paused
+    pub auto_start_focus: bool,
+}
+
+impl Default for FocusSettings {
+    fn default() -> Self {
+        Self {
+            focus_minutes: 25,
+            short_break_minutes: 5,
+            long_break_minutes: 15,
+            long_break_every: 4,
+            auto_start_focus: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FocusPhase {
+    Focus,
+    ShortBreak,
+    LongBreak,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FocusState {
+    pub phase: FocusPhase,
+    pub phase_seconds: i64,
+    pub remaining_seconds: i64,
+    pub paused: bool,
+    /// Focus intervals completed in this run
+    pub completed_cycles: i64,
+    pub task_id: Option<i64>,
+    pub airdrop_id: Option<i64>,
+    /// The `focus_sessions` row of the current focus interval
+    pub session_id: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct StartFocusRequest {
+    pub task_id: Option<i64>,
+    pub airdrop_id: Option<i64>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct FocusSessionFilter {
+    pub task_id: Option<i64>,
+    pub airdrop_id: Option<i64>,
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FocusSessionDetail {
+    #[serde(flatten)]
+    pub session: FocusSession,
+    pub interruptions: Vec<FocusInterruption>,
+}
+
+impl FocusState {
+    /// Seconds spent in the current phase so far.
+    fn elapsed_seconds(&self) -> i64 {
+        self.phase_seconds - self.remaining_seconds
+    }
+}
+
+/// The timer's state machine. It does no I/O and takes the clock as an
+/// argument; `FocusEngine` wraps it with the ticker, sessions and events.
+#[derive(Debug, Clone)]
+struct FocusTimer {
+    state: FocusState,
+    settings: FocusSettings,
+    /// When the current phase ends; only meaningful while not paused
+    ends_at: DateTime<Utc>,
+}
+
+impl FocusTimer {
+    fn start(
+        settings: FocusSettings,
+        task_id: Option<i64>,
+        airdrop_id: Option<i64>,
+        session_id: i64,
+        now: DateTime<Utc>,
+    ) -> Self {
+        let phase_seconds = settings.focus_minutes * 60;
+        Self {
+            state: FocusState {
+                phase: FocusPhase::Focus,
+                phase_seconds,
+                remaining_seconds: phase_seconds,
+                paused: false,
+                completed_cycles: 0,
+                task_id,
+                airdrop_id,
+                session_id: Some(session_id),
+            },
+            settings,
+            ends_at: now + ChronoDuration::seconds(phase_seconds),
+        }
+    }
+
+    /// Current state with the remaining time brought up to `now`.
+    fn snapshot(&mut self, now: DateTime<Utc>) -> FocusState {
+        if !self.state.paused {
+            self.state.remaining_seconds = (self.ends_at - now).num_seconds().max(0);
+        }
+        self.state.clone()
+    }
+
+    fn pause(&mut self, now: DateTime<Utc>) -> FocusState {
+        self.snapshot(now);
+        self.state.paused = true;
+        self.state.clone()
+    }
+
+    fn resume(&mut self, now: DateTime<Utc>) -> FocusState {
+        if self.state.paused {
+            self.ends_at = now + ChronoDuration::seconds(self.state.remaining_seconds);
+            self.state.paused = false;
+        }
+        self.state.clone()
+    }
+
+    /// The state after the current phase ends, run to the end (`completed`)
+    /// or skipped. Only completed focus intervals count toward the long
+    /// break. A new focus interval has no session until one is opened.
+    fn next_phase(&self, completed: bool) -> FocusState {
+        let settings = &self.settings;
+        let mut next = self.state.clone();
+        match self.state.phase {
+            FocusPhase::Focus => {
+                if completed {
+                    next.completed_cycles += 1;
+                }
+                let long = completed && next.completed_cycles % settings.long_break_every.max(1) == 0;
+                next.phase = if long { FocusPhase::LongBreak } else { FocusPhase::ShortBreak };
+                next.phase_seconds = if long {
+                    settings.long_break_minutes * 60
+                } else {
+                    settings.short_break_minutes * 60
+                };
+                next.paused = false;
+            }
+            FocusPhase::ShortBreak | FocusPhase::LongBreak => {
+                next.phase = FocusPhase::Focus;
+                next.phase_seconds = settings.focus_minutes * 60;
+                next.paused = !settings.auto_start_focus;
+            }
+        }
+        next.session_id = None;
+        next.remaining_seconds = next.phase_seconds;
+        next
+    }
+
+    /// Switches to `next`, unless the timer left the `from` phase meanwhile.
+    fn advance(&mut self, from: &FocusState, next: FocusState, now: DateTime<Utc>) -> bool {
+        if self.state.phase != from.phase || self.state.session_id != from.session_id {
+            return false;
+        }
+        self.ends_at = now + ChronoDuration::seconds(next.phase_seconds);
+        self.state = next;
+        true
+    }
+}
+
+struct FocusRun {
+    timer: FocusTimer,
+    ticker: tauri::async_runtime::JoinHandle<()>,
+}
+
+/// The running focus (Pomodoro) timer, shared through `AppState`. It lives in
+/// the backend so it keeps time across webview reloads; the frontend renders
+/// `focus-tick` / `focus-phase-changed` and can ask `get_focus_state`.
+#[derive(Clone, Default)]
+pub struct FocusEngine(Arc<Mutex<Option<FocusRun>>>);
+
+impl FocusEngine {
+    fn lock(&self) -> MutexGuard<'_, Option<FocusRun>> {
+        self.0.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Current state with the remaining time brought up to date.
+    fn snapshot(&self) -> Option<FocusState> {
+        Some(self.lock().as_mut()?.timer.snapshot(Utc::now()))
+    }
+}
+
+#[tauri::command]
+pub async fn get_focus_settings(state: State<'_, crate::AppState>) -> Result<FocusSettings, String> {
+    read_setting(&state.db, FOCUS_SETTINGS_KEY).await
+}
+
+#[tauri::command]
+pub async fn update_focus_settings(
+    state: State<'_, crate::AppState>,
+    settings: FocusSettings,
+) -> Result<(), String> {
+    if settings.focus_minutes < 1 || settings.short_break_minutes < 1 || settings.long_break_minutes < 1 {
+        return Err("Focus and break lengths must be at least one minute".to_string());
+    }
+    if settings.long_break_every < 1 {
+        return Err("Long break interval must be at least 1".to_string());
+    }
+
+    write_setting(&state.db, FOCUS_SETTINGS_KEY, &settings).await
+}
+
+/// The running timer, if any. Call this after a reload to pick the timer up again.
+#[tauri::command]
+pub async fn get_focus_state(state: State<'_, crate::AppState>) -> Result<Option<FocusState>, String> {
+    Ok(state.focus.snapshot())
+}
+
+/// Starts a focus interval, optionally bound to a project task or airdrop.
+/// Fails while a timer is already running.
+#[tauri::command]
+pub async fn start_focus(
+    app: AppHandle,
+    state: State<'_, crate::AppState>,
+    req: StartFocusRequest,
+) -> Result<FocusState, String> {
+    if state.focus.lock().is_some() {
+        return Err("A focus timer is already running".to_string());
+    }
+
+    let settings: FocusSettings = read_setting(&state.db, FOCUS_SETTINGS_KEY).await?;
+    let session_id = open_session(&state.db, req.task_id, req.airdrop_id, settings.focus_minutes * 60).await?;
+
+    let timer = FocusTimer::start(settings, req.task_id, req.airdrop_id, session_id, Utc::now());
+    let focus_state = timer.state.clone();
+
+    let started = {
+        let mut guard = state.focus.lock();
+        // Another start may have won the race while the session was opening
+        let free = guard.is_none();
+        if free {
+            *guard = Some(FocusRun {
+                timer,
+                ticker: spawn_ticker(app.clone(), state.db.clone(), state.focus.clone()),
+            });
+        }
+        free
+    };
+    if !started {
+        close_session(&state.db, session_id, 0, false).await?;
+        return Err("A focus timer is already running".to_string());
+    }
+
+    info!("phase=focus_start_ok session={} task={:?} airdrop={:?}", session_id, req.task_id, req.airdrop_id);
+    let _ = app.emit("focus-phase-changed", focus_state.clone());
+    Ok(focus_state)
+}
+
+#[tauri::command]
+pub async fn pause_focus(app: AppHandle, state: State<'_, crate::AppState>) -> Result<FocusState, String> {
+    let snapshot = {
+        let mut guard = state.focus.lock();
+        let run = guard.as_mut().ok_or("No focus timer is running")?;
+        run.timer.pause(Utc::now())
+    };
+
+    let _ = app.emit("focus-tick", snapshot.clone());
+    Ok(snapshot)
+}
+
+#[tauri::command]
+pub async fn resume_focus(app: AppHandle, state: State<'_, crate::AppState>) -> Result<FocusState, String> {
+    let snapshot = {
+        let mut guard = state.focus.lock();
+        let run = guard.as_mut().ok_or("No focus timer is running")?;
+        run.timer.resume(Utc::now())
+    };
+
+    let _ = app.emit("focus-tick", snapshot.clone());
+    Ok(snapshot)
+}
+
+/// Ends the current phase early. A skipped focus interval is saved as not
+/// completed and does not count toward the long break.
+#[tauri::command]
+pub async fn skip_focus_phase(app: AppHandle, state: State<'_, crate::AppState>) -> Result<FocusState, String> {
+    advance_phase(&app, &state.db, &state.focus, false)
+        .await?
+        .ok_or_else(|| "No focus timer is running".to_string())
+}
+
+/// Stops the timer, saving the current focus interval as not completed.
+#[tauri::command]
+pub async fn stop_focus(app: AppHandle, state: State<'_, crate::AppState>) -> Result<(), String> {
+    if let Some(snapshot) = stop_running_focus(&state.db, &state.focus).await? {
+        let _ = app.emit("focus-stopped", snapshot);
+    }
+    Ok(())
+}
+
+/// Stops the timer if one is running and returns its final state. Also
+/// called on app exit so no focus interval is left open.
+pub(crate) async fn stop_running_focus(db: &SqlitePool, engine: &FocusEngine) -> Result<Option<FocusState>, String> {
+    let Some(mut run) = engine.lock().take() else {
+        return Ok(None);
+    };
+    run.ticker.abort();
+    let snapshot = run.timer.snapshot(Utc::now());
+
+    if let (FocusPhase::Focus, Some(session_id)) = (snapshot.phase, snapshot.session_id) {
+        close_session(db, session_id, snapshot.elapsed_seconds(), false).await?;
+    }
+
+    info!("phase=focus_stop_ok cycles={}", snapshot.completed_cycles);
+    Ok(Some(snapshot))
+}
+
+/// Notes an interruption against the current focus interval.
+#[tauri::command]
+pub async fn add_focus_interruption(state: State<'_, crate::AppState>, note: String) -> Result<i64, String> {
+    let note = note.trim();
+    if note.is_empty() {
+        return Err("Interruption note is required".to_string());
+    }
+    let session_id = state
+        .focus
+        .snapshot()
+        .and_then(|s| s.session_id)
+        .ok_or("No focus interval is running")?;
+
+    let id = sqlx::query("INSERT INTO focus_interruptions (session_id, note, noted_at) VALUES (?, ?, ?)")
+        .bind(session_id)
+        .bind(note)
+        .bind(Utc::now().to_rfc3339())
+        .execute(&state.db)
+        .await
+        .map_err(|e| format!("Failed to save interruption: {}", e))?
+        .last_insert_rowid();
+
+    Ok(id)
+}
+
+/// Finished focus intervals, newest first, with their interruption notes.
+#[tauri::command]
+pub async fn list_focus_sessions(
+    state: State<'_, crate::AppState>,
+    filter: Option<FocusSessionFilter>,
+) -> Result<Vec<FocusSessionDetail>, String> {
+    let filter = filter.unwrap_or_default();
+    let sessions = sqlx::query_as::<_, FocusSession>(
+        r#"
+        SELECT * FROM focus_sessions
+        WHERE ended_at IS NOT NULL
+          AND (? IS NULL OR task_id = ?)
+          AND (? IS NULL OR airdrop_id = ?)
+        ORDER BY started_at DESC
+        LIMIT ?
+        "#,
+    )
+    .bind(filter.task_id)
+    .bind(filter.task_id)
+    .bind(filter.airdrop_id)
+    .bind(filter.airdrop_id)
+    .bind(filter.limit.unwrap_or(100))
+    .fetch_all(&state.db)
+    .await
+    .map_err(|e| format!("Failed to fetch focus sessions: {}", e))?;
+
+    let mut details = Vec::with_capacity(sessions.len());
+    for session in sessions {
+        let interruptions = sqlx::query_as::<_, FocusInterruption>(
+            "SELECT * FROM focus_interruptions WHERE session_id = ? ORDER BY noted_at ASC",
+        )
+        .bind(session.id)
+        .fetch_all(&state.db)
+        .await
+        .map_err(|e| format!("Failed to fetch interruptions: {}", e))?;
+        details.push(FocusSessionDetail { session, interruptions });
+    }
+
+    Ok(details)
+}
+
+/// Closes focus intervals left open when the app last crashed, counting
+/// them as focused for their planned length (or until now, if sooner).
+pub(crate) async fn close_stale_sessions(db: &SqlitePool) -> Result<(), String> {
+    let open = sqlx::query_as::<_, FocusSession>("SELECT * FROM focus_sessions WHERE ended_at IS NULL")
+        .fetch_all(db)
+        .await
+        .map_err(|e| format!("Failed to fetch open focus sessions: {}", e))?;
+
+    let now = Utc::now();
+    for session in open {
+        let ended_at = stale_session_end(&session, now);
+        sqlx::query("UPDATE focus_sessions SET focused_seconds = ?, completed = 0, ended_at = ? WHERE id = ?")
+            .bind((ended_at - session.started_at).num_seconds())
+            .bind(ended_at.to_rfc3339())
+            .bind(session.id)
+            .execute(db)
+            .await
+            .map_err(|e| format!("Failed to close stale focus session: {}", e))?;
+        info!("phase=focus_recover_ok session={}", session.id);
+    }
+
+    Ok(())
+}
+
+fn stale_session_end(session: &FocusSession, now: DateTime<Utc>) -> DateTime<Utc> {
+    (session.started_at + ChronoDuration::seconds(session.planned_seconds.max(0)))
+        .min(now)
+        .max(session.started_at)
+}
+
+fn spawn_ticker(app: AppHandle, db: SqlitePool, engine: FocusEngine) -> tauri::async_runtime::JoinHandle<()> {
+    tauri::async_runtime::spawn(async move {
+        let mut interval = tokio::time::interval(TICK_INTERVAL);
+        loop {
+            interval.tick().await;
+            let Some(snapshot) = engine.snapshot() else {
+                break;
+            };
+            let finished = !snapshot.paused && snapshot.remaining_seconds == 0;
+            let _ = app.emit("focus-tick", snapshot);
+            if finished {
+                if let Err(e) = advance_phase(&app, &db, &engine, true).await {
+                    warn!("phase=focus_advance_failed error={}", e);
+                }
+            }
+        }
+    })
+}
+
+/// Moves from focus to a break or from a break to the next focus interval.
+async fn advance_phase(
+    app: &AppHandle,
+    db: &SqlitePool,
+    engine: &FocusEngine,
+    completed: bool,
+) -> Result<Option<FocusState>, String> {
+    let transition = engine.lock().as_mut().map(|run| {
+        let current = run.timer.snapshot(Utc::now());
+        (current, run.timer.next_phase(completed))
+    });
+    let Some((current, mut next)) = transition else {
+        return Ok(None);
+    };
+
+    if let (FocusPhase::Focus, Some(session_id)) = (current.phase, current.session_id) {
+        close_session(db, session_id, current.elapsed_seconds(), completed).await?;
+    }
+    if next.phase == FocusPhase::Focus {
+        next.session_id = Some(open_session(db, next.task_id, next.airdrop_id, next.phase_seconds).await?);
+    }
+
+    // Only apply if nobody stopped or advanced the timer meanwhile
+    let applied = engine
+        .lock()
+        .as_mut()
+        .is_some_and(|run| run.timer.advance(&current, next.clone(), Utc::now()));
+    if !applied {
+        if let (FocusPhase::Focus, Some(session_id)) = (next.phase, next.session_id) {
+            close_session(db, session_id, 0, false).await?;
+        }
+        return Ok(None);
+    }
+
+    info!("phase=focus_phase_ok phase={:?} cycles={}", next.phase, next.completed_cycles);
+    let _ = app.emit("focus-phase-changed", next.clone());
+    notify_phase(app, &next);
+    Ok(Some(next))
+}
+
+fn notify_phase(app: &AppHandle, state: &FocusState) {
+    let (title, body) = match state.phase {
+        FocusPhase::Focus if state.paused => ("Break over", "Resume when you're ready to focus."),
+        FocusPhase::Focus => ("Back to focus", "Next focus interval started."),
+        FocusPhase::ShortBreak => ("Time for a break", "Step away for a few minutes."),
+        FocusPhase::LongBreak => ("Time for a long break", "Nice run. Take a proper rest."),
+    };
+    if let Err(e) = app.notification().builder().title(title).body(body).show() {
+        warn!("phase=focus_notification_failed error={}", e);
+    }
+}
+
+async fn open_session(
+    db: &SqlitePool,
+    task_id: Option<i64>,
+    airdrop_id: Option<i64>,
+    planned_seconds: i64,
+) -> Result<i64, String> {
+    let id = sqlx::query(
+        r#"
+        INSERT INTO focus_sessions (task_id, airdrop_id, planned_seconds, started_at)
+        VALUES (?, ?, ?, ?)
+        "#,
+    )
+    .bind(task_id)
+    .bind(airdrop_id)
+    .bind(planned_seconds)
+    .bind(Utc::now().to_rfc3339())
+    .execute(db)
+    .await
+    .map_err(|e| format!("Failed to start focus session: {}", e))?
+    .last_insert_rowid();
+
+    Ok(id)
+}
+
+async fn close_session(db: &SqlitePool, id: i64, focused_seconds: i64, completed: bool) -> Result<(), String> {
+    sqlx::query(
+        r#"
+        UPDATE focus_sessions SET focused_seconds = ?, completed = ?, ended_at = ?
+        WHERE id = ?
+        "#,
+    )
+    .bind(focused_seconds.max(0))
+    .bind(if completed { 1 } else { 0 })
+    .bind(Utc::now().to_rfc3339())
+    .bind(id)
+    .execute(db)
+    .await
+    .map_err(|e| format!("Failed to save focus session: {}", e))?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings(auto_start_focus: bool) -> FocusSettings {
+        FocusSettings {
+            long_break_every: 2,
+            auto_start_focus,
+            ..FocusSettings::default()
+        }
+    }
+
+    fn at(seconds: i64) -> DateTime<Utc> {
+        DateTime::from_timestamp(1_700_000_000 + seconds, 0).unwrap()
+    }
+
+    /// Runs the current phase to its end (or skips it) and opens a session
+    /// for a new focus interval, as the engine does.
+    fn finish(timer: &mut FocusTimer, completed: bool, now: DateTime<Utc>, session_id: i64) -> FocusState {
+        let current = timer.snapshot(now);
+        let mut next = timer.next_phase(completed);
+        if next.phase == FocusPhase::Focus {
+            next.session_id = Some(session_id);
+        }
+        assert!(timer.advance(&current, next, now));
+        timer.state.clone()
+    }
+
+    #[test]
+    fn pausing_freezes_the_remaining_time() {
+        let mut timer = FocusTimer::start(settings(true), Some(7), None, 1, at(0));
+        assert_eq!(timer.snapshot(at(60)).remaining_seconds, 25 * 60 - 60);
+
+        let paused = timer.pause(at(100));
+        assert!(paused.paused);
+        assert_eq!(paused.elapsed_seconds(), 100);
+        assert_eq!(timer.snapshot(at(1_000)).remaining_seconds, 25 * 60 - 100);
+
+        timer.resume(at(1_000));
+        assert_eq!(timer.snapshot(at(1_050)).remaining_seconds, 25 * 60 - 150);
+    }
+
+    #[test]
+    fn completed_focus_intervals_lead_to_short_then_long_breaks() {
+        let mut timer = FocusTimer::start(settings(true), None, Some(3), 1, at(0));
+
+        let first_break = finish(&mut timer, true, at(1_500), 0);
+        assert_eq!(first_break.phase, FocusPhase::ShortBreak);
+        assert_eq!(first_break.phase_seconds, 5 * 60);
+        assert_eq!(first_break.completed_cycles, 1);
+        assert_eq!(first_break.session_id, None);
+
+        let focus = finish(&mut timer, true, at(1_800), 2);
+        assert_eq!(focus.phase, FocusPhase::Focus);
+        assert_eq!(focus.session_id, Some(2));
+        assert_eq!(focus.airdrop_id, Some(3));
+        assert!(!focus.paused);
+
+        let second_break = finish(&mut timer, true, at(3_300), 0);
+        assert_eq!(second_break.phase, FocusPhase::LongBreak);
+        assert_eq!(second_break.phase_seconds, 15 * 60);
+        assert_eq!(second_break.completed_cycles, 2);
+    }
+
+    #[test]
+    fn skipped_focus_intervals_do_not_count_toward_the_long_break() {
+        let mut timer = FocusTimer::start(settings(true), None, None, 1, at(0));
+        finish(&mut timer, true, at(1_500), 0);
+        finish(&mut timer, true, at(1_800), 2);
+
+        let skipped = finish(&mut timer, false, at(1_900), 0);
+        assert_eq!(skipped.phase, FocusPhase::ShortBreak);
+        assert_eq!(skipped.completed_cycles, 1);
+    }
+
+    #[test]
+    fn breaks_end_paused_without_auto_start() {
+        let mut timer = FocusTimer::start(settings(false), None, None, 1, at(0));
+        finish(&mut timer, true, at(1_500), 0);
+
+        let focus = finish(&mut timer, true, at(1_800), 2);
+        assert!(focus.paused);
+        assert_eq!(timer.snapshot(at(5_000)).remaining_seconds, 25 * 60);
+    }
+
+    #[test]
+    fn stale_transitions_are_not_applied() {
+        let mut timer = FocusTimer::start(settings(true), None, None, 1, at(0));
+        let current = timer.snapshot(at(10));
+        let next = timer.next_phase(false);
+        finish(&mut timer, false, at(20), 0);
+
+        // The phase already moved on, e.g. a skip raced the ticker
+        assert!(!timer.advance(&current, next, at(30)));
+        assert_eq!(timer.state.phase, FocusPhase::ShortBreak);
+    }
+
+    #[test]
+    fn stale_sessions_count_their_planned_time_up_to_now() {
+        let session = FocusSession {
+            id: 1,
+            task_id: None,
+            airdrop_id: None,
+            planned_seconds: 1_500,
+            focused_seconds: 0,
+            completed: false,
+            started_at: at(0),
+            ended_at: None,
+        };
+
+        assert_eq!(stale_session_end(&session, at(10_000)), at(1_500));
+        assert_eq!(stale_session_end(&session, at(600)), at(600));
+        assert_eq!(stale_session_end(&session, at(-5)), at(0));
+    }
+}
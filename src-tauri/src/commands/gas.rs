@@ -0,0 +1,342 @@
+use log::{info, warn};
+use tauri::{AppHandle, Emitter, State};
+use sqlx::SqlitePool;
+use chrono::{Duration as ChronoDuration, Utc};
+use serde::Serialize;
+use std::time::Duration;
+use tauri_plugin_notification::NotificationExt;
+
+use crate::models::{Chain, GasPrice};
+use crate::rpc;
+
+/// How often the background watcher samples every configured chain.
+const GAS_POLL_INTERVAL: Duration = Duration::from_secs(2 * 60);
+/// Samples older than this are pruned; the history only backs a sparkline.
+const GAS_HISTORY_HOURS: i64 = 24;
+const WEI_PER_GWEI: f64 = 1_000_000_000.0;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct GasPollResult {
+    pub chain_id: i64,
+    pub chain_name: String,
+    pub sample: Option<GasPrice>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct GasPriceAlert {
+    pub chain_id: i64,
+    pub chain_name: String,
+    pub gas_price_gwei: f64,
+    pub threshold_gwei: f64,
+}
+
+#[tauri::command]
+pub async fn poll_gas_prices(
+    app: AppHandle,
+    state: State<'_, crate::AppState>,
+    chain_id: Option<i64>,
+) -> Result<Vec<GasPollResult>, String> {
+    let results = poll_and_alert(&app, &state.db, chain_id).await?;
+    Ok(results)
+}
+
+#[tauri::command]
+pub async fn list_gas_price_history(
+    state: State<'_, crate::AppState>,
+    chain_id: i64,
+    hours: Option<i64>,
+) -> Result<Vec<GasPrice>, String> {
+    let since = Utc::now() - ChronoDuration::hours(hours.unwrap_or(GAS_HISTORY_HOURS));
+
+    let history = sqlx::query_as::<_, GasPrice>(
+        "SELECT * FROM gas_prices WHERE chain_id = ? AND recorded_at >= ? ORDER BY recorded_at ASC",
+    )
+    .bind(chain_id)
+    .bind(since.to_rfc3339())
+    .fetch_all(&state.db)
+    .await
+    .map_err(|e| format!("Failed to fetch gas history: {}", e))?;
+
+    Ok(history)
+}
+
+#[tauri::command]
+pub async fn latest_gas_prices(state: State<'_, crate::AppState>) -> Result<Vec<GasPrice>, String> {
+    let latest = sqlx::query_as::<_, GasPrice>(
+        r#"
+        SELECT * FROM gas_prices g
+        WHERE g.id = (SELECT MAX(id) FROM gas_prices WHERE chain_id = g.chain_id)
+        ORDER BY g.chain_id ASC
+        "#,
+    )
+    .fetch_all(&state.db)
+    .await
+    .map_err(|e| format!("Failed to fetch gas prices: {}", e))?;
+
+    Ok(latest)
+}
+
+pub(crate) fn spawn_gas_watcher(app: AppHandle, db: SqlitePool) {
+    tauri::async_runtime::spawn(async move {
+        let mut interval = tokio::time::interval(GAS_POLL_INTERVAL);
+        loop {
+            interval.tick().await;
+            if let Err(e) = poll_and_alert(&app, &db, None).await {
+                warn!("phase=gas_poll_failed error={}", e);
+            }
+        }
+    });
+}
+
+/// Samples gas on one chain (or all chains), emits the fresh samples and
+/// raises an alert for each chain that just dropped below its threshold.
+async fn poll_and_alert(
+    app: &AppHandle,
+    db: &SqlitePool,
+    chain_id: Option<i64>,
+) -> Result<Vec<GasPollResult>, String> {
+    let (results, alerts) = sample_chains(db, chain_id).await?;
+    for alert in &alerts {
+        notify(app, alert);
+    }
+
+    if !results.is_empty() {
+        let _ = app.emit("gas-prices-updated", results.clone());
+    }
+    Ok(results)
+}
+
+/// Stores a fresh sample per chain and returns the alerts for chains that
+/// just crossed below their threshold.
+pub(crate) async fn sample_chains(
+    db: &SqlitePool,
+    chain_id: Option<i64>,
+) -> Result<(Vec<GasPollResult>, Vec<GasPriceAlert>), String> {
+    let chains = match chain_id {
+        Some(chain_id) => {
+            sqlx::query_as::<_, Chain>("SELECT * FROM chains WHERE id = ?")
+                .bind(chain_id)
+                .fetch_all(db)
+                .await
+        }
+        None => {
+            sqlx::query_as::<_, Chain>("SELECT * FROM chains")
+                .fetch_all(db)
+                .await
+        }
+    }
+    .map_err(|e| format!("Failed to fetch chains: {}", e))?;
+
+    let client = rpc::client()?;
+    let mut results = Vec::with_capacity(chains.len());
+    let mut alerts = Vec::new();
+    for chain in &chains {
+        let previous = sqlx::query_scalar::<_, f64>(
+            "SELECT gas_price_gwei FROM gas_prices WHERE chain_id = ? ORDER BY id DESC LIMIT 1",
+        )
+        .bind(chain.id)
+        .fetch_optional(db)
+        .await
+        .map_err(|e| format!("Failed to fetch gas history: {}", e))?;
+
+        match sample_chain(&client, db, chain).await {
+            Ok(sample) => {
+                alerts.extend(crossed_below(chain, previous, sample.gas_price_gwei));
+                results.push(GasPollResult {
+                    chain_id: chain.id,
+                    chain_name: chain.name.clone(),
+                    sample: Some(sample),
+                    error: None,
+                });
+            }
+            Err(e) => {
+                warn!("phase=gas_sample_failed chain={} error={}", chain.name, e);
+                results.push(GasPollResult {
+                    chain_id: chain.id,
+                    chain_name: chain.name.clone(),
+                    sample: None,
+                    error: Some(e),
+                });
+            }
+        }
+    }
+
+    let cutoff = Utc::now() - ChronoDuration::hours(GAS_HISTORY_HOURS);
+    sqlx::query("DELETE FROM gas_prices WHERE recorded_at < ?")
+        .bind(cutoff.to_rfc3339())
+        .execute(db)
+        .await
+        .map_err(|e| format!("Failed to prune gas history: {}", e))?;
+
+    Ok((results, alerts))
+}
+
+async fn sample_chain(
+    client: &reqwest::Client,
+    db: &SqlitePool,
+    chain: &Chain,
+) -> Result<GasPrice, String> {
+    let gas_price = rpc::gas_price(client, &chain.rpc_url).await?;
+    // Pre-London chains and some L2s don't implement eth_feeHistory.
+    let (base_fee, priority_fee) = match rpc::fee_history(client, &chain.rpc_url).await {
+        Ok((base, priority)) => (Some(base), priority),
+        Err(_) => (None, None),
+    };
+    let gwei = gas_price as f64 / WEI_PER_GWEI;
+    let now = Utc::now();
+
+    let id = sqlx::query(
+        r#"
+        INSERT INTO gas_prices (chain_id, gas_price_wei, base_fee_wei, priority_fee_wei, gas_price_gwei, recorded_at)
+        VALUES (?, ?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(chain.id)
+    .bind(gas_price.to_string())
+    .bind(base_fee.map(|f| f.to_string()))
+    .bind(priority_fee.map(|f| f.to_string()))
+    .bind(gwei)
+    .bind(now.to_rfc3339())
+    .execute(db)
+    .await
+    .map_err(|e| format!("Failed to store gas price: {}", e))?
+    .last_insert_rowid();
+
+    info!("phase=gas_sample_ok chain={} gwei={:.3}", chain.name, gwei);
+    Ok(GasPrice {
+        id,
+        chain_id: chain.id,
+        gas_price_wei: gas_price.to_string(),
+        base_fee_wei: base_fee.map(|f| f.to_string()),
+        priority_fee_wei: priority_fee.map(|f| f.to_string()),
+        gas_price_gwei: gwei,
+        recorded_at: now,
+    })
+}
+
+/// Only alert on the transition, so a chain sitting under its threshold
+/// doesn't notify on every poll.
+fn crossed_below(chain: &Chain, previous_gwei: Option<f64>, current_gwei: f64) -> Option<GasPriceAlert> {
+    let threshold = chain.gas_threshold_gwei.filter(|_| chain.gas_alerts_enabled)?;
+    let was_below = previous_gwei.is_some_and(|p| p < threshold);
+    (current_gwei < threshold && !was_below).then(|| GasPriceAlert {
+        chain_id: chain.id,
+        chain_name: chain.name.clone(),
+        gas_price_gwei: current_gwei,
+        threshold_gwei: threshold,
+    })
+}
+
+fn notify(app: &AppHandle, alert: &GasPriceAlert) {
+    info!(
+        "phase=gas_alert chain={} gwei={:.3} threshold={}",
+        alert.chain_name, alert.gas_price_gwei, alert.threshold_gwei
+    );
+    let _ = app.emit("gas-price-alert", alert.clone());
+
+    if let Err(e) = app
+        .notification()
+        .builder()
+        .title(format!("Low gas on {}", alert.chain_name))
+        .body(format!(
+            "{:.2} gwei (threshold {} gwei)",
+            alert.gas_price_gwei, alert.threshold_gwei
+        ))
+        .show()
+    {
+        warn!("phase=gas_notification_failed error={}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{memory_db, rpc_stand_in};
+    use serde_json::json;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    fn chain(threshold: Option<f64>, enabled: bool) -> Chain {
+        Chain {
+            id: 1,
+            name: "Base".to_string(),
+            chain_id: Some(8453),
+            rpc_url: String::new(),
+            native_symbol: "ETH".to_string(),
+            gas_threshold_gwei: threshold,
+            gas_alerts_enabled: enabled,
+            min_native_balance: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn alerts_only_when_crossing_below() {
+        let base = chain(Some(5.0), true);
+        assert!(crossed_below(&base, None, 4.0).is_some());
+        assert!(crossed_below(&base, Some(6.0), 4.0).is_some());
+        assert!(crossed_below(&base, Some(4.5), 4.0).is_none());
+        assert!(crossed_below(&base, Some(6.0), 5.0).is_none());
+        assert!(crossed_below(&base, Some(4.0), 7.0).is_none());
+
+        assert!(crossed_below(&chain(Some(5.0), false), Some(6.0), 4.0).is_none());
+        assert!(crossed_below(&chain(None, true), Some(6.0), 4.0).is_none());
+    }
+
+    #[tokio::test]
+    async fn samples_node_and_alerts_on_each_drop() {
+        // One eth_gasPrice answer per poll, in gwei
+        let prices = [10u128, 3, 2, 8, 4];
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = calls.clone();
+        let rpc_url = rpc_stand_in(move |method, _| match method {
+            "eth_gasPrice" => {
+                let gwei = prices[counter.fetch_add(1, Ordering::SeqCst)];
+                Ok(json!(format!("0x{:x}", gwei * 1_000_000_000)))
+            }
+            _ => Err(json!({ "code": -32601, "message": "the method eth_feeHistory does not exist" })),
+        })
+        .await;
+
+        let db = memory_db().await;
+        let now = Utc::now().to_rfc3339();
+        sqlx::query("INSERT INTO chains (name, rpc_url, gas_threshold_gwei, created_at, updated_at) VALUES ('Base', ?, 5.0, ?, ?)")
+            .bind(&rpc_url)
+            .bind(&now)
+            .bind(&now)
+            .execute(&db)
+            .await
+            .unwrap();
+
+        let mut alerted = Vec::new();
+        for _ in prices {
+            let (results, alerts) = sample_chains(&db, None).await.unwrap();
+            let sample = results[0].sample.as_ref().unwrap();
+            assert_eq!(sample.base_fee_wei, None);
+            alerted.push(alerts.first().map(|a| a.gas_price_gwei));
+        }
+
+        assert_eq!(alerted, vec![None, Some(3.0), None, None, Some(4.0)]);
+        let history: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM gas_prices").fetch_one(&db).await.unwrap();
+        assert_eq!(history, 5);
+    }
+
+    #[tokio::test]
+    async fn reports_unreachable_nodes_per_chain() {
+        let db = memory_db().await;
+        let now = Utc::now().to_rfc3339();
+        sqlx::query("INSERT INTO chains (name, rpc_url, created_at, updated_at) VALUES ('Dead', 'http://127.0.0.1:9', ?, ?)")
+            .bind(&now)
+            .bind(&now)
+            .execute(&db)
+            .await
+            .unwrap();
+
+        let (results, alerts) = sample_chains(&db, None).await.unwrap();
+        assert!(results[0].sample.is_none());
+        assert!(results[0].error.as_deref().unwrap().contains("eth_gasPrice"));
+        assert!(alerts.is_empty());
+    }
+}
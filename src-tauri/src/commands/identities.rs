@@ -0,0 +1,616 @@
+use log::{info, warn};
+use tauri::State;
+use sqlx::SqlitePool;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+use crate::models::{AirdropReferral, SocialIdentity, Wallet};
+
+#[derive(Debug, Deserialize)]
+pub struct CreateSocialIdentityRequest {
+    pub platform: String,
+    pub handle: String,
+    pub notes: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateSocialIdentityRequest {
+    pub id: i64,
+    pub platform: Option<String>,
+    pub handle: Option<String>,
+    pub notes: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct LinkAirdropIdentityRequest {
+    pub airdrop_id: i64,
+    pub identity_id: i64,
+    pub wallet_id: Option<i64>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct IdentityUsageFilter {
+    pub identity_id: Option<i64>,
+    pub wallet_id: Option<i64>,
+    pub airdrop_id: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateAirdropReferralRequest {
+    pub airdrop_id: i64,
+    pub wallet_id: Option<i64>,
+    pub code: Option<String>,
+    pub link: Option<String>,
+    pub notes: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateAirdropReferralRequest {
+    pub id: i64,
+    pub wallet_id: Option<i64>,
+    pub code: Option<String>,
+    pub link: Option<String>,
+    pub notes: Option<String>,
+}
+
+/// One identity linked to one airdrop, with the wallet it was used from.
+#[derive(Debug, Clone, Serialize)]
+pub struct IdentityUsage {
+    pub link_id: i64,
+    pub identity_id: i64,
+    pub platform: String,
+    pub handle: String,
+    pub airdrop_id: i64,
+    pub airdrop_name: String,
+    pub wallet_id: Option<i64>,
+    pub wallet_label: Option<String>,
+    pub wallet_address: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct WalletRef {
+    pub id: i64,
+    pub label: String,
+    pub address: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct IdentityReuseWarning {
+    pub identity_id: i64,
+    pub platform: String,
+    pub handle: String,
+    pub wallets: Vec<WalletRef>,
+    pub airdrop_ids: Vec<i64>,
+    pub message: String,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct IdentityUsageRow {
+    link_id: i64,
+    identity_id: i64,
+    platform: String,
+    handle: String,
+    airdrop_id: i64,
+    airdrop_name: String,
+    wallet_id: Option<i64>,
+    airdrop_wallet_address: Option<String>,
+}
+
+#[tauri::command]
+pub async fn list_social_identities(
+    state: State<'_, crate::AppState>,
+) -> Result<Vec<SocialIdentity>, String> {
+    let identities = sqlx::query_as::<_, SocialIdentity>(
+        "SELECT * FROM social_identities ORDER BY platform ASC, handle ASC",
+    )
+    .fetch_all(&state.db)
+    .await
+    .map_err(|e| format!("Failed to fetch identities: {}", e))?;
+
+    Ok(identities)
+}
+
+#[tauri::command]
+pub async fn create_social_identity(
+    state: State<'_, crate::AppState>,
+    req: CreateSocialIdentityRequest,
+) -> Result<i64, String> {
+    let now = Utc::now();
+
+    let id = sqlx::query(
+        r#"
+        INSERT INTO social_identities (platform, handle, notes, created_at, updated_at)
+        VALUES (?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(req.platform.trim().to_lowercase())
+    .bind(req.handle.trim())
+    .bind(&req.notes)
+    .bind(now.to_rfc3339())
+    .bind(now.to_rfc3339())
+    .execute(&state.db)
+    .await
+    .map_err(|e| format!("Failed to create identity: {}", e))?
+    .last_insert_rowid();
+
+    Ok(id)
+}
+
+#[tauri::command]
+pub async fn update_social_identity(
+    state: State<'_, crate::AppState>,
+    req: UpdateSocialIdentityRequest,
+) -> Result<(), String> {
+    let mut updates = Vec::new();
+    if req.platform.is_some() {
+        updates.push("platform = ?");
+    }
+    if req.handle.is_some() {
+        updates.push("handle = ?");
+    }
+    if req.notes.is_some() {
+        updates.push("notes = ?");
+    }
+    updates.push("updated_at = ?");
+
+    let query = format!(
+        "UPDATE social_identities SET {} WHERE id = ?",
+        updates.join(", ")
+    );
+
+    let mut q = sqlx::query(&query);
+    if let Some(platform) = &req.platform {
+        q = q.bind(platform.trim().to_lowercase());
+    }
+    if let Some(handle) = &req.handle {
+        q = q.bind(handle.trim());
+    }
+    if let Some(notes) = &req.notes {
+        q = q.bind(notes);
+    }
+    q = q.bind(Utc::now().to_rfc3339());
+    q = q.bind(req.id);
+
+    q.execute(&state.db)
+        .await
+        .map_err(|e| format!("Failed to update identity: {}", e))?;
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn delete_social_identity(
+    state: State<'_, crate::AppState>,
+    id: i64,
+) -> Result<(), String> {
+    sqlx::query("DELETE FROM social_identities WHERE id = ?")
+        .bind(id)
+        .execute(&state.db)
+        .await
+        .map_err(|e| format!("Failed to delete identity: {}", e))?;
+
+    Ok(())
+}
+
+/// Links an identity to an airdrop and returns any reuse warnings for that identity.
+#[tauri::command]
+pub async fn link_airdrop_identity(
+    state: State<'_, crate::AppState>,
+    req: LinkAirdropIdentityRequest,
+) -> Result<Vec<IdentityReuseWarning>, String> {
+    let existing = sqlx::query_scalar::<_, i64>(
+        "SELECT id FROM airdrop_identities WHERE airdrop_id = ? AND identity_id = ? AND wallet_id IS ?",
+    )
+    .bind(req.airdrop_id)
+    .bind(req.identity_id)
+    .bind(req.wallet_id)
+    .fetch_optional(&state.db)
+    .await
+    .map_err(|e| format!("Failed to check identity link: {}", e))?;
+
+    if existing.is_none() {
+        sqlx::query(
+            r#"
+            INSERT INTO airdrop_identities (airdrop_id, identity_id, wallet_id, created_at)
+            VALUES (?, ?, ?, ?)
+            "#,
+        )
+        .bind(req.airdrop_id)
+        .bind(req.identity_id)
+        .bind(req.wallet_id)
+        .bind(Utc::now().to_rfc3339())
+        .execute(&state.db)
+        .await
+        .map_err(|e| format!("Failed to link identity: {}", e))?;
+    }
+
+    let warnings: Vec<IdentityReuseWarning> = reuse_warnings(&state.db)
+        .await?
+        .into_iter()
+        .filter(|w| w.identity_id == req.identity_id)
+        .collect();
+
+    for warning in &warnings {
+        warn!(
+            "phase=identity_reuse_warning identity_id={} wallets={}",
+            warning.identity_id,
+            warning.wallets.len()
+        );
+    }
+    info!(
+        "phase=identity_link_ok airdrop_id={} identity_id={}",
+        req.airdrop_id, req.identity_id
+    );
+    Ok(warnings)
+}
+
+#[tauri::command]
+pub async fn unlink_airdrop_identity(
+    state: State<'_, crate::AppState>,
+    id: i64,
+) -> Result<(), String> {
+    sqlx::query("DELETE FROM airdrop_identities WHERE id = ?")
+        .bind(id)
+        .execute(&state.db)
+        .await
+        .map_err(|e| format!("Failed to unlink identity: {}", e))?;
+
+    Ok(())
+}
+
+/// Looks up where identities are used, filtered by identity, wallet or airdrop.
+#[tauri::command]
+pub async fn list_identity_usage(
+    state: State<'_, crate::AppState>,
+    filter: Option<IdentityUsageFilter>,
+) -> Result<Vec<IdentityUsage>, String> {
+    let filter = filter.unwrap_or_default();
+    let usages = identity_usages(&state.db)
+        .await?
+        .into_iter()
+        .filter(|u| filter.identity_id.map_or(true, |id| u.identity_id == id))
+        .filter(|u| filter.wallet_id.map_or(true, |id| u.wallet_id == Some(id)))
+        .filter(|u| filter.airdrop_id.map_or(true, |id| u.airdrop_id == id))
+        .collect();
+
+    Ok(usages)
+}
+
+#[tauri::command]
+pub async fn list_identity_reuse_warnings(
+    state: State<'_, crate::AppState>,
+) -> Result<Vec<IdentityReuseWarning>, String> {
+    reuse_warnings(&state.db).await
+}
+
+#[tauri::command]
+pub async fn list_airdrop_referrals(
+    state: State<'_, crate::AppState>,
+    airdrop_id: Option<i64>,
+) -> Result<Vec<AirdropReferral>, String> {
+    let referrals = match airdrop_id {
+        Some(airdrop_id) => {
+            sqlx::query_as::<_, AirdropReferral>(
+                "SELECT * FROM airdrop_referrals WHERE airdrop_id = ? ORDER BY created_at ASC",
+            )
+            .bind(airdrop_id)
+            .fetch_all(&state.db)
+            .await
+        }
+        None => {
+            sqlx::query_as::<_, AirdropReferral>(
+                "SELECT * FROM airdrop_referrals ORDER BY airdrop_id ASC, created_at ASC",
+            )
+            .fetch_all(&state.db)
+            .await
+        }
+    }
+    .map_err(|e| format!("Failed to fetch referrals: {}", e))?;
+
+    Ok(referrals)
+}
+
+#[tauri::command]
+pub async fn create_airdrop_referral(
+    state: State<'_, crate::AppState>,
+    req: CreateAirdropReferralRequest,
+) -> Result<i64, String> {
+    if req.code.is_none() && req.link.is_none() {
+        return Err("A referral needs a code or a link".to_string());
+    }
+    let now = Utc::now();
+
+    let id = sqlx::query(
+        r#"
+        INSERT INTO airdrop_referrals (airdrop_id, wallet_id, code, link, notes, created_at, updated_at)
+        VALUES (?, ?, ?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(req.airdrop_id)
+    .bind(req.wallet_id)
+    .bind(&req.code)
+    .bind(&req.link)
+    .bind(&req.notes)
+    .bind(now.to_rfc3339())
+    .bind(now.to_rfc3339())
+    .execute(&state.db)
+    .await
+    .map_err(|e| format!("Failed to create referral: {}", e))?
+    .last_insert_rowid();
+
+    Ok(id)
+}
+
+#[tauri::command]
+pub async fn update_airdrop_referral(
+    state: State<'_, crate::AppState>,
+    req: UpdateAirdropReferralRequest,
+) -> Result<(), String> {
+    let mut updates = Vec::new();
+    if req.wallet_id.is_some() {
+        updates.push("wallet_id = ?");
+    }
+    if req.code.is_some() {
+        updates.push("code = ?");
+    }
+    if req.link.is_some() {
+        updates.push("link = ?");
+    }
+    if req.notes.is_some() {
+        updates.push("notes = ?");
+    }
+    updates.push("updated_at = ?");
+
+    let query = format!(
+        "UPDATE airdrop_referrals SET {} WHERE id = ?",
+        updates.join(", ")
+    );
+
+    let mut q = sqlx::query(&query);
+    if let Some(wallet_id) = &req.wallet_id {
+        q = q.bind(wallet_id);
+    }
+    if let Some(code) = &req.code {
+        q = q.bind(code);
+    }
+    if let Some(link) = &req.link {
+        q = q.bind(link);
+    }
+    if let Some(notes) = &req.notes {
+        q = q.bind(notes);
+    }
+    q = q.bind(Utc::now().to_rfc3339());
+    q = q.bind(req.id);
+
+    q.execute(&state.db)
+        .await
+        .map_err(|e| format!("Failed to update referral: {}", e))?;
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn delete_airdrop_referral(
+    state: State<'_, crate::AppState>,
+    id: i64,
+) -> Result<(), String> {
+    sqlx::query("DELETE FROM airdrop_referrals WHERE id = ?")
+        .bind(id)
+        .execute(&state.db)
+        .await
+        .map_err(|e| format!("Failed to delete referral: {}", e))?;
+
+    Ok(())
+}
+
+/// Resolves every identity link to a wallet, falling back to the airdrop's
+/// `wallet_address` when the link doesn't name a wallet.
+pub(crate) async fn identity_usages(db: &SqlitePool) -> Result<Vec<IdentityUsage>, String> {
+    let rows = sqlx::query_as::<_, IdentityUsageRow>(
+        r#"
+        SELECT ai.id AS link_id, ai.identity_id, si.platform, si.handle,
+               ai.airdrop_id, a.name AS airdrop_name, ai.wallet_id,
+               a.wallet_address AS airdrop_wallet_address
+        FROM airdrop_identities ai
+        JOIN social_identities si ON si.id = ai.identity_id
+        JOIN airdrops a ON a.id = ai.airdrop_id
+        ORDER BY si.platform ASC, si.handle ASC, a.name ASC
+        "#,
+    )
+    .fetch_all(db)
+    .await
+    .map_err(|e| format!("Failed to fetch identity usage: {}", e))?;
+
+    let wallets = sqlx::query_as::<_, Wallet>("SELECT * FROM wallets")
+        .fetch_all(db)
+        .await
+        .map_err(|e| format!("Failed to fetch wallets: {}", e))?;
+    let by_id: HashMap<i64, &Wallet> = wallets.iter().map(|w| (w.id, w)).collect();
+    let by_address: HashMap<String, &Wallet> = wallets
+        .iter()
+        .map(|w| (w.address.to_lowercase(), w))
+        .collect();
+
+    Ok(rows
+        .into_iter()
+        .map(|row| {
+            let wallet = row
+                .wallet_id
+                .and_then(|id| by_id.get(&id))
+                .or_else(|| {
+                    row.airdrop_wallet_address
+                        .as_ref()
+                        .and_then(|address| by_address.get(&address.trim().to_lowercase()))
+                });
+            IdentityUsage {
+                link_id: row.link_id,
+                identity_id: row.identity_id,
+                platform: row.platform,
+                handle: row.handle,
+                airdrop_id: row.airdrop_id,
+                airdrop_name: row.airdrop_name,
+                wallet_id: wallet.map(|w| w.id),
+                wallet_label: wallet.map(|w| w.label.clone()),
+                wallet_address: wallet
+                    .map(|w| w.address.clone())
+                    .or(row.airdrop_wallet_address),
+            }
+        })
+        .collect())
+}
+
+/// Flags identities used from two or more wallets that must stay separate.
+pub(crate) async fn reuse_warnings(db: &SqlitePool) -> Result<Vec<IdentityReuseWarning>, String> {
+    let usages = identity_usages(db).await?;
+    let wallets = sqlx::query_as::<_, Wallet>("SELECT * FROM wallets")
+        .fetch_all(db)
+        .await
+        .map_err(|e| format!("Failed to fetch wallets: {}", e))?;
+    let by_id: HashMap<i64, &Wallet> = wallets.iter().map(|w| (w.id, w)).collect();
+
+    let mut by_identity: BTreeMap<i64, Vec<&IdentityUsage>> = BTreeMap::new();
+    for usage in &usages {
+        by_identity.entry(usage.identity_id).or_default().push(usage);
+    }
+
+    let mut warnings = Vec::new();
+    for (identity_id, usages) in by_identity {
+        let mut used_wallets: Vec<&Wallet> = usages
+            .iter()
+            .filter_map(|u| u.wallet_id.and_then(|id| by_id.get(&id).copied()))
+            .collect();
+        used_wallets.sort_by_key(|w| w.id);
+        used_wallets.dedup_by_key(|w| w.id);
+
+        let conflicting = used_wallets.iter().enumerate().any(|(i, a)| {
+            used_wallets[i + 1..]
+                .iter()
+                .any(|b| a.must_stay_separate_from(b))
+        });
+        if !conflicting {
+            continue;
+        }
+
+        let first = usages[0];
+        let mut airdrop_ids: Vec<i64> = usages.iter().map(|u| u.airdrop_id).collect();
+        airdrop_ids.sort_unstable();
+        airdrop_ids.dedup();
+
+        warnings.push(IdentityReuseWarning {
+            identity_id,
+            platform: first.platform.clone(),
+            handle: first.handle.clone(),
+            message: format!(
+                "{} identity {} is used from {} wallets that should stay separate",
+                first.platform,
+                first.handle,
+                used_wallets.len()
+            ),
+            wallets: used_wallets
+                .iter()
+                .map(|w| WalletRef {
+                    id: w.id,
+                    label: w.label.clone(),
+                    address: w.address.clone(),
+                })
+                .collect(),
+            airdrop_ids,
+        });
+    }
+
+    Ok(warnings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::memory_db;
+
+    async fn wallet(db: &SqlitePool, address: &str, group_name: Option<&str>) -> i64 {
+        let now = Utc::now().to_rfc3339();
+        sqlx::query("INSERT INTO wallets (label, address, group_name, created_at, updated_at) VALUES (?, ?, ?, ?, ?)")
+            .bind(address)
+            .bind(address)
+            .bind(group_name)
+            .bind(&now)
+            .bind(&now)
+            .execute(db)
+            .await
+            .unwrap()
+            .last_insert_rowid()
+    }
+
+    async fn airdrop(db: &SqlitePool, name: &str, wallet_address: Option<&str>) -> i64 {
+        let now = Utc::now().to_rfc3339();
+        sqlx::query("INSERT INTO airdrops (name, url, wallet_address, created_at, updated_at) VALUES (?, 'https://example.xyz', ?, ?, ?)")
+            .bind(name)
+            .bind(wallet_address)
+            .bind(&now)
+            .bind(&now)
+            .execute(db)
+            .await
+            .unwrap()
+            .last_insert_rowid()
+    }
+
+    async fn identity(db: &SqlitePool, handle: &str) -> i64 {
+        let now = Utc::now().to_rfc3339();
+        sqlx::query("INSERT INTO social_identities (platform, handle, created_at, updated_at) VALUES ('x', ?, ?, ?)")
+            .bind(handle)
+            .bind(&now)
+            .bind(&now)
+            .execute(db)
+            .await
+            .unwrap()
+            .last_insert_rowid()
+    }
+
+    async fn link(db: &SqlitePool, airdrop_id: i64, identity_id: i64, wallet_id: Option<i64>) {
+        sqlx::query("INSERT INTO airdrop_identities (airdrop_id, identity_id, wallet_id, created_at) VALUES (?, ?, ?, ?)")
+            .bind(airdrop_id)
+            .bind(identity_id)
+            .bind(wallet_id)
+            .bind(Utc::now().to_rfc3339())
+            .execute(db)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn warns_only_about_identities_shared_across_separate_wallets() {
+        let db = memory_db().await;
+        let main = wallet(&db, "0xAAA", Some("Main")).await;
+        let main_alt = wallet(&db, "0xBBB", Some("main")).await;
+        let loner = wallet(&db, "0xCCC", None).await;
+        let first = airdrop(&db, "First", None).await;
+        let second = airdrop(&db, "Second", None).await;
+        let by_address = airdrop(&db, "Third", Some("0xccc")).await;
+
+        let grouped = identity(&db, "grouped").await;
+        link(&db, first, grouped, Some(main)).await;
+        link(&db, second, grouped, Some(main_alt)).await;
+
+        let shared = identity(&db, "shared").await;
+        link(&db, first, shared, Some(main)).await;
+        link(&db, second, shared, Some(loner)).await;
+
+        // The airdrop's wallet address stands in for a link without a wallet
+        let implicit = identity(&db, "implicit").await;
+        link(&db, first, implicit, Some(main_alt)).await;
+        link(&db, by_address, implicit, None).await;
+
+        let single = identity(&db, "single").await;
+        link(&db, first, single, Some(loner)).await;
+        link(&db, by_address, single, None).await;
+
+        let warnings = reuse_warnings(&db).await.unwrap();
+        let flagged: Vec<i64> = warnings.iter().map(|w| w.identity_id).collect();
+        assert_eq!(flagged, vec![shared, implicit]);
+
+        let implicit_warning = &warnings[1];
+        let wallet_ids: Vec<i64> = implicit_warning.wallets.iter().map(|w| w.id).collect();
+        assert_eq!(wallet_ids, vec![main_alt, loner]);
+        assert_eq!(implicit_warning.airdrop_ids, vec![first, by_address]);
+    }
+}
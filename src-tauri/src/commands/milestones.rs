@@ -0,0 +1,459 @@
+use tauri::State;
+use sqlx::SqlitePool;
+use chrono::{NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::commands::projects::parse_task_date;
+use crate::commands::settings::{read_setting, write_setting};
+use crate::models::{Project, ProjectMilestone};
+
+pub(crate) const PLANNING_SETTINGS_KEY: &str = "planning_settings";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PlanningSettings {
+    /// Minutes of project work available per day, used to judge milestone risk
+    pub daily_capacity_minutes: i64,
+}
+
+impl Default for PlanningSettings {
+    fn default() -> Self {
+        Self {
+            daily_capacity_minutes: 240,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateMilestoneRequest {
+    pub project_id: i64,
+    pub name: String,
+    pub description: Option<String>,
+    pub target_date: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateMilestoneRequest {
+    pub id: i64,
+    pub name: Option<String>,
+    pub description: Option<String>,
+    /// An empty string clears the date
+    pub target_date: Option<String>,
+}
+
+/// Task counts and estimates over leaf tasks (tasks without subtasks), so a
+/// parent and its steps are not counted twice. A subtask belongs to the
+/// milestone of its nearest assigned ancestor unless it has its own.
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct ProgressTotals {
+    pub total_tasks: i64,
+    pub done_tasks: i64,
+    /// 0-100 by task count
+    pub task_progress: f64,
+    pub estimated_minutes: i64,
+    pub remaining_minutes: i64,
+    /// 0-100 by estimated minutes; `None` when nothing is estimated
+    pub estimate_progress: Option<f64>,
+    /// Open tasks without an estimate, not reflected in `remaining_minutes`
+    pub unestimated_tasks: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MilestoneProgress {
+    #[serde(flatten)]
+    pub milestone: ProjectMilestone,
+    #[serde(flatten)]
+    pub totals: ProgressTotals,
+    /// Days from today through the target date, today included
+    pub days_left: Option<i64>,
+    pub available_minutes: Option<i64>,
+    /// The target date passed with work left, or the remaining estimate
+    /// exceeds the time available before it
+    pub at_risk: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ProjectProgress {
+    pub project_id: i64,
+    pub project_name: String,
+    #[serde(flatten)]
+    pub totals: ProgressTotals,
+    pub milestones: Vec<MilestoneProgress>,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct LeafTask {
+    project_id: i64,
+    milestone_id: Option<i64>,
+    done: bool,
+    estimated_minutes: Option<i64>,
+}
+
+impl ProgressTotals {
+    fn add(&mut self, task: &LeafTask) {
+        let estimate = task.estimated_minutes.unwrap_or(0);
+        self.total_tasks += 1;
+        self.estimated_minutes += estimate;
+        if task.done {
+            self.done_tasks += 1;
+        } else if task.estimated_minutes.is_some() {
+            self.remaining_minutes += estimate;
+        } else {
+            self.unestimated_tasks += 1;
+        }
+    }
+
+    fn finish(mut self) -> Self {
+        if self.total_tasks > 0 {
+            self.task_progress = self.done_tasks as f64 * 100.0 / self.total_tasks as f64;
+        }
+        if self.estimated_minutes > 0 {
+            let done_minutes = self.estimated_minutes - self.remaining_minutes;
+            self.estimate_progress = Some(done_minutes as f64 * 100.0 / self.estimated_minutes as f64);
+        }
+        self
+    }
+}
+
+#[tauri::command]
+pub async fn get_planning_settings(state: State<'_, crate::AppState>) -> Result<PlanningSettings, String> {
+    read_setting(&state.db, PLANNING_SETTINGS_KEY).await
+}
+
+#[tauri::command]
+pub async fn update_planning_settings(
+    state: State<'_, crate::AppState>,
+    settings: PlanningSettings,
+) -> Result<(), String> {
+    if settings.daily_capacity_minutes < 1 {
+        return Err("Daily capacity must be at least one minute".to_string());
+    }
+    write_setting(&state.db, PLANNING_SETTINGS_KEY, &settings).await
+}
+
+#[tauri::command]
+pub async fn list_project_milestones(
+    state: State<'_, crate::AppState>,
+    project_id: i64,
+) -> Result<Vec<MilestoneProgress>, String> {
+    let mut progress = build_progress(&state.db, Some(project_id)).await?;
+    Ok(progress.pop().map(|p| p.milestones).unwrap_or_default())
+}
+
+#[tauri::command]
+pub async fn create_project_milestone(
+    state: State<'_, crate::AppState>,
+    req: CreateMilestoneRequest,
+) -> Result<i64, String> {
+    let name = req.name.trim();
+    if name.is_empty() {
+        return Err("Milestone name is required".to_string());
+    }
+    let target_date = parse_task_date(req.target_date.as_deref(), "target")?;
+
+    let now = Utc::now();
+    let id = sqlx::query(
+        r#"
+        INSERT INTO project_milestones (project_id, name, description, target_date, created_at, updated_at)
+        VALUES (?, ?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(req.project_id)
+    .bind(name)
+    .bind(&req.description)
+    .bind(&target_date)
+    .bind(now.to_rfc3339())
+    .bind(now.to_rfc3339())
+    .execute(&state.db)
+    .await
+    .map_err(|e| format!("Failed to create milestone: {}", e))?
+    .last_insert_rowid();
+
+    Ok(id)
+}
+
+#[tauri::command]
+pub async fn update_project_milestone(
+    state: State<'_, crate::AppState>,
+    req: UpdateMilestoneRequest,
+) -> Result<(), String> {
+    if req.name.as_deref().is_some_and(|n| n.trim().is_empty()) {
+        return Err("Milestone name is required".to_string());
+    }
+    let target_date = req
+        .target_date
+        .as_deref()
+        .map(|d| parse_task_date(Some(d), "target"))
+        .transpose()?;
+
+    let mut updates = Vec::new();
+    if req.name.is_some() {
+        updates.push("name = ?");
+    }
+    if req.description.is_some() {
+        updates.push("description = ?");
+    }
+    if target_date.is_some() {
+        updates.push("target_date = ?");
+    }
+    updates.push("updated_at = ?");
+
+    let query = format!(
+        "UPDATE project_milestones SET {} WHERE id = ?",
+        updates.join(", ")
+    );
+
+    let mut q = sqlx::query(&query);
+    if let Some(name) = &req.name {
+        q = q.bind(name.trim());
+    }
+    if let Some(description) = &req.description {
+        q = q.bind(description);
+    }
+    if let Some(target_date) = &target_date {
+        q = q.bind(target_date.clone());
+    }
+    q = q.bind(Utc::now().to_rfc3339());
+    q = q.bind(req.id);
+
+    q.execute(&state.db)
+        .await
+        .map_err(|e| format!("Failed to update milestone: {}", e))?;
+
+    Ok(())
+}
+
+/// Deletes a milestone; its tasks stay in the project unassigned.
+#[tauri::command]
+pub async fn delete_project_milestone(state: State<'_, crate::AppState>, id: i64) -> Result<(), String> {
+    sqlx::query("DELETE FROM project_milestones WHERE id = ?")
+        .bind(id)
+        .execute(&state.db)
+        .await
+        .map_err(|e| format!("Failed to delete milestone: {}", e))?;
+
+    Ok(())
+}
+
+/// Assigns a task to a milestone of its project, or unassigns it when
+/// `milestone_id` is `None`.
+#[tauri::command]
+pub async fn set_task_milestone(
+    state: State<'_, crate::AppState>,
+    task_id: i64,
+    milestone_id: Option<i64>,
+) -> Result<(), String> {
+    if let Some(milestone_id) = milestone_id {
+        let same_project = sqlx::query_scalar::<_, i64>(
+            r#"
+            SELECT COUNT(*) FROM project_tasks t
+            JOIN project_milestones m ON m.project_id = t.project_id
+            WHERE t.id = ? AND m.id = ?
+            "#,
+        )
+        .bind(task_id)
+        .bind(milestone_id)
+        .fetch_one(&state.db)
+        .await
+        .map_err(|e| format!("Failed to check milestone: {}", e))?;
+        if same_project == 0 {
+            return Err("Milestone not found in the task's project".to_string());
+        }
+    }
+
+    sqlx::query("UPDATE project_tasks SET milestone_id = ?, updated_at = ? WHERE id = ?")
+        .bind(milestone_id)
+        .bind(Utc::now().to_rfc3339())
+        .bind(task_id)
+        .execute(&state.db)
+        .await
+        .map_err(|e| format!("Failed to assign milestone: {}", e))?;
+
+    Ok(())
+}
+
+/// Progress of one project, or of every project that is not archived.
+#[tauri::command]
+pub async fn list_project_progress(
+    state: State<'_, crate::AppState>,
+    project_id: Option<i64>,
+) -> Result<Vec<ProjectProgress>, String> {
+    build_progress(&state.db, project_id).await
+}
+
+async fn build_progress(db: &SqlitePool, project_id: Option<i64>) -> Result<Vec<ProjectProgress>, String> {
+    let settings: PlanningSettings = read_setting(db, PLANNING_SETTINGS_KEY).await?;
+    let today = Utc::now().date_naive();
+
+    let projects = sqlx::query_as::<_, Project>(
+        r#"
+        SELECT * FROM projects
+        WHERE (? IS NULL AND status != 'archived') OR id = ?
+        ORDER BY created_at DESC
+        "#,
+    )
+    .bind(project_id)
+    .bind(project_id)
+    .fetch_all(db)
+    .await
+    .map_err(|e| format!("Failed to fetch projects: {}", e))?;
+
+    let milestones = sqlx::query_as::<_, ProjectMilestone>(
+        r#"
+        SELECT * FROM project_milestones
+        WHERE ? IS NULL OR project_id = ?
+        ORDER BY target_date IS NULL, target_date ASC, id ASC
+        "#,
+    )
+    .bind(project_id)
+    .bind(project_id)
+    .fetch_all(db)
+    .await
+    .map_err(|e| format!("Failed to fetch milestones: {}", e))?;
+
+    // Subtasks inherit the milestone of the nearest assigned ancestor
+    let tasks = sqlx::query_as::<_, LeafTask>(
+        r#"
+        WITH RECURSIVE effective(id, milestone_id) AS (
+            SELECT id, milestone_id FROM project_tasks WHERE parent_task_id IS NULL
+            UNION ALL
+            SELECT t.id, COALESCE(t.milestone_id, e.milestone_id)
+            FROM project_tasks t JOIN effective e ON t.parent_task_id = e.id
+        )
+        SELECT t.project_id, e.milestone_id, t.done, t.estimated_minutes
+        FROM project_tasks t
+        JOIN effective e ON e.id = t.id
+        WHERE (? IS NULL OR t.project_id = ?)
+          AND NOT EXISTS (SELECT 1 FROM project_tasks c WHERE c.parent_task_id = t.id)
+        "#,
+    )
+    .bind(project_id)
+    .bind(project_id)
+    .fetch_all(db)
+    .await
+    .map_err(|e| format!("Failed to fetch project tasks: {}", e))?;
+
+    let progress = projects
+        .into_iter()
+        .map(|project| {
+            let mut totals = ProgressTotals::default();
+            for task in tasks.iter().filter(|t| t.project_id == project.id) {
+                totals.add(task);
+            }
+
+            let milestones = milestones
+                .iter()
+                .filter(|m| m.project_id == project.id)
+                .map(|milestone| {
+                    let mut totals = ProgressTotals::default();
+                    for task in tasks.iter().filter(|t| t.milestone_id == Some(milestone.id)) {
+                        totals.add(task);
+                    }
+                    milestone_progress(milestone.clone(), totals.finish(), today, &settings)
+                })
+                .collect();
+
+            ProjectProgress {
+                project_id: project.id,
+                project_name: project.name,
+                totals: totals.finish(),
+                milestones,
+            }
+        })
+        .collect();
+
+    Ok(progress)
+}
+
+fn milestone_progress(
+    milestone: ProjectMilestone,
+    totals: ProgressTotals,
+    today: NaiveDate,
+    settings: &PlanningSettings,
+) -> MilestoneProgress {
+    let target = milestone
+        .target_date
+        .as_deref()
+        .and_then(|d| NaiveDate::parse_from_str(d, "%Y-%m-%d").ok());
+    let days_left = target.map(|t| ((t - today).num_days() + 1).max(0));
+    let available_minutes = days_left.map(|d| d * settings.daily_capacity_minutes);
+    let work_left = totals.done_tasks < totals.total_tasks;
+    let at_risk = match available_minutes {
+        Some(available) => work_left && (available == 0 || totals.remaining_minutes > available),
+        None => false,
+    };
+
+    MilestoneProgress {
+        milestone,
+        totals,
+        days_left,
+        available_minutes,
+        at_risk,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn milestone(target_date: Option<&str>) -> ProjectMilestone {
+        let now = Utc::now();
+        ProjectMilestone {
+            id: 1,
+            project_id: 1,
+            name: "Beta".to_string(),
+            description: None,
+            target_date: target_date.map(str::to_string),
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    fn totals(total_tasks: i64, done_tasks: i64, remaining_minutes: i64) -> ProgressTotals {
+        ProgressTotals { total_tasks, done_tasks, remaining_minutes, ..Default::default() }
+    }
+
+    fn date(raw: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(raw, "%Y-%m-%d").unwrap()
+    }
+
+    fn settings(daily_capacity_minutes: i64) -> PlanningSettings {
+        PlanningSettings { daily_capacity_minutes }
+    }
+
+    #[test]
+    fn risk_compares_remaining_work_with_capacity_until_the_target() {
+        let today = date("2024-05-01");
+        // Today and tomorrow count: 2 days of 120 minutes
+        let target = milestone(Some("2024-05-02"));
+
+        let fits = milestone_progress(target.clone(), totals(3, 1, 240), today, &settings(120));
+        assert_eq!((fits.days_left, fits.available_minutes), (Some(2), Some(240)));
+        assert!(!fits.at_risk);
+
+        let too_much = milestone_progress(target, totals(3, 1, 241), today, &settings(120));
+        assert!(too_much.at_risk);
+    }
+
+    #[test]
+    fn overdue_milestones_with_open_work_are_at_risk() {
+        let today = date("2024-05-10");
+        let overdue = milestone(Some("2024-05-01"));
+
+        // Open but unestimated work still counts once the target has passed
+        let open = milestone_progress(overdue.clone(), totals(2, 1, 0), today, &settings(240));
+        assert_eq!((open.days_left, open.available_minutes), (Some(0), Some(0)));
+        assert!(open.at_risk);
+
+        let finished = milestone_progress(overdue, totals(2, 2, 0), today, &settings(240));
+        assert!(!finished.at_risk);
+    }
+
+    #[test]
+    fn milestones_without_a_target_are_never_at_risk() {
+        let progress = milestone_progress(milestone(None), totals(5, 0, 10_000), date("2024-05-01"), &settings(60));
+
+        assert_eq!((progress.days_left, progress.available_minutes), (None, None));
+        assert!(!progress.at_risk);
+    }
+}
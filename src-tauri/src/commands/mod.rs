@@ -1,5 +1,33 @@
 pub mod airdrops;
+pub mod airdrop_io;
+pub mod template_packs;
+pub mod url_health;
+pub mod feeds;
+pub mod candidates;
+pub mod wallets;
+pub mod browser_profiles;
+pub mod identities;
+pub mod sybil;
+pub mod transactions;
+pub mod proofs;
+pub mod chains;
+pub mod gas;
+pub mod balances;
+pub mod settings;
+pub mod expenses;
+pub mod scoring;
+pub mod claims;
+pub mod tax;
+pub mod prices;
+pub mod valuation;
 pub mod projects;
+pub mod task_dependencies;
+pub mod project_columns;
+pub mod time_entries;
+pub mod focus;
+pub mod milestones;
+pub mod recurrence;
+pub mod project_templates;
 pub mod ideas;
 pub mod house;
 pub mod calendar;
@@ -0,0 +1,574 @@
+use log::{info, warn};
+use tauri::State;
+use sqlx::SqlitePool;
+use chrono::{NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use std::time::Duration;
+
+use crate::commands::settings::{read_setting, write_setting};
+use crate::models::Price;
+
+pub(crate) const CURRENCY_SETTINGS_KEY: &str = "currency_settings";
+const PRICE_TIMEOUT: Duration = Duration::from_secs(15);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CurrencySettings {
+    /// Currency every report is converted into
+    pub base_currency: String,
+    /// URL template with `{asset}`, `{quote}` and `{date}` placeholders
+    pub price_endpoint: Option<String>,
+    /// Dotted path to the price in the endpoint's JSON, e.g. `data.price`;
+    /// when unset the body must be a bare number or have a `price` field
+    pub price_json_path: Option<String>,
+}
+
+impl Default for CurrencySettings {
+    fn default() -> Self {
+        Self {
+            base_currency: "USD".to_string(),
+            price_endpoint: None,
+            price_json_path: None,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PriceFilter {
+    pub asset: Option<String>,
+    pub quote: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpsertPriceRequest {
+    pub asset: String,
+    pub quote: String,
+    pub date: String,
+    pub price: f64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct FetchPricesRequest {
+    pub assets: Vec<String>,
+    /// Defaults to the base currency
+    pub quote: Option<String>,
+    /// `YYYY-MM-DD`; defaults to today
+    pub date: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PriceImportReport {
+    pub imported: usize,
+    pub errors: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PriceFetchResult {
+    pub asset: String,
+    pub price: Option<f64>,
+    pub error: Option<String>,
+}
+
+/// Every stored price, for converting between any two assets on a date.
+pub(crate) struct PriceBook {
+    /// (asset, quote) -> prices sorted by date
+    rates: HashMap<(String, String), Vec<(NaiveDate, f64)>>,
+}
+
+impl PriceBook {
+    pub(crate) async fn load(db: &SqlitePool) -> Result<Self, String> {
+        let prices = sqlx::query_as::<_, Price>("SELECT * FROM prices ORDER BY date ASC")
+            .fetch_all(db)
+            .await
+            .map_err(|e| format!("Failed to fetch prices: {}", e))?;
+
+        let mut rates: HashMap<(String, String), Vec<(NaiveDate, f64)>> = HashMap::new();
+        for price in prices {
+            let Ok(date) = NaiveDate::parse_from_str(&price.date, "%Y-%m-%d") else {
+                continue;
+            };
+            if price.price > 0.0 {
+                rates
+                    .entry((price.asset, price.quote))
+                    .or_default()
+                    .push((date, price.price));
+            }
+        }
+        Ok(Self { rates })
+    }
+
+    /// Converts `amount` of `from` into `to` using the latest rates on or
+    /// before `date`, going through one intermediate currency if needed.
+    pub(crate) fn convert(&self, amount: f64, from: &str, to: &str, date: NaiveDate) -> Option<f64> {
+        self.rate(&from.to_uppercase(), &to.to_uppercase(), date)
+            .map(|rate| amount * rate)
+    }
+
+    fn rate(&self, from: &str, to: &str, date: NaiveDate) -> Option<f64> {
+        if from == to {
+            return Some(1.0);
+        }
+        if let Some(rate) = self.direct(from, to, date) {
+            return Some(rate);
+        }
+        let bridges: BTreeSet<&str> = self
+            .rates
+            .keys()
+            .flat_map(|(asset, quote)| [asset.as_str(), quote.as_str()])
+            .filter(|c| *c != from && *c != to)
+            .collect();
+        bridges.into_iter().find_map(|bridge| {
+            Some(self.direct(from, bridge, date)? * self.direct(bridge, to, date)?)
+        })
+    }
+
+    fn direct(&self, from: &str, to: &str, date: NaiveDate) -> Option<f64> {
+        let on_or_before = |series: &Vec<(NaiveDate, f64)>| {
+            series
+                .iter()
+                .rev()
+                .find(|(d, _)| *d <= date)
+                .map(|(_, price)| *price)
+        };
+        self.rates
+            .get(&(from.to_string(), to.to_string()))
+            .and_then(on_or_before)
+            .or_else(|| {
+                self.rates
+                    .get(&(to.to_string(), from.to_string()))
+                    .and_then(on_or_before)
+                    .map(|price| 1.0 / price)
+            })
+    }
+}
+
+#[tauri::command]
+pub async fn get_currency_settings(state: State<'_, crate::AppState>) -> Result<CurrencySettings, String> {
+    read_setting(&state.db, CURRENCY_SETTINGS_KEY).await
+}
+
+#[tauri::command]
+pub async fn update_currency_settings(
+    state: State<'_, crate::AppState>,
+    settings: CurrencySettings,
+) -> Result<(), String> {
+    let base_currency = normalize_code(&settings.base_currency);
+    if base_currency.is_empty() {
+        return Err("Base currency is required".to_string());
+    }
+    if let Some(endpoint) = settings.price_endpoint.as_deref().filter(|e| !e.trim().is_empty()) {
+        let url = url::Url::parse(&endpoint.replace(['{', '}'], ""))
+            .map_err(|e| format!("Invalid price endpoint: {}", e))?;
+        if !matches!(url.scheme(), "http" | "https") {
+            return Err("Price endpoints must be http or https".to_string());
+        }
+    }
+
+    write_setting(
+        &state.db,
+        CURRENCY_SETTINGS_KEY,
+        &CurrencySettings {
+            base_currency,
+            ..settings
+        },
+    )
+    .await
+}
+
+#[tauri::command]
+pub async fn list_prices(
+    state: State<'_, crate::AppState>,
+    filter: Option<PriceFilter>,
+) -> Result<Vec<Price>, String> {
+    let asset = filter.as_ref().and_then(|f| f.asset.as_deref()).map(normalize_code);
+    let quote = filter.as_ref().and_then(|f| f.quote.as_deref()).map(normalize_code);
+
+    let prices = sqlx::query_as::<_, Price>(
+        r#"
+        SELECT * FROM prices
+        WHERE (? IS NULL OR asset = ?) AND (? IS NULL OR quote = ?)
+        ORDER BY asset ASC, quote ASC, date DESC
+        "#,
+    )
+    .bind(&asset)
+    .bind(&asset)
+    .bind(&quote)
+    .bind(&quote)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|e| format!("Failed to fetch prices: {}", e))?;
+
+    Ok(prices)
+}
+
+#[tauri::command]
+pub async fn upsert_price(state: State<'_, crate::AppState>, req: UpsertPriceRequest) -> Result<(), String> {
+    let date = parse_price_date(&req.date)?;
+    store_price(&state.db, &req.asset, &req.quote, &date, req.price, "manual").await
+}
+
+#[tauri::command]
+pub async fn delete_price(
+    state: State<'_, crate::AppState>,
+    asset: String,
+    quote: String,
+    date: String,
+) -> Result<(), String> {
+    sqlx::query("DELETE FROM prices WHERE asset = ? AND quote = ? AND date = ?")
+        .bind(normalize_code(&asset))
+        .bind(normalize_code(&quote))
+        .bind(date.trim())
+        .execute(&state.db)
+        .await
+        .map_err(|e| format!("Failed to delete price: {}", e))?;
+
+    Ok(())
+}
+
+/// Imports `asset,quote,date,price` rows. A header row is optional; when
+/// present its columns may be in any order.
+#[tauri::command]
+pub async fn import_prices_csv(
+    state: State<'_, crate::AppState>,
+    content: String,
+) -> Result<PriceImportReport, String> {
+    let report = import_price_rows(&state.db, &content).await;
+
+    info!("phase=price_import_done imported={} errors={}", report.imported, report.errors.len());
+    Ok(report)
+}
+
+pub(crate) async fn import_price_rows(db: &SqlitePool, content: &str) -> PriceImportReport {
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_reader(content.as_bytes());
+
+    let mut columns = [0usize, 1, 2, 3];
+    let mut imported = 0;
+    let mut errors = Vec::new();
+    for (index, record) in reader.records().enumerate() {
+        let line = index + 1;
+        let record = match record {
+            Ok(record) => record,
+            Err(e) => {
+                errors.push(format!("Line {}: {}", line, e));
+                continue;
+            }
+        };
+        if index == 0 {
+            let header: Vec<String> = record.iter().map(|h| h.to_lowercase()).collect();
+            let find = |names: &[&str]| header.iter().position(|h| names.contains(&h.as_str()));
+            if let (Some(asset), Some(quote), Some(date), Some(price)) = (
+                find(&["asset", "symbol", "token", "base"]),
+                find(&["quote", "currency", "quote_currency"]),
+                find(&["date", "day"]),
+                find(&["price", "rate", "close"]),
+            ) {
+                columns = [asset, quote, date, price];
+                continue;
+            }
+        }
+
+        let field = |i: usize| record.get(columns[i]).unwrap_or_default();
+        let parsed = parse_price_date(field(2)).and_then(|date| {
+            let price: f64 = field(3)
+                .parse()
+                .map_err(|_| format!("Invalid price: {}", field(3)))?;
+            Ok((date, price))
+        });
+        let result = match parsed {
+            Ok((date, price)) => store_price(db, field(0), field(1), &date, price, "csv").await,
+            Err(e) => Err(e),
+        };
+        match result {
+            Ok(()) => imported += 1,
+            Err(e) => errors.push(format!("Line {}: {}", line, e)),
+        }
+    }
+
+    PriceImportReport { imported, errors }
+}
+
+/// Fetches prices from the configured HTTP endpoint and stores them.
+#[tauri::command]
+pub async fn fetch_prices(
+    state: State<'_, crate::AppState>,
+    req: FetchPricesRequest,
+) -> Result<Vec<PriceFetchResult>, String> {
+    fetch_and_store_prices(&state.db, &req).await
+}
+
+pub(crate) async fn fetch_and_store_prices(
+    db: &SqlitePool,
+    req: &FetchPricesRequest,
+) -> Result<Vec<PriceFetchResult>, String> {
+    let settings: CurrencySettings = read_setting(db, CURRENCY_SETTINGS_KEY).await?;
+    let endpoint = settings
+        .price_endpoint
+        .as_deref()
+        .filter(|e| !e.trim().is_empty())
+        .ok_or("No price endpoint configured")?;
+    let quote = normalize_code(req.quote.as_deref().unwrap_or(&settings.base_currency));
+    let date = match req.date.as_deref() {
+        Some(date) => parse_price_date(date)?,
+        None => Utc::now().format("%Y-%m-%d").to_string(),
+    };
+
+    let client = reqwest::Client::builder()
+        .timeout(PRICE_TIMEOUT)
+        .build()
+        .map_err(|e| format!("Failed to build HTTP client: {}", e))?;
+
+    let mut results = Vec::with_capacity(req.assets.len());
+    for asset in &req.assets {
+        let asset = normalize_code(asset);
+        let url = endpoint
+            .replace("{asset}", &urlencode(&asset))
+            .replace("{quote}", &urlencode(&quote))
+            .replace("{date}", &date);
+
+        let price = async {
+            let body: serde_json::Value = client
+                .get(&url)
+                .send()
+                .await
+                .map_err(|e| format!("Request failed: {}", e))?
+                .error_for_status()
+                .map_err(|e| format!("Price endpoint error: {}", e))?
+                .json()
+                .await
+                .map_err(|e| format!("Invalid price response: {}", e))?;
+            extract_price(&body, settings.price_json_path.as_deref())
+        }
+        .await;
+
+        let result = match price {
+            Ok(price) => match store_price(db, &asset, &quote, &date, price, "http").await {
+                Ok(()) => PriceFetchResult {
+                    asset,
+                    price: Some(price),
+                    error: None,
+                },
+                Err(e) => PriceFetchResult {
+                    asset,
+                    price: None,
+                    error: Some(e),
+                },
+            },
+            Err(e) => {
+                warn!("phase=price_fetch_failed asset={} error={}", asset, e);
+                PriceFetchResult {
+                    asset,
+                    price: None,
+                    error: Some(e),
+                }
+            }
+        };
+        results.push(result);
+    }
+
+    Ok(results)
+}
+
+#[tauri::command]
+pub async fn convert_amount(
+    state: State<'_, crate::AppState>,
+    amount: f64,
+    from: String,
+    to: Option<String>,
+    date: Option<String>,
+) -> Result<f64, String> {
+    let to = match to {
+        Some(to) => to,
+        None => base_currency(&state.db).await?,
+    };
+    let date = match date.as_deref() {
+        Some(date) => NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d")
+            .map_err(|_| format!("Invalid date: {}", date))?,
+        None => Utc::now().date_naive(),
+    };
+
+    PriceBook::load(&state.db)
+        .await?
+        .convert(amount, &from, &to, date)
+        .ok_or_else(|| format!("No price to convert {} to {} on {}", from, to, date))
+}
+
+pub(crate) async fn base_currency(db: &SqlitePool) -> Result<String, String> {
+    let settings: CurrencySettings = read_setting(db, CURRENCY_SETTINGS_KEY).await?;
+    Ok(normalize_code(&settings.base_currency))
+}
+
+async fn store_price(
+    db: &SqlitePool,
+    asset: &str,
+    quote: &str,
+    date: &str,
+    price: f64,
+    source: &str,
+) -> Result<(), String> {
+    let (asset, quote) = (normalize_code(asset), normalize_code(quote));
+    if asset.is_empty() || quote.is_empty() {
+        return Err("Asset and quote are required".to_string());
+    }
+    if !price.is_finite() || price <= 0.0 {
+        return Err(format!("Invalid price: {}", price));
+    }
+
+    sqlx::query(
+        r#"
+        INSERT INTO prices (asset, quote, date, price, source, updated_at) VALUES (?, ?, ?, ?, ?, ?)
+        ON CONFLICT(asset, quote, date) DO UPDATE SET
+            price = excluded.price, source = excluded.source, updated_at = excluded.updated_at
+        "#,
+    )
+    .bind(&asset)
+    .bind(&quote)
+    .bind(date)
+    .bind(price)
+    .bind(source)
+    .bind(Utc::now().to_rfc3339())
+    .execute(db)
+    .await
+    .map_err(|e| format!("Failed to store price: {}", e))?;
+
+    Ok(())
+}
+
+fn extract_price(body: &serde_json::Value, path: Option<&str>) -> Result<f64, String> {
+    let value = match path.map(str::trim).filter(|p| !p.is_empty()) {
+        Some(path) => path
+            .split('.')
+            .try_fold(body, |value, key| match key.parse::<usize>() {
+                Ok(index) if value.is_array() => value.get(index),
+                _ => value.get(key),
+            })
+            .ok_or_else(|| format!("No value at {} in price response", path))?,
+        None => body.get("price").unwrap_or(body),
+    };
+
+    value
+        .as_f64()
+        .or_else(|| value.as_str().and_then(|s| s.trim().parse().ok()))
+        .filter(|price| price.is_finite() && *price > 0.0)
+        .ok_or_else(|| format!("Price response has no usable number: {}", value))
+}
+
+fn parse_price_date(raw: &str) -> Result<String, String> {
+    NaiveDate::parse_from_str(raw.trim(), "%Y-%m-%d")
+        .map(|d| d.to_string())
+        .map_err(|_| format!("Invalid date: {}", raw))
+}
+
+fn normalize_code(code: &str) -> String {
+    code.trim().to_uppercase()
+}
+
+fn urlencode(value: &str) -> String {
+    url::form_urlencoded::byte_serialize(value.as_bytes()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{http_stand_in, memory_db, StandInReply};
+    use serde_json::json;
+
+    fn day(raw: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(raw, "%Y-%m-%d").unwrap()
+    }
+
+    async fn book(rows: &[(&str, &str, &str, f64)]) -> PriceBook {
+        let db = memory_db().await;
+        for (asset, quote, date, price) in rows {
+            store_price(&db, asset, quote, date, *price, "manual").await.unwrap();
+        }
+        PriceBook::load(&db).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn converts_with_latest_price_on_or_before_date() {
+        let prices = book(&[("ETH", "USD", "2024-01-01", 2000.0), ("ETH", "USD", "2024-02-01", 3000.0)]).await;
+
+        assert_eq!(prices.convert(2.0, "eth", "usd", day("2024-01-15")), Some(4000.0));
+        assert_eq!(prices.convert(2.0, "ETH", "USD", day("2024-03-01")), Some(6000.0));
+        assert_eq!(prices.convert(2.0, "ETH", "USD", day("2023-12-31")), None);
+        assert_eq!(prices.convert(6000.0, "USD", "ETH", day("2024-02-01")), Some(2.0));
+        assert_eq!(prices.convert(5.0, "EUR", "eur", day("2020-01-01")), Some(5.0));
+    }
+
+    #[tokio::test]
+    async fn converts_through_one_bridge_currency() {
+        let prices = book(&[("ETH", "USD", "2024-01-01", 2000.0), ("EUR", "USD", "2024-01-01", 1.25)]).await;
+
+        assert_eq!(prices.convert(1.0, "ETH", "EUR", day("2024-01-02")), Some(1600.0));
+        assert_eq!(prices.convert(1.0, "ETH", "GBP", day("2024-01-02")), None);
+    }
+
+    #[test]
+    fn extracts_price_from_common_shapes() {
+        assert_eq!(extract_price(&json!(1.5), None), Ok(1.5));
+        assert_eq!(extract_price(&json!({ "price": "2.25" }), None), Ok(2.25));
+        assert_eq!(
+            extract_price(&json!({ "data": { "rates": [{ "close": 3.0 }] } }), Some("data.rates.0.close")),
+            Ok(3.0)
+        );
+        assert!(extract_price(&json!({ "data": {} }), Some("data.price")).is_err());
+        assert!(extract_price(&json!({ "price": 0 }), None).is_err());
+        assert!(extract_price(&json!({ "price": "n/a" }), None).is_err());
+    }
+
+    #[tokio::test]
+    async fn csv_import_detects_headers_in_any_order() {
+        let db = memory_db().await;
+        let report = import_price_rows(&db, "Date,Close,Symbol,Currency\n2024-01-01,2000,eth,usd\n2024-01-02,oops,eth,usd\n").await;
+        assert_eq!(report.imported, 1);
+        assert_eq!(report.errors, vec!["Line 3: Invalid price: oops".to_string()]);
+
+        let stored = sqlx::query_as::<_, (String, String, String, f64)>("SELECT asset, quote, date, price FROM prices")
+            .fetch_all(&db)
+            .await
+            .unwrap();
+        assert_eq!(stored, vec![("ETH".to_string(), "USD".to_string(), "2024-01-01".to_string(), 2000.0)]);
+    }
+
+    #[tokio::test]
+    async fn csv_import_without_header_uses_default_columns() {
+        let db = memory_db().await;
+        let report = import_price_rows(&db, "ARB,USD,2024-01-01,1.1\nARB,USD,01/02/2024,1.2\n").await;
+        assert_eq!(report.imported, 1);
+        assert_eq!(report.errors.len(), 1);
+        assert!(report.errors[0].starts_with("Line 2: Invalid date"));
+    }
+
+    #[tokio::test]
+    async fn fetches_prices_from_endpoint() {
+        let base = http_stand_in(|request| match request.path.as_str() {
+            "/price/ETH/EUR/2024-01-01" => StandInReply::ok(r#"{"data":{"price":"2100.5"}}"#),
+            _ => StandInReply::status(404),
+        })
+        .await;
+        let db = memory_db().await;
+        let settings = CurrencySettings {
+            base_currency: "EUR".to_string(),
+            price_endpoint: Some(format!("{}/price/{{asset}}/{{quote}}/{{date}}", base)),
+            price_json_path: Some("data.price".to_string()),
+        };
+        write_setting(&db, CURRENCY_SETTINGS_KEY, &settings).await.unwrap();
+
+        let req = FetchPricesRequest {
+            assets: vec!["eth".to_string(), "DOGE".to_string()],
+            quote: None,
+            date: Some("2024-01-01".to_string()),
+        };
+        let results = fetch_and_store_prices(&db, &req).await.unwrap();
+
+        assert_eq!(results[0].price, Some(2100.5));
+        assert!(results[1].error.as_deref().unwrap().contains("404"));
+        let prices = PriceBook::load(&db).await.unwrap();
+        assert_eq!(prices.convert(2.0, "ETH", "EUR", day("2024-01-05")), Some(4201.0));
+    }
+}
@@ -0,0 +1,836 @@
+use log::{info, warn};
+use tauri::{AppHandle, State};
+use sqlx::{Sqlite, SqlitePool, Transaction};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+
+use crate::commands::projects::{after_task_completed, BLOCKED_COLUMN};
+use crate::models::{ProjectColumn, ProjectTask};
+
+const DEFAULT_COLUMNS: [(&str, bool); 3] = [("Todo", false), ("Doing", false), ("Done", true)];
+
+#[derive(Debug, Deserialize)]
+pub struct CreateProjectColumnRequest {
+    pub project_id: i64,
+    pub name: String,
+    /// Appended after the last column when omitted
+    pub position: Option<i64>,
+    pub wip_limit: Option<i64>,
+    pub is_done: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateProjectColumnRequest {
+    pub id: i64,
+    pub name: Option<String>,
+    /// A zero or negative limit clears it
+    pub wip_limit: Option<i64>,
+    pub is_done: Option<bool>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BoardColumn {
+    #[serde(flatten)]
+    pub column: ProjectColumn,
+    pub tasks: Vec<ProjectTask>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ProjectBoard {
+    pub columns: Vec<BoardColumn>,
+    /// Tasks without a column, e.g. because theirs was at its WIP limit
+    pub unfiled: Vec<ProjectTask>,
+}
+
+/// Gives a project the default Todo / Doing / Done columns if it has none,
+/// and files tasks without a column by their `done` state.
+pub(crate) async fn ensure_project_columns(db: &SqlitePool, project_id: i64) -> Result<(), String> {
+    let mut tx = db
+        .begin()
+        .await
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+
+    create_default_columns(&mut tx, project_id).await?;
+
+    let unfiled = sqlx::query_scalar::<_, i64>(
+        "SELECT id FROM project_tasks WHERE project_id = ? AND column_id IS NULL ORDER BY \"order\" ASC, id ASC",
+    )
+    .bind(project_id)
+    .fetch_all(&mut *tx)
+    .await
+    .map_err(|e| format!("Failed to fetch project tasks: {}", e))?;
+    for task_id in unfiled {
+        file_task(&mut tx, task_id).await?;
+    }
+
+    tx.commit()
+        .await
+        .map_err(|e| format!("Failed to commit project columns: {}", e))?;
+
+    Ok(())
+}
+
+/// Creates the default columns for a project that has none yet.
+pub(crate) async fn create_default_columns(tx: &mut Transaction<'_, Sqlite>, project_id: i64) -> Result<(), String> {
+    let count = sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM project_columns WHERE project_id = ?")
+        .bind(project_id)
+        .fetch_one(&mut **tx)
+        .await
+        .map_err(|e| format!("Failed to count project columns: {}", e))?;
+    if count > 0 {
+        return Ok(());
+    }
+
+    let now = Utc::now().to_rfc3339();
+    for (position, (name, is_done)) in DEFAULT_COLUMNS.iter().enumerate() {
+        sqlx::query(
+            r#"
+            INSERT OR IGNORE INTO project_columns (project_id, name, position, is_done, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(project_id)
+        .bind(name)
+        .bind(position as i64)
+        .bind(if *is_done { 1 } else { 0 })
+        .bind(&now)
+        .bind(&now)
+        .execute(&mut **tx)
+        .await
+        .map_err(|e| format!("Failed to create default columns: {}", e))?;
+    }
+
+    Ok(())
+}
+
+/// Puts a task that has no column at the end of the first column whose done
+/// state matches. A WIP limit only blocks moving cards, so when that column is
+/// full, or no column matches, the task stays unfiled. Returns whether it was
+/// filed.
+pub(crate) async fn file_task(tx: &mut Transaction<'_, Sqlite>, task_id: i64) -> Result<bool, String> {
+    let (project_id, done) = sqlx::query_as::<_, (i64, bool)>("SELECT project_id, done FROM project_tasks WHERE id = ?")
+        .bind(task_id)
+        .fetch_one(&mut **tx)
+        .await
+        .map_err(|e| format!("Failed to fetch project task: {}", e))?;
+
+    let Some(column) = matching_column(tx, project_id, done).await? else {
+        return Ok(false);
+    };
+    if !has_room(tx, &column, 1).await? {
+        warn!("phase=task_file_skipped task={} column={} reason=wip_limit", task_id, column.id);
+        return Ok(false);
+    }
+
+    let mut ids = column_task_ids(tx, column.id, task_id).await?;
+    ids.push(task_id);
+    write_column_order(tx, column.id, &ids).await?;
+    Ok(true)
+}
+
+/// After `done` was toggled directly, moves the task to the end of the first
+/// column whose done state matches, unless its column already matches.
+/// Fails when that column is at its WIP limit.
+pub(crate) async fn sync_task_column(tx: &mut Transaction<'_, Sqlite>, task_id: i64, done: bool) -> Result<(), String> {
+    let (project_id, column_id) = sqlx::query_as::<_, (i64, Option<i64>)>(
+        "SELECT project_id, column_id FROM project_tasks WHERE id = ?",
+    )
+    .bind(task_id)
+    .fetch_one(&mut **tx)
+    .await
+    .map_err(|e| format!("Failed to fetch project task: {}", e))?;
+
+    if let Some(column_id) = column_id {
+        let column = fetch_column(tx, column_id).await?;
+        if column.is_done == done {
+            return Ok(());
+        }
+    }
+
+    let Some(target) = matching_column(tx, project_id, done).await? else {
+        // No column matches; the task keeps its column until one is added
+        return Ok(());
+    };
+    check_wip_limit(tx, &target, 1).await?;
+
+    let mut ids = column_task_ids(tx, target.id, task_id).await?;
+    ids.push(task_id);
+    write_column_order(tx, target.id, &ids).await?;
+    if let Some(column_id) = column_id {
+        let remaining = column_task_ids(tx, column_id, task_id).await?;
+        write_column_order(tx, column_id, &remaining).await?;
+    }
+
+    Ok(())
+}
+
+/// Fails when `incoming` more tasks would take the column past its WIP limit.
+pub(crate) async fn check_wip_limit(
+    tx: &mut Transaction<'_, Sqlite>,
+    column: &ProjectColumn,
+    incoming: usize,
+) -> Result<(), String> {
+    if has_room(tx, column, incoming).await? {
+        Ok(())
+    } else {
+        Err(format!(
+            "{} is at its WIP limit of {}",
+            column.name,
+            column.wip_limit.unwrap_or_default()
+        ))
+    }
+}
+
+/// Whether `incoming` more tasks fit in the column without passing its WIP limit.
+async fn has_room(
+    tx: &mut Transaction<'_, Sqlite>,
+    column: &ProjectColumn,
+    incoming: usize,
+) -> Result<bool, String> {
+    let Some(limit) = column.wip_limit else {
+        return Ok(true);
+    };
+    let count = sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM project_tasks WHERE column_id = ?")
+        .bind(column.id)
+        .fetch_one(&mut **tx)
+        .await
+        .map_err(|e| format!("Failed to count column tasks: {}", e))?;
+
+    Ok(count + incoming as i64 <= limit)
+}
+
+/// The first column of the project whose done state is `done`.
+async fn matching_column(
+    tx: &mut Transaction<'_, Sqlite>,
+    project_id: i64,
+    done: bool,
+) -> Result<Option<ProjectColumn>, String> {
+    sqlx::query_as::<_, ProjectColumn>(
+        "SELECT * FROM project_columns WHERE project_id = ? AND is_done = ? ORDER BY position ASC, id ASC LIMIT 1",
+    )
+    .bind(project_id)
+    .bind(if done { 1 } else { 0 })
+    .fetch_optional(&mut **tx)
+    .await
+    .map_err(|e| format!("Failed to fetch project columns: {}", e))
+}
+
+async fn fetch_column(tx: &mut Transaction<'_, Sqlite>, column_id: i64) -> Result<ProjectColumn, String> {
+    sqlx::query_as::<_, ProjectColumn>("SELECT * FROM project_columns WHERE id = ?")
+        .bind(column_id)
+        .fetch_optional(&mut **tx)
+        .await
+        .map_err(|e| format!("Failed to fetch project column: {}", e))?
+        .ok_or_else(|| format!("Project column {} not found", column_id))
+}
+
+/// Tasks of a column in board order, leaving out `except_task_id`.
+async fn column_task_ids(
+    tx: &mut Transaction<'_, Sqlite>,
+    column_id: i64,
+    except_task_id: i64,
+) -> Result<Vec<i64>, String> {
+    sqlx::query_scalar::<_, i64>(
+        "SELECT id FROM project_tasks WHERE column_id = ? AND id != ? ORDER BY column_order ASC, id ASC",
+    )
+    .bind(column_id)
+    .bind(except_task_id)
+    .fetch_all(&mut **tx)
+    .await
+    .map_err(|e| format!("Failed to fetch column tasks: {}", e))
+}
+
+/// Puts `task_ids` into the column in the given order.
+async fn write_column_order(
+    tx: &mut Transaction<'_, Sqlite>,
+    column_id: i64,
+    task_ids: &[i64],
+) -> Result<(), String> {
+    let now = Utc::now().to_rfc3339();
+    for (order, task_id) in task_ids.iter().enumerate() {
+        sqlx::query(
+            r#"
+            UPDATE project_tasks
+            SET column_id = ?, column_order = ?, updated_at = ?
+            WHERE id = ?
+            "#,
+        )
+        .bind(column_id)
+        .bind(order as i64)
+        .bind(&now)
+        .bind(task_id)
+        .execute(&mut **tx)
+        .await
+        .map_err(|e| format!("Failed to reorder column tasks: {}", e))?;
+    }
+
+    Ok(())
+}
+
+/// Brings `done` and `completed_at` of every task in the column in line
+/// with the column's done state. Returns the tasks this completed.
+async fn sync_column_done(tx: &mut Transaction<'_, Sqlite>, column_id: i64) -> Result<Vec<i64>, String> {
+    let completed = sqlx::query_scalar::<_, i64>(
+        r#"
+        SELECT t.id FROM project_tasks t
+        JOIN project_columns c ON c.id = t.column_id
+        WHERE t.column_id = ? AND c.is_done = 1 AND t.done = 0
+        "#,
+    )
+    .bind(column_id)
+    .fetch_all(&mut **tx)
+    .await
+    .map_err(|e| format!("Failed to fetch column tasks: {}", e))?;
+
+    sqlx::query(
+        r#"
+        UPDATE project_tasks
+        SET done = (SELECT is_done FROM project_columns WHERE id = ?),
+            completed_at = CASE
+                WHEN (SELECT is_done FROM project_columns WHERE id = ?) THEN COALESCE(completed_at, ?)
+                ELSE NULL
+            END
+        WHERE column_id = ?
+        "#,
+    )
+    .bind(column_id)
+    .bind(column_id)
+    .bind(Utc::now().to_rfc3339())
+    .bind(column_id)
+    .execute(&mut **tx)
+    .await
+    .map_err(|e| format!("Failed to update task done state: {}", e))?;
+
+    Ok(completed)
+}
+
+#[tauri::command]
+pub async fn list_project_columns(
+    state: State<'_, crate::AppState>,
+    project_id: i64,
+) -> Result<Vec<ProjectColumn>, String> {
+    ensure_project_columns(&state.db, project_id).await?;
+
+    sqlx::query_as::<_, ProjectColumn>(
+        "SELECT * FROM project_columns WHERE project_id = ? ORDER BY position ASC, id ASC",
+    )
+    .bind(project_id)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|e| format!("Failed to fetch project columns: {}", e))
+}
+
+/// Columns of a project with their tasks in board order, plus the tasks no
+/// column could take.
+#[tauri::command]
+pub async fn get_project_board(
+    state: State<'_, crate::AppState>,
+    project_id: i64,
+) -> Result<ProjectBoard, String> {
+    let columns = list_project_columns(state.clone(), project_id).await?;
+    let tasks = sqlx::query_as::<_, ProjectTask>(&format!(
+        "SELECT t.*, {} FROM project_tasks t WHERE t.project_id = ? ORDER BY t.column_order ASC, t.id ASC",
+        BLOCKED_COLUMN
+    ))
+    .bind(project_id)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|e| format!("Failed to fetch project tasks: {}", e))?;
+
+    let mut board = ProjectBoard {
+        columns: columns
+            .into_iter()
+            .map(|column| BoardColumn { column, tasks: Vec::new() })
+            .collect(),
+        unfiled: Vec::new(),
+    };
+    for task in tasks {
+        match board.columns.iter_mut().find(|c| Some(c.column.id) == task.column_id) {
+            Some(slot) => slot.tasks.push(task),
+            None => board.unfiled.push(task),
+        }
+    }
+
+    Ok(board)
+}
+
+#[tauri::command]
+pub async fn create_project_column(
+    state: State<'_, crate::AppState>,
+    req: CreateProjectColumnRequest,
+) -> Result<i64, String> {
+    let name = req.name.trim();
+    if name.is_empty() {
+        return Err("Column name is required".to_string());
+    }
+    ensure_project_columns(&state.db, req.project_id).await?;
+
+    let mut tx = state
+        .db
+        .begin()
+        .await
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+
+    let count = sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM project_columns WHERE project_id = ?")
+        .bind(req.project_id)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to count project columns: {}", e))?;
+    let position = req.position.unwrap_or(count).clamp(0, count);
+
+    sqlx::query("UPDATE project_columns SET position = position + 1 WHERE project_id = ? AND position >= ?")
+        .bind(req.project_id)
+        .bind(position)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to shift project columns: {}", e))?;
+
+    let now = Utc::now();
+    let id = sqlx::query(
+        r#"
+        INSERT INTO project_columns (project_id, name, position, wip_limit, is_done, created_at, updated_at)
+        VALUES (?, ?, ?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(req.project_id)
+    .bind(name)
+    .bind(position)
+    .bind(req.wip_limit.filter(|l| *l > 0))
+    .bind(if req.is_done.unwrap_or(false) { 1 } else { 0 })
+    .bind(now.to_rfc3339())
+    .bind(now.to_rfc3339())
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| format!("Failed to create project column: {}", e))?
+    .last_insert_rowid();
+
+    tx.commit()
+        .await
+        .map_err(|e| format!("Failed to commit project column: {}", e))?;
+
+    Ok(id)
+}
+
+#[tauri::command]
+pub async fn update_project_column(
+    app: AppHandle,
+    state: State<'_, crate::AppState>,
+    req: UpdateProjectColumnRequest,
+) -> Result<(), String> {
+    if req.name.as_deref().is_some_and(|n| n.trim().is_empty()) {
+        return Err("Column name is required".to_string());
+    }
+
+    let mut tx = state
+        .db
+        .begin()
+        .await
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+
+    let column = fetch_column(&mut tx, req.id).await?;
+    if let Some(limit) = req.wip_limit.filter(|l| *l > 0) {
+        let count = sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM project_tasks WHERE column_id = ?")
+            .bind(req.id)
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(|e| format!("Failed to count column tasks: {}", e))?;
+        if count > limit {
+            return Err(format!(
+                "{} already holds {} tasks, more than a limit of {}",
+                column.name, count, limit
+            ));
+        }
+    }
+
+    let mut updates = Vec::new();
+    if req.name.is_some() {
+        updates.push("name = ?");
+    }
+    if req.wip_limit.is_some() {
+        updates.push("wip_limit = ?");
+    }
+    if req.is_done.is_some() {
+        updates.push("is_done = ?");
+    }
+    updates.push("updated_at = ?");
+
+    let query = format!(
+        "UPDATE project_columns SET {} WHERE id = ?",
+        updates.join(", ")
+    );
+
+    let mut q = sqlx::query(&query);
+    if let Some(name) = &req.name {
+        q = q.bind(name.trim());
+    }
+    if let Some(limit) = req.wip_limit {
+        q = q.bind(Some(limit).filter(|l| *l > 0));
+    }
+    if let Some(is_done) = req.is_done {
+        q = q.bind(if is_done { 1 } else { 0 });
+    }
+    q = q.bind(Utc::now().to_rfc3339());
+    q = q.bind(req.id);
+
+    q.execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to update project column: {}", e))?;
+
+    let mut completed = Vec::new();
+    if req.is_done.is_some_and(|d| d != column.is_done) {
+        completed = sync_column_done(&mut tx, req.id).await?;
+    }
+
+    tx.commit()
+        .await
+        .map_err(|e| format!("Failed to commit project column: {}", e))?;
+
+    for task_id in completed {
+        after_task_completed(&app, &state.db, task_id).await;
+    }
+
+    Ok(())
+}
+
+/// Deletes a column. Its tasks move to the end of `move_to_column_id`,
+/// which is required when the column is not empty.
+#[tauri::command]
+pub async fn delete_project_column(
+    app: AppHandle,
+    state: State<'_, crate::AppState>,
+    id: i64,
+    move_to_column_id: Option<i64>,
+) -> Result<(), String> {
+    let mut tx = state
+        .db
+        .begin()
+        .await
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+
+    let column = fetch_column(&mut tx, id).await?;
+    let siblings = sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM project_columns WHERE project_id = ?")
+        .bind(column.project_id)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to count project columns: {}", e))?;
+    if siblings <= 1 {
+        return Err("A project needs at least one column".to_string());
+    }
+
+    let moving = column_task_ids(&mut tx, id, 0).await?;
+    let mut completed = Vec::new();
+    if !moving.is_empty() {
+        let target_id = move_to_column_id
+            .filter(|t| *t != id)
+            .ok_or_else(|| format!("{} still has tasks; choose a column to move them to", column.name))?;
+        let target = fetch_column(&mut tx, target_id).await?;
+        if target.project_id != column.project_id {
+            return Err("Tasks can only move to a column of the same project".to_string());
+        }
+        check_wip_limit(&mut tx, &target, moving.len()).await?;
+        let mut ids = column_task_ids(&mut tx, target_id, 0).await?;
+        ids.extend(moving);
+        write_column_order(&mut tx, target_id, &ids).await?;
+        completed = sync_column_done(&mut tx, target_id).await?;
+    }
+
+    sqlx::query("DELETE FROM project_columns WHERE id = ?")
+        .bind(id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to delete project column: {}", e))?;
+
+    sqlx::query("UPDATE project_columns SET position = position - 1 WHERE project_id = ? AND position > ?")
+        .bind(column.project_id)
+        .bind(column.position)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to shift project columns: {}", e))?;
+
+    tx.commit()
+        .await
+        .map_err(|e| format!("Failed to commit column deletion: {}", e))?;
+
+    for task_id in completed {
+        after_task_completed(&app, &state.db, task_id).await;
+    }
+
+    Ok(())
+}
+
+/// Fails unless `column_ids` lists every column of the project exactly once.
+async fn check_column_set(
+    tx: &mut Transaction<'_, Sqlite>,
+    project_id: i64,
+    column_ids: &[i64],
+) -> Result<(), String> {
+    let mut existing = sqlx::query_scalar::<_, i64>("SELECT id FROM project_columns WHERE project_id = ?")
+        .bind(project_id)
+        .fetch_all(&mut **tx)
+        .await
+        .map_err(|e| format!("Failed to fetch project columns: {}", e))?;
+    existing.sort_unstable();
+    let mut requested = column_ids.to_vec();
+    requested.sort_unstable();
+
+    if requested != existing {
+        return Err("Column order must list every column of the project exactly once".to_string());
+    }
+    Ok(())
+}
+
+/// Sets the column order of a project. `column_ids` must list every column
+/// of the project exactly once.
+#[tauri::command]
+pub async fn reorder_project_columns(
+    state: State<'_, crate::AppState>,
+    project_id: i64,
+    column_ids: Vec<i64>,
+) -> Result<(), String> {
+    let mut tx = state
+        .db
+        .begin()
+        .await
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+
+    check_column_set(&mut tx, project_id, &column_ids).await?;
+
+    for (position, id) in column_ids.iter().enumerate() {
+        sqlx::query("UPDATE project_columns SET position = ?, updated_at = ? WHERE id = ? AND project_id = ?")
+            .bind(position as i64)
+            .bind(Utc::now().to_rfc3339())
+            .bind(id)
+            .bind(project_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| format!("Failed to update column position: {}", e))?;
+    }
+
+    tx.commit()
+        .await
+        .map_err(|e| format!("Failed to commit reorder: {}", e))?;
+
+    Ok(())
+}
+
+/// Moves a task into a column at `position` (the end when omitted), shifting
+/// the other tasks of both columns. Rejected when the target column is at its
+/// WIP limit. The task's `done` follows the column.
+#[tauri::command]
+pub async fn move_task_to_column(
+    app: AppHandle,
+    state: State<'_, crate::AppState>,
+    task_id: i64,
+    column_id: i64,
+    position: Option<i64>,
+) -> Result<(), String> {
+    let mut tx = state
+        .db
+        .begin()
+        .await
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+
+    let (project_id, from_column, was_done) = sqlx::query_as::<_, (i64, Option<i64>, bool)>(
+        "SELECT project_id, column_id, done FROM project_tasks WHERE id = ?",
+    )
+    .bind(task_id)
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(|e| format!("Failed to fetch project task: {}", e))?
+    .ok_or_else(|| format!("Project task {} not found", task_id))?;
+
+    let column = fetch_column(&mut tx, column_id).await?;
+    if column.project_id != project_id {
+        return Err("Tasks can only move to a column of the same project".to_string());
+    }
+
+    if from_column != Some(column_id) {
+        check_wip_limit(&mut tx, &column, 1).await?;
+    }
+    let mut ids = column_task_ids(&mut tx, column_id, task_id).await?;
+
+    let position = position.unwrap_or(ids.len() as i64).clamp(0, ids.len() as i64) as usize;
+    ids.insert(position, task_id);
+    write_column_order(&mut tx, column_id, &ids).await?;
+    if let Some(from) = from_column.filter(|f| *f != column_id) {
+        let remaining = column_task_ids(&mut tx, from, task_id).await?;
+        write_column_order(&mut tx, from, &remaining).await?;
+    }
+
+    sqlx::query(
+        r#"
+        UPDATE project_tasks
+        SET done = ?, completed_at = CASE WHEN ? THEN COALESCE(completed_at, ?) ELSE NULL END
+        WHERE id = ?
+        "#,
+    )
+    .bind(if column.is_done { 1 } else { 0 })
+    .bind(if column.is_done { 1 } else { 0 })
+    .bind(Utc::now().to_rfc3339())
+    .bind(task_id)
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| format!("Failed to update task done state: {}", e))?;
+
+    tx.commit()
+        .await
+        .map_err(|e| format!("Failed to commit task move: {}", e))?;
+
+    info!("phase=task_move_ok task={} column={} position={}", task_id, column_id, position);
+
+    if column.is_done && !was_done {
+        after_task_completed(&app, &state.db, task_id).await;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::memory_db;
+
+    async fn seed_project(db: &SqlitePool) -> i64 {
+        let now = Utc::now().to_rfc3339();
+        let project_id = sqlx::query("INSERT INTO projects (name, status, created_at, updated_at) VALUES ('Board', 'active', ?, ?)")
+            .bind(&now)
+            .bind(&now)
+            .execute(db)
+            .await
+            .unwrap()
+            .last_insert_rowid();
+        ensure_project_columns(db, project_id).await.unwrap();
+        project_id
+    }
+
+    async fn add_task(db: &SqlitePool, project_id: i64, order: i64) -> i64 {
+        let now = Utc::now().to_rfc3339();
+        sqlx::query("INSERT INTO project_tasks (project_id, title, done, \"order\", created_at, updated_at) VALUES (?, 'task', 0, ?, ?, ?)")
+            .bind(project_id)
+            .bind(order)
+            .bind(&now)
+            .bind(&now)
+            .execute(db)
+            .await
+            .unwrap()
+            .last_insert_rowid()
+    }
+
+    async fn set_limit(db: &SqlitePool, project_id: i64, name: &str, limit: i64) {
+        sqlx::query("UPDATE project_columns SET wip_limit = ? WHERE project_id = ? AND name = ?")
+            .bind(limit)
+            .bind(project_id)
+            .bind(name)
+            .execute(db)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn filing_a_task_respects_the_wip_limit() {
+        let db = memory_db().await;
+        let project_id = seed_project(&db).await;
+        set_limit(&db, project_id, "Todo", 1).await;
+        let first = add_task(&db, project_id, 0).await;
+        let second = add_task(&db, project_id, 1).await;
+
+        let mut tx = db.begin().await.unwrap();
+        assert!(file_task(&mut tx, first).await.unwrap());
+        assert!(!file_task(&mut tx, second).await.unwrap());
+        tx.commit().await.unwrap();
+
+        let unfiled: Option<i64> = sqlx::query_scalar("SELECT column_id FROM project_tasks WHERE id = ?")
+            .bind(second)
+            .fetch_one(&db)
+            .await
+            .unwrap();
+        assert_eq!(unfiled, None);
+    }
+
+    #[tokio::test]
+    async fn completing_a_task_respects_the_done_column_limit() {
+        let db = memory_db().await;
+        let project_id = seed_project(&db).await;
+        set_limit(&db, project_id, "Done", 1).await;
+        let first = add_task(&db, project_id, 0).await;
+        let second = add_task(&db, project_id, 1).await;
+        ensure_project_columns(&db, project_id).await.unwrap();
+
+        let mut tx = db.begin().await.unwrap();
+        sync_task_column(&mut tx, first, true).await.unwrap();
+        assert!(sync_task_column(&mut tx, second, true).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn board_moves_keep_sibling_order() {
+        let db = memory_db().await;
+        let project_id = seed_project(&db).await;
+        let first = add_task(&db, project_id, 7).await;
+        let second = add_task(&db, project_id, 3).await;
+        ensure_project_columns(&db, project_id).await.unwrap();
+
+        let mut tx = db.begin().await.unwrap();
+        sync_task_column(&mut tx, second, true).await.unwrap();
+        sync_task_column(&mut tx, first, true).await.unwrap();
+        tx.commit().await.unwrap();
+
+        let rows = sqlx::query_as::<_, (i64, i64)>(
+            "SELECT \"order\", column_order FROM project_tasks WHERE id IN (?, ?) ORDER BY id",
+        )
+        .bind(first)
+        .bind(second)
+        .fetch_all(&db)
+        .await
+        .unwrap();
+        assert_eq!(rows, vec![(7, 1), (3, 0)]);
+    }
+
+    #[tokio::test]
+    async fn marking_a_column_done_reports_the_tasks_it_completed() {
+        let db = memory_db().await;
+        let project_id = seed_project(&db).await;
+        let open = add_task(&db, project_id, 0).await;
+        ensure_project_columns(&db, project_id).await.unwrap();
+        let todo: i64 = sqlx::query_scalar("SELECT id FROM project_columns WHERE project_id = ? AND name = 'Todo'")
+            .bind(project_id)
+            .fetch_one(&db)
+            .await
+            .unwrap();
+        sqlx::query("UPDATE project_columns SET is_done = 1 WHERE id = ?")
+            .bind(todo)
+            .execute(&db)
+            .await
+            .unwrap();
+
+        let mut tx = db.begin().await.unwrap();
+        assert_eq!(sync_column_done(&mut tx, todo).await.unwrap(), vec![open]);
+        // Already done tasks are not reported again
+        assert!(sync_column_done(&mut tx, todo).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn column_order_must_list_each_column_once() {
+        let db = memory_db().await;
+        let project_id = seed_project(&db).await;
+        let other_project = seed_project(&db).await;
+        let ids = |project: i64| {
+            let db = db.clone();
+            async move {
+                sqlx::query_scalar::<_, i64>("SELECT id FROM project_columns WHERE project_id = ? ORDER BY position")
+                    .bind(project)
+                    .fetch_all(&db)
+                    .await
+                    .unwrap()
+            }
+        };
+        let columns = ids(project_id).await;
+        let foreign = ids(other_project).await;
+
+        let mut tx = db.begin().await.unwrap();
+        let reversed: Vec<i64> = columns.iter().rev().copied().collect();
+        assert!(check_column_set(&mut tx, project_id, &reversed).await.is_ok());
+        assert!(check_column_set(&mut tx, project_id, &columns[..2]).await.is_err());
+        assert!(check_column_set(&mut tx, project_id, &[columns[0], columns[0], columns[1]]).await.is_err());
+        assert!(check_column_set(&mut tx, project_id, &[columns[0], columns[1], foreign[0]]).await.is_err());
+    }
+}
@@ -0,0 +1,628 @@
+use log::info;
+use tauri::State;
+use sqlx::SqlitePool;
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+use crate::commands::project_columns::{check_wip_limit, create_default_columns, ensure_project_columns, file_task};
+use crate::commands::projects::record_status_change;
+use crate::models::{Project, ProjectColumn, ProjectMilestone, ProjectStatus, ProjectTask, ProjectTemplate};
+
+/// A project's structure with dates stored as day offsets from an anchor
+/// date, so it can be laid out again from any start date.
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TemplateContent {
+    pub description: Option<String>,
+    pub budget: Option<f64>,
+    pub budget_currency: Option<String>,
+    /// In board order; empty means the default columns
+    pub columns: Vec<TemplateColumn>,
+    pub milestones: Vec<TemplateMilestone>,
+    pub tasks: Vec<TemplateTask>,
+    /// `(task key, blocked-by task key)` pairs
+    pub dependencies: Vec<(usize, usize)>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TemplateColumn {
+    pub name: String,
+    pub wip_limit: Option<i64>,
+    #[serde(default)]
+    pub is_done: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TemplateMilestone {
+    pub name: String,
+    pub description: Option<String>,
+    pub target_offset_days: Option<i64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TemplateTask {
+    /// Unique within the template; referenced by `dependencies`
+    pub key: usize,
+    pub title: String,
+    #[serde(default)]
+    pub order: i64,
+    pub priority: Option<i64>,
+    pub estimated_minutes: Option<i64>,
+    pub start_offset_days: Option<i64>,
+    pub due_offset_days: Option<i64>,
+    /// Index into `milestones`
+    pub milestone: Option<usize>,
+    /// Index into `columns`; only used when cloning
+    pub column: Option<usize>,
+    /// Position within the column; only used when cloning
+    #[serde(default)]
+    pub column_order: i64,
+    pub recurrence: Option<serde_json::Value>,
+    /// Only used when cloning
+    #[serde(default)]
+    pub done: bool,
+    /// Only used when cloning
+    #[serde(default)]
+    pub completed_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub children: Vec<TemplateTask>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SaveProjectTemplateRequest {
+    pub project_id: i64,
+    pub name: String,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct InstantiateTemplateRequest {
+    pub template_id: i64,
+    pub name: String,
+    /// Relative dates resolve from here; defaults to today
+    pub start_date: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CloneProjectRequest {
+    pub project_id: i64,
+    pub name: String,
+    /// Moves every date by this many days
+    pub shift_days: Option<i64>,
+}
+
+#[tauri::command]
+pub async fn list_project_templates(state: State<'_, crate::AppState>) -> Result<Vec<ProjectTemplate>, String> {
+    sqlx::query_as::<_, ProjectTemplate>("SELECT * FROM project_templates ORDER BY name ASC")
+        .fetch_all(&state.db)
+        .await
+        .map_err(|e| format!("Failed to fetch project templates: {}", e))
+}
+
+/// Saves a project's description, task tree, milestones, columns and
+/// dependencies as a template. Done tasks are left out. Dates become offsets
+/// from the project's earliest start, due or target date.
+#[tauri::command]
+pub async fn save_project_as_template(
+    state: State<'_, crate::AppState>,
+    req: SaveProjectTemplateRequest,
+) -> Result<i64, String> {
+    let name = req.name.trim();
+    if name.is_empty() {
+        return Err("Template name is required".to_string());
+    }
+    let (content, _) = snapshot_project(&state.db, req.project_id, false).await?;
+    let content = serde_json::to_string(&content).map_err(|e| format!("Invalid template: {}", e))?;
+
+    let now = Utc::now();
+    let id = sqlx::query(
+        r#"
+        INSERT INTO project_templates (name, description, content, created_at, updated_at)
+        VALUES (?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(name)
+    .bind(&req.description)
+    .bind(content)
+    .bind(now.to_rfc3339())
+    .bind(now.to_rfc3339())
+    .execute(&state.db)
+    .await
+    .map_err(|e| match e {
+        sqlx::Error::Database(db) if db.is_unique_violation() => format!("A template named {} already exists", name),
+        e => format!("Failed to save project template: {}", e),
+    })?
+    .last_insert_rowid();
+
+    info!("phase=project_template_save_ok id={} project={}", id, req.project_id);
+    Ok(id)
+}
+
+#[tauri::command]
+pub async fn delete_project_template(state: State<'_, crate::AppState>, id: i64) -> Result<(), String> {
+    sqlx::query("DELETE FROM project_templates WHERE id = ?")
+        .bind(id)
+        .execute(&state.db)
+        .await
+        .map_err(|e| format!("Failed to delete project template: {}", e))?;
+
+    Ok(())
+}
+
+/// Creates a new active project from a template, resolving relative dates
+/// from `start_date`. Every task starts open.
+#[tauri::command]
+pub async fn instantiate_project_template(
+    state: State<'_, crate::AppState>,
+    req: InstantiateTemplateRequest,
+) -> Result<i64, String> {
+    let template = sqlx::query_as::<_, ProjectTemplate>("SELECT * FROM project_templates WHERE id = ?")
+        .bind(req.template_id)
+        .fetch_optional(&state.db)
+        .await
+        .map_err(|e| format!("Failed to fetch project template: {}", e))?
+        .ok_or_else(|| format!("Project template {} not found", req.template_id))?;
+    let content: TemplateContent =
+        serde_json::from_value(template.content).map_err(|e| format!("Invalid template content: {}", e))?;
+
+    let start = match req.start_date.as_deref().map(str::trim).filter(|d| !d.is_empty()) {
+        Some(date) => parse_date(date).ok_or_else(|| format!("Invalid start date: {}", date))?,
+        None => Utc::now().date_naive(),
+    };
+
+    let id = create_from_content(&state.db, &content, &req.name, start, false).await?;
+    info!("phase=project_template_use_ok template={} project={}", req.template_id, id);
+    Ok(id)
+}
+
+/// Copies a project with its task tree, milestones, columns, dependencies
+/// and progress. Time entries, focus sessions and status history stay with
+/// the original.
+#[tauri::command]
+pub async fn clone_project(state: State<'_, crate::AppState>, req: CloneProjectRequest) -> Result<i64, String> {
+    let (content, anchor) = snapshot_project(&state.db, req.project_id, true).await?;
+    let start = anchor + Duration::days(req.shift_days.unwrap_or(0));
+
+    let id = create_from_content(&state.db, &content, &req.name, start, true).await?;
+    info!("phase=project_clone_ok from={} to={}", req.project_id, id);
+    Ok(id)
+}
+
+fn parse_date(raw: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(raw, "%Y-%m-%d").ok()
+}
+
+/// The project as template content plus the anchor its offsets are relative to.
+/// Without `keep_progress`, done tasks and past recurrence occurrences are left
+/// out and their open subtasks move up to the nearest kept ancestor.
+async fn snapshot_project(
+    db: &SqlitePool,
+    project_id: i64,
+    keep_progress: bool,
+) -> Result<(TemplateContent, NaiveDate), String> {
+    let project = sqlx::query_as::<_, Project>("SELECT * FROM projects WHERE id = ?")
+        .bind(project_id)
+        .fetch_optional(db)
+        .await
+        .map_err(|e| format!("Failed to fetch project: {}", e))?
+        .ok_or_else(|| format!("Project {} not found", project_id))?;
+
+    ensure_project_columns(db, project_id).await?;
+    let columns = sqlx::query_as::<_, ProjectColumn>(
+        "SELECT * FROM project_columns WHERE project_id = ? ORDER BY position ASC, id ASC",
+    )
+    .bind(project_id)
+    .fetch_all(db)
+    .await
+    .map_err(|e| format!("Failed to fetch project columns: {}", e))?;
+
+    let milestones = sqlx::query_as::<_, ProjectMilestone>(
+        "SELECT * FROM project_milestones WHERE project_id = ? ORDER BY target_date IS NULL, target_date ASC, id ASC",
+    )
+    .bind(project_id)
+    .fetch_all(db)
+    .await
+    .map_err(|e| format!("Failed to fetch milestones: {}", e))?;
+
+    let tasks = sqlx::query_as::<_, ProjectTask>(
+        "SELECT * FROM project_tasks WHERE project_id = ? ORDER BY \"order\" ASC, id ASC",
+    )
+    .bind(project_id)
+    .fetch_all(db)
+    .await
+    .map_err(|e| format!("Failed to fetch project tasks: {}", e))?;
+    let skipped: HashSet<i64> = tasks
+        .iter()
+        .filter(|t| !keep_progress && (t.done || t.next_occurrence_id.is_some()))
+        .map(|t| t.id)
+        .collect();
+
+    let dependencies = sqlx::query_as::<_, (i64, i64)>(
+        r#"
+        SELECT d.task_id, d.blocked_by_task_id FROM project_task_dependencies d
+        JOIN project_tasks t ON t.id = d.task_id
+        JOIN project_tasks b ON b.id = d.blocked_by_task_id
+        WHERE t.project_id = ? AND b.project_id = ?
+        "#,
+    )
+    .bind(project_id)
+    .bind(project_id)
+    .fetch_all(db)
+    .await
+    .map_err(|e| format!("Failed to fetch task dependencies: {}", e))?;
+
+    let anchor = tasks
+        .iter()
+        .filter(|t| !skipped.contains(&t.id))
+        .flat_map(|t| [t.start_date.as_deref(), t.due_date.as_deref()])
+        .chain(milestones.iter().map(|m| m.target_date.as_deref()))
+        .flatten()
+        .filter_map(parse_date)
+        .min()
+        .unwrap_or_else(|| project.created_at.date_naive());
+    let offset = |date: Option<&str>| date.and_then(parse_date).map(|d| (d - anchor).num_days());
+
+    let column_index: HashMap<i64, usize> = columns.iter().enumerate().map(|(i, c)| (c.id, i)).collect();
+    let milestone_index: HashMap<i64, usize> = milestones.iter().enumerate().map(|(i, m)| (m.id, i)).collect();
+    let task_keys: HashMap<i64, usize> = tasks.iter().enumerate().map(|(i, t)| (t.id, i)).collect();
+
+    let parents: HashMap<i64, Option<i64>> = tasks.iter().map(|t| (t.id, t.parent_task_id)).collect();
+    let mut children: HashMap<Option<i64>, Vec<TemplateTask>> = HashMap::new();
+    // Build bottom-up: deepest tasks first so their parents can take them
+    let mut ordered: Vec<&ProjectTask> = tasks.iter().collect();
+    ordered.sort_by_key(|t| std::cmp::Reverse(depth(t.id, &parents)));
+    for task in ordered {
+        let mut subtasks = children.remove(&Some(task.id)).unwrap_or_default();
+        if skipped.contains(&task.id) {
+            children.entry(task.parent_task_id).or_default().append(&mut subtasks);
+            continue;
+        }
+        let node = TemplateTask {
+            key: task_keys[&task.id],
+            title: task.title.clone(),
+            order: task.order,
+            priority: task.priority,
+            estimated_minutes: task.estimated_minutes,
+            start_offset_days: offset(task.start_date.as_deref()),
+            due_offset_days: offset(task.due_date.as_deref()),
+            milestone: task.milestone_id.and_then(|m| milestone_index.get(&m).copied()),
+            column: task.column_id.and_then(|c| column_index.get(&c).copied()),
+            column_order: task.column_order,
+            recurrence: task.recurrence.clone(),
+            done: task.done,
+            completed_at: task.completed_at.filter(|_| task.done),
+            children: subtasks,
+        };
+        children.entry(task.parent_task_id).or_default().push(node);
+    }
+    let mut roots = children.remove(&None).unwrap_or_default();
+    sort_tree(&mut roots);
+
+    let content = TemplateContent {
+        description: project.description,
+        budget: project.budget,
+        budget_currency: project.budget_currency,
+        columns: columns
+            .iter()
+            .map(|c| TemplateColumn {
+                name: c.name.clone(),
+                wip_limit: c.wip_limit,
+                is_done: c.is_done,
+            })
+            .collect(),
+        milestones: milestones
+            .iter()
+            .map(|m| TemplateMilestone {
+                name: m.name.clone(),
+                description: m.description.clone(),
+                target_offset_days: offset(m.target_date.as_deref()),
+            })
+            .collect(),
+        tasks: roots,
+        dependencies: dependencies
+            .iter()
+            .filter(|(task, blocker)| !skipped.contains(task) && !skipped.contains(blocker))
+            .filter_map(|(task, blocker)| Some((*task_keys.get(task)?, *task_keys.get(blocker)?)))
+            .collect(),
+    };
+
+    Ok((content, anchor))
+}
+
+fn depth(task_id: i64, parents: &HashMap<i64, Option<i64>>) -> usize {
+    let mut depth = 0;
+    let mut current = parents.get(&task_id).copied().flatten();
+    while let Some(parent) = current {
+        depth += 1;
+        current = parents.get(&parent).copied().flatten();
+    }
+    depth
+}
+
+fn sort_tree(tasks: &mut [TemplateTask]) {
+    tasks.sort_by_key(|t| (t.order, t.key));
+    for task in tasks {
+        sort_tree(&mut task.children);
+    }
+}
+
+/// Tasks in parent-before-child order, each with its parent's key.
+fn flatten_tasks<'a>(tasks: &'a [TemplateTask], parent: Option<usize>, out: &mut Vec<(Option<usize>, &'a TemplateTask)>) {
+    for task in tasks {
+        out.push((parent, task));
+        flatten_tasks(&task.children, Some(task.key), out);
+    }
+}
+
+/// Creates a project from template content with dates resolved from `start`.
+/// `keep_progress` also restores each task's column, done state and
+/// completion time.
+async fn create_from_content(
+    db: &SqlitePool,
+    content: &TemplateContent,
+    name: &str,
+    start: NaiveDate,
+    keep_progress: bool,
+) -> Result<i64, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("Project name is required".to_string());
+    }
+    let resolve = |offset: Option<i64>| offset.map(|o| (start + Duration::days(o)).format("%Y-%m-%d").to_string());
+    let now = Utc::now().to_rfc3339();
+
+    let mut tx = db
+        .begin()
+        .await
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+
+    let project_id = sqlx::query(
+        r#"
+        INSERT INTO projects (name, description, status, budget, budget_currency, created_at, updated_at)
+        VALUES (?, ?, ?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(name)
+    .bind(&content.description)
+    .bind(ProjectStatus::Active.as_str())
+    .bind(content.budget)
+    .bind(&content.budget_currency)
+    .bind(&now)
+    .bind(&now)
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| format!("Failed to create project: {}", e))?
+    .last_insert_rowid();
+
+    record_status_change(&mut tx, project_id, None, ProjectStatus::Active, None).await?;
+
+    for (position, column) in content.columns.iter().enumerate() {
+        sqlx::query(
+            r#"
+            INSERT INTO project_columns (project_id, name, position, wip_limit, is_done, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(project_id)
+        .bind(&column.name)
+        .bind(position as i64)
+        .bind(column.wip_limit)
+        .bind(if column.is_done { 1 } else { 0 })
+        .bind(&now)
+        .bind(&now)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to create project column: {}", e))?;
+    }
+    create_default_columns(&mut tx, project_id).await?;
+    let columns = sqlx::query_as::<_, ProjectColumn>(
+        "SELECT * FROM project_columns WHERE project_id = ? ORDER BY position ASC, id ASC",
+    )
+    .bind(project_id)
+    .fetch_all(&mut *tx)
+    .await
+    .map_err(|e| format!("Failed to fetch project columns: {}", e))?;
+
+    let mut milestone_ids = Vec::with_capacity(content.milestones.len());
+    for milestone in &content.milestones {
+        let id = sqlx::query(
+            r#"
+            INSERT INTO project_milestones (project_id, name, description, target_date, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(project_id)
+        .bind(&milestone.name)
+        .bind(&milestone.description)
+        .bind(resolve(milestone.target_offset_days))
+        .bind(&now)
+        .bind(&now)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to create milestone: {}", e))?
+        .last_insert_rowid();
+        milestone_ids.push(id);
+    }
+
+    let mut flat = Vec::new();
+    flatten_tasks(&content.tasks, None, &mut flat);
+    let mut task_ids: HashMap<usize, i64> = HashMap::new();
+    for (parent, task) in flat {
+        let parent_id = parent.and_then(|p| task_ids.get(&p).copied());
+        let milestone_id = task.milestone.and_then(|m| milestone_ids.get(m).copied());
+        let column = task.column.filter(|_| keep_progress).and_then(|c| columns.get(c));
+        let done = keep_progress && task.done;
+
+        let id = sqlx::query(
+            r#"
+            INSERT INTO project_tasks (
+                project_id, title, done, "order", parent_task_id, due_date, start_date, priority,
+                estimated_minutes, milestone_id, recurrence, completed_at, created_at, updated_at
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(project_id)
+        .bind(&task.title)
+        .bind(if done { 1 } else { 0 })
+        .bind(task.order)
+        .bind(parent_id)
+        .bind(resolve(task.due_offset_days))
+        .bind(resolve(task.start_offset_days))
+        .bind(task.priority)
+        .bind(task.estimated_minutes)
+        .bind(milestone_id)
+        .bind(task.recurrence.as_ref().map(|r| r.to_string()))
+        .bind(if done { Some(task.completed_at.map_or_else(|| now.clone(), |t| t.to_rfc3339())) } else { None })
+        .bind(&now)
+        .bind(&now)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to create project task: {}", e))?
+        .last_insert_rowid();
+        task_ids.insert(task.key, id);
+
+        match column {
+            Some(column) => {
+                check_wip_limit(&mut tx, column, 1).await?;
+                sqlx::query("UPDATE project_tasks SET column_id = ?, column_order = ? WHERE id = ?")
+                    .bind(column.id)
+                    .bind(task.column_order)
+                    .bind(id)
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(|e| format!("Failed to place project task: {}", e))?;
+            }
+            None => {
+                file_task(&mut tx, id).await?;
+            }
+        }
+    }
+
+    for (task, blocker) in &content.dependencies {
+        let (Some(task_id), Some(blocker_id)) = (task_ids.get(task), task_ids.get(blocker)) else {
+            continue;
+        };
+        sqlx::query(
+            r#"
+            INSERT OR IGNORE INTO project_task_dependencies (task_id, blocked_by_task_id, created_at)
+            VALUES (?, ?, ?)
+            "#,
+        )
+        .bind(task_id)
+        .bind(blocker_id)
+        .bind(&now)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to copy task dependency: {}", e))?;
+    }
+
+    tx.commit()
+        .await
+        .map_err(|e| format!("Failed to commit project: {}", e))?;
+
+    Ok(project_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::memory_db;
+
+    async fn seed_project(db: &SqlitePool) -> i64 {
+        let now = Utc::now().to_rfc3339();
+        sqlx::query("INSERT INTO projects (name, status, created_at, updated_at) VALUES ('Launch', 'active', ?, ?)")
+            .bind(&now)
+            .bind(&now)
+            .execute(db)
+            .await
+            .unwrap()
+            .last_insert_rowid()
+    }
+
+    async fn add_task(db: &SqlitePool, project_id: i64, title: &str, parent: Option<i64>, completed_at: Option<&str>) -> i64 {
+        let now = Utc::now().to_rfc3339();
+        sqlx::query(
+            "INSERT INTO project_tasks (project_id, title, done, parent_task_id, completed_at, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(project_id)
+        .bind(title)
+        .bind(completed_at.is_some())
+        .bind(parent)
+        .bind(completed_at)
+        .bind(&now)
+        .bind(&now)
+        .execute(db)
+        .await
+        .unwrap()
+        .last_insert_rowid()
+    }
+
+    fn titles(tasks: &[TemplateTask]) -> Vec<String> {
+        let mut flat = Vec::new();
+        flatten_tasks(tasks, None, &mut flat);
+        flat.iter().map(|(_, t)| t.title.clone()).collect()
+    }
+
+    #[tokio::test]
+    async fn templates_leave_out_done_tasks_and_past_occurrences() {
+        let db = memory_db().await;
+        let project_id = seed_project(&db).await;
+        let shipped = add_task(&db, project_id, "Ship beta", None, Some("2024-03-01T10:00:00+00:00")).await;
+        add_task(&db, project_id, "Write notes", Some(shipped), None).await;
+        let standup = add_task(&db, project_id, "Standup", None, None).await;
+        let past = add_task(&db, project_id, "Standup", None, None).await;
+        sqlx::query("UPDATE project_tasks SET next_occurrence_id = ? WHERE id = ?")
+            .bind(standup)
+            .bind(past)
+            .execute(&db)
+            .await
+            .unwrap();
+        sqlx::query("INSERT INTO project_task_dependencies (task_id, blocked_by_task_id, created_at) VALUES (?, ?, ?)")
+            .bind(standup)
+            .bind(shipped)
+            .bind(Utc::now().to_rfc3339())
+            .execute(&db)
+            .await
+            .unwrap();
+
+        let (template, _) = snapshot_project(&db, project_id, false).await.unwrap();
+        let mut kept = titles(&template.tasks);
+        kept.sort();
+        assert_eq!(kept, vec!["Standup", "Write notes"]);
+        // The open subtask moved up in place of its done parent
+        assert_eq!(template.tasks.len(), 2);
+        assert!(template.dependencies.is_empty());
+
+        let (copy, _) = snapshot_project(&db, project_id, true).await.unwrap();
+        assert_eq!(titles(&copy.tasks).len(), 4);
+        assert_eq!(copy.dependencies.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn clones_keep_the_original_completion_time() {
+        let db = memory_db().await;
+        let project_id = seed_project(&db).await;
+        add_task(&db, project_id, "Ship beta", None, Some("2024-03-01T10:00:00+00:00")).await;
+
+        let (content, anchor) = snapshot_project(&db, project_id, true).await.unwrap();
+        let clone_id = create_from_content(&db, &content, "Launch copy", anchor, true).await.unwrap();
+
+        let task = sqlx::query_as::<_, ProjectTask>("SELECT * FROM project_tasks WHERE project_id = ?")
+            .bind(clone_id)
+            .fetch_one(&db)
+            .await
+            .unwrap();
+        assert!(task.done);
+        assert_eq!(task.completed_at.map(|t| t.to_rfc3339()).as_deref(), Some("2024-03-01T10:00:00+00:00"));
+
+        let fresh_id = create_from_content(&db, &content, "Launch again", anchor, false).await.unwrap();
+        let completed: Option<String> = sqlx::query_scalar("SELECT completed_at FROM project_tasks WHERE project_id = ?")
+            .bind(fresh_id)
+            .fetch_one(&db)
+            .await
+            .unwrap();
+        assert_eq!(completed, None);
+    }
+}
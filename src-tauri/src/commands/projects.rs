@@ -1,15 +1,24 @@
-use tauri::State;
-use sqlx::SqlitePool;
-use chrono::Utc;
+use tauri::{AppHandle, State};
+use sqlx::{Sqlite, SqlitePool, Transaction};
+use chrono::{Datelike, NaiveDate, Utc};
 use serde::Deserialize;
+use log::{info, warn};
+use std::collections::HashMap;
 
-use crate::models::{Project, ProjectTask, ProjectStatus};
+use crate::commands::project_columns::{create_default_columns, file_task, sync_task_column};
+use crate::commands::recurrence::create_next_occurrence;
+use crate::commands::task_dependencies::announce_unblocked;
+use crate::models::{
+    Project, ProjectTask, ProjectTaskNode, ProjectStatus, ProjectStatusChange, ScheduledProjectTask,
+};
 
 #[derive(Debug, Deserialize)]
 pub struct CreateProjectRequest {
     pub name: String,
     pub description: Option<String>,
     pub status: Option<String>,
+    pub budget: Option<f64>,
+    pub budget_currency: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -18,6 +27,12 @@ pub struct UpdateProjectRequest {
     pub name: Option<String>,
     pub description: Option<String>,
     pub status: Option<String>,
+    /// Recorded in the status history when `status` changes
+    pub status_reason: Option<String>,
+    /// A negative budget clears it
+    pub budget: Option<f64>,
+    /// A blank currency clears it
+    pub budget_currency: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -25,6 +40,11 @@ pub struct CreateProjectTaskRequest {
     pub project_id: i64,
     pub title: String,
     pub order: i64,
+    pub parent_task_id: Option<i64>,
+    pub due_date: Option<String>,
+    pub start_date: Option<String>,
+    pub priority: Option<i64>,
+    pub estimated_minutes: Option<i64>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -33,6 +53,14 @@ pub struct UpdateProjectTaskRequest {
     pub title: Option<String>,
     pub done: Option<bool>,
     pub order: Option<i64>,
+    /// An empty string clears the date
+    pub due_date: Option<String>,
+    /// An empty string clears the date
+    pub start_date: Option<String>,
+    /// A negative priority clears it
+    pub priority: Option<i64>,
+    /// A negative estimate clears it
+    pub estimated_minutes: Option<i64>,
 }
 
 #[tauri::command]
@@ -55,37 +83,111 @@ pub async fn create_project(
         .status
         .as_deref()
         .unwrap_or("active")
-        .parse::<ProjectStatus>()
-        .unwrap_or_else(|_| ProjectStatus::Active);
+        .parse::<ProjectStatus>()?;
 
-    sqlx::query(
+    let mut tx = state
+        .db
+        .begin()
+        .await
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+
+    let id = sqlx::query(
         r#"
-        INSERT INTO projects (name, description, status, created_at, updated_at)
-        VALUES (?, ?, ?, ?, ?)
+        INSERT INTO projects (name, description, status, budget, budget_currency, created_at, updated_at)
+        VALUES (?, ?, ?, ?, ?, ?, ?)
         "#,
     )
     .bind(&req.name)
     .bind(&req.description)
-    .bind(format!("{:?}", status).to_lowercase())
+    .bind(status.as_str())
+    .bind(req.budget.and_then(normalize_budget))
+    .bind(req.budget_currency.as_deref().and_then(normalize_currency))
     .bind(now.to_rfc3339())
     .bind(now.to_rfc3339())
-    .execute(&state.db)
+    .execute(&mut *tx)
     .await
-    .map_err(|e| format!("Failed to create project: {}", e))?;
+    .map_err(|e| format!("Failed to create project: {}", e))?
+    .last_insert_rowid();
+
+    record_status_change(&mut tx, id, None, status, None).await?;
+    create_default_columns(&mut tx, id).await?;
 
-    let id = sqlx::query_scalar::<_, i64>("SELECT last_insert_rowid()")
-        .fetch_one(&state.db)
+    tx.commit()
         .await
-        .map_err(|e| format!("Failed to get project ID: {}", e))?;
+        .map_err(|e| format!("Failed to commit project: {}", e))?;
 
     Ok(id)
 }
 
+pub(crate) async fn record_status_change(
+    tx: &mut Transaction<'_, Sqlite>,
+    project_id: i64,
+    from: Option<ProjectStatus>,
+    to: ProjectStatus,
+    reason: Option<&str>,
+) -> Result<(), String> {
+    sqlx::query(
+        r#"
+        INSERT INTO project_status_history (project_id, from_status, to_status, reason, changed_at)
+        VALUES (?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(project_id)
+    .bind(from.map(|s| s.as_str()))
+    .bind(to.as_str())
+    .bind(reason.map(str::trim).filter(|r| !r.is_empty()))
+    .bind(Utc::now().to_rfc3339())
+    .execute(&mut **tx)
+    .await
+    .map_err(|e| format!("Failed to record project status change: {}", e))?;
+
+    Ok(())
+}
+
+async fn current_status(
+    tx: &mut Transaction<'_, Sqlite>,
+    project_id: i64,
+) -> Result<ProjectStatus, String> {
+    sqlx::query_scalar::<_, ProjectStatus>("SELECT status FROM projects WHERE id = ?")
+        .bind(project_id)
+        .fetch_optional(&mut **tx)
+        .await
+        .map_err(|e| format!("Failed to fetch project: {}", e))?
+        .ok_or_else(|| format!("Project {} not found", project_id))
+}
+
 #[tauri::command]
 pub async fn update_project(
     state: State<'_, crate::AppState>,
     req: UpdateProjectRequest,
 ) -> Result<(), String> {
+    let status = req
+        .status
+        .as_deref()
+        .map(str::parse::<ProjectStatus>)
+        .transpose()?;
+
+    let mut tx = state
+        .db
+        .begin()
+        .await
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+
+    let previous = current_status(&mut tx, req.id).await?;
+    let status = status.filter(|s| *s != previous);
+    if let Some(status) = status {
+        if previous == ProjectStatus::Archived {
+            return Err("Archived projects must be unarchived before changing status".to_string());
+        }
+        if !previous.can_transition_to(status) {
+            return Err(format!(
+                "Cannot change project status from {} to {}",
+                previous.as_str(),
+                status.as_str()
+            ));
+        }
+    }
+
     let mut updates = Vec::new();
     if req.name.is_some() {
         updates.push("name = ?");
@@ -93,9 +195,15 @@ pub async fn update_project(
     if req.description.is_some() {
         updates.push("description = ?");
     }
-    if req.status.is_some() {
+    if status.is_some() {
         updates.push("status = ?");
     }
+    if req.budget.is_some() {
+        updates.push("budget = ?");
+    }
+    if req.budget_currency.is_some() {
+        updates.push("budget_currency = ?");
+    }
     updates.push("updated_at = ?");
 
     let query = format!(
@@ -110,19 +218,94 @@ pub async fn update_project(
     if let Some(desc) = &req.description {
         q = q.bind(desc);
     }
-    if let Some(status) = &req.status {
-        q = q.bind(status.to_lowercase());
+    if let Some(status) = status {
+        q = q.bind(status.as_str());
+    }
+    if let Some(budget) = req.budget {
+        q = q.bind(normalize_budget(budget));
+    }
+    if let Some(currency) = &req.budget_currency {
+        q = q.bind(normalize_currency(currency));
     }
     q = q.bind(Utc::now().to_rfc3339());
     q = q.bind(req.id);
 
-    q.execute(&state.db)
+    q.execute(&mut *tx)
         .await
         .map_err(|e| format!("Failed to update project: {}", e))?;
 
+    if let Some(status) = status {
+        record_status_change(&mut tx, req.id, Some(previous), status, req.status_reason.as_deref()).await?;
+    }
+
+    tx.commit()
+        .await
+        .map_err(|e| format!("Failed to commit project update: {}", e))?;
+
+    if let Some(status) = status {
+        info!(
+            "phase=project_status_ok id={} from={} to={}",
+            req.id,
+            previous.as_str(),
+            status.as_str()
+        );
+    }
+
+    Ok(())
+}
+
+/// The only way out of `archived`; brings the project back as active.
+#[tauri::command]
+pub async fn unarchive_project(
+    state: State<'_, crate::AppState>,
+    id: i64,
+    reason: Option<String>,
+) -> Result<(), String> {
+    let mut tx = state
+        .db
+        .begin()
+        .await
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+
+    let previous = current_status(&mut tx, id).await?;
+    if previous != ProjectStatus::Archived {
+        return Err(format!("Project is {}, not archived", previous.as_str()));
+    }
+
+    sqlx::query("UPDATE projects SET status = ?, updated_at = ? WHERE id = ?")
+        .bind(ProjectStatus::Active.as_str())
+        .bind(Utc::now().to_rfc3339())
+        .bind(id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to unarchive project: {}", e))?;
+
+    record_status_change(&mut tx, id, Some(previous), ProjectStatus::Active, reason.as_deref()).await?;
+
+    tx.commit()
+        .await
+        .map_err(|e| format!("Failed to commit unarchive: {}", e))?;
+
+    info!("phase=project_unarchive_ok id={}", id);
     Ok(())
 }
 
+#[tauri::command]
+pub async fn list_project_status_history(
+    state: State<'_, crate::AppState>,
+    project_id: i64,
+) -> Result<Vec<ProjectStatusChange>, String> {
+    let history = sqlx::query_as::<_, ProjectStatusChange>(
+        "SELECT * FROM project_status_history WHERE project_id = ? ORDER BY changed_at DESC, id DESC",
+    )
+    .bind(project_id)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|e| format!("Failed to fetch project status history: {}", e))?;
+
+    Ok(history)
+}
+
 #[tauri::command]
 pub async fn delete_project(state: State<'_, crate::AppState>, id: i64) -> Result<(), String> {
     sqlx::query("DELETE FROM projects WHERE id = ?")
@@ -140,7 +323,10 @@ pub async fn list_project_tasks(
     project_id: i64,
 ) -> Result<Vec<ProjectTask>, String> {
     let tasks = sqlx::query_as::<_, ProjectTask>(
-        "SELECT * FROM project_tasks WHERE project_id = ? ORDER BY \"order\" ASC",
+        &format!(
+            "SELECT t.*, {} FROM project_tasks t WHERE t.project_id = ? ORDER BY t.\"order\" ASC",
+            BLOCKED_COLUMN
+        ),
     )
     .bind(project_id)
     .fetch_all(&state.db)
@@ -150,51 +336,225 @@ pub async fn list_project_tasks(
     Ok(tasks)
 }
 
+/// Tasks of a project nested under their parents, siblings in `order`.
+#[tauri::command]
+pub async fn list_project_task_tree(
+    state: State<'_, crate::AppState>,
+    project_id: i64,
+) -> Result<Vec<ProjectTaskNode>, String> {
+    let tasks = sqlx::query_as::<_, ProjectTask>(
+        &format!(
+            "SELECT t.*, {} FROM project_tasks t WHERE t.project_id = ? ORDER BY t.\"order\" ASC, t.id ASC",
+            BLOCKED_COLUMN
+        ),
+    )
+    .bind(project_id)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|e| format!("Failed to fetch project tasks: {}", e))?;
+
+    Ok(build_task_tree(tasks))
+}
+
+pub(crate) fn build_task_tree(tasks: Vec<ProjectTask>) -> Vec<ProjectTaskNode> {
+    let mut children: HashMap<Option<i64>, Vec<ProjectTask>> = HashMap::new();
+    for task in tasks {
+        children.entry(task.parent_task_id).or_default().push(task);
+    }
+    build_nodes(None, &mut children)
+}
+
+fn build_nodes(
+    parent: Option<i64>,
+    children: &mut HashMap<Option<i64>, Vec<ProjectTask>>,
+) -> Vec<ProjectTaskNode> {
+    let Some(tasks) = children.remove(&parent) else {
+        return Vec::new();
+    };
+
+    tasks
+        .into_iter()
+        .map(|task| {
+            let subtasks = build_nodes(Some(task.id), children);
+            let (total_leaves, done_leaves) = if subtasks.is_empty() {
+                (1, if task.done { 1 } else { 0 })
+            } else {
+                subtasks
+                    .iter()
+                    .fold((0, 0), |(t, d), c| (t + c.total_leaves, d + c.done_leaves))
+            };
+            ProjectTaskNode {
+                task,
+                children: subtasks,
+                total_leaves,
+                done_leaves,
+                completion: done_leaves as f64 * 100.0 / total_leaves as f64,
+                rolled_up_done: done_leaves == total_leaves,
+            }
+        })
+        .collect()
+}
+
+/// Checks that `parent_id` is a task of `project_id` and is not `task_id`
+/// itself or one of its descendants.
+async fn validate_parent(
+    db: &SqlitePool,
+    project_id: i64,
+    parent_id: i64,
+    task_id: Option<i64>,
+) -> Result<(), String> {
+    let parent_project = sqlx::query_scalar::<_, i64>("SELECT project_id FROM project_tasks WHERE id = ?")
+        .bind(parent_id)
+        .fetch_optional(db)
+        .await
+        .map_err(|e| format!("Failed to fetch parent task: {}", e))?
+        .ok_or_else(|| format!("Parent task {} not found", parent_id))?;
+    if parent_project != project_id {
+        return Err("Parent task belongs to another project".to_string());
+    }
+
+    if let Some(task_id) = task_id {
+        let creates_cycle = sqlx::query_scalar::<_, i64>(
+            r#"
+            WITH RECURSIVE ancestors(id) AS (
+                SELECT ?
+                UNION
+                SELECT t.parent_task_id FROM project_tasks t
+                JOIN ancestors a ON t.id = a.id
+                WHERE t.parent_task_id IS NOT NULL
+            )
+            SELECT COUNT(*) FROM ancestors WHERE id = ?
+            "#,
+        )
+        .bind(parent_id)
+        .bind(task_id)
+        .fetch_one(db)
+        .await
+        .map_err(|e| format!("Failed to check task hierarchy: {}", e))?;
+        if creates_cycle > 0 {
+            return Err("A task cannot be nested under itself or its subtasks".to_string());
+        }
+    }
+
+    Ok(())
+}
+
 #[tauri::command]
 pub async fn create_project_task(
     state: State<'_, crate::AppState>,
     req: CreateProjectTaskRequest,
 ) -> Result<i64, String> {
     let now = Utc::now();
+    if let Some(parent_id) = req.parent_task_id {
+        validate_parent(&state.db, req.project_id, parent_id, None).await?;
+    }
+    let due_date = parse_task_date(req.due_date.as_deref(), "due")?;
+    let start_date = parse_task_date(req.start_date.as_deref(), "start")?;
+    if let (Some(start), Some(due)) = (&start_date, &due_date) {
+        if start > due {
+            return Err("Start date must not be after the due date".to_string());
+        }
+    }
+    if let Some(priority) = req.priority {
+        validate_priority(priority)?;
+    }
 
-    sqlx::query(
+    let mut tx = state
+        .db
+        .begin()
+        .await
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+
+    let id = sqlx::query(
         r#"
-        INSERT INTO project_tasks (project_id, title, done, "order", created_at, updated_at)
-        VALUES (?, ?, 0, ?, ?, ?)
+        INSERT INTO project_tasks (
+            project_id, title, done, "order", parent_task_id,
+            due_date, start_date, priority, estimated_minutes, created_at, updated_at
+        )
+        VALUES (?, ?, 0, ?, ?, ?, ?, ?, ?, ?, ?)
         "#,
     )
     .bind(req.project_id)
     .bind(&req.title)
     .bind(req.order)
+    .bind(req.parent_task_id)
+    .bind(&due_date)
+    .bind(&start_date)
+    .bind(req.priority)
+    .bind(req.estimated_minutes.filter(|m| *m >= 0))
     .bind(now.to_rfc3339())
     .bind(now.to_rfc3339())
-    .execute(&state.db)
+    .execute(&mut *tx)
     .await
-    .map_err(|e| format!("Failed to create project task: {}", e))?;
+    .map_err(|e| format!("Failed to create project task: {}", e))?
+    .last_insert_rowid();
+
+    // Files the new task into the first open column, if it has room
+    create_default_columns(&mut tx, req.project_id).await?;
+    file_task(&mut tx, id).await?;
 
-    let id = sqlx::query_scalar::<_, i64>("SELECT last_insert_rowid()")
-        .fetch_one(&state.db)
+    tx.commit()
         .await
-        .map_err(|e| format!("Failed to get project task ID: {}", e))?;
+        .map_err(|e| format!("Failed to commit project task: {}", e))?;
 
     Ok(id)
 }
 
 #[tauri::command]
 pub async fn update_project_task(
+    app: AppHandle,
     state: State<'_, crate::AppState>,
     req: UpdateProjectTaskRequest,
 ) -> Result<(), String> {
+    let due_date = req
+        .due_date
+        .as_deref()
+        .map(|d| parse_task_date(Some(d), "due"))
+        .transpose()?;
+    let start_date = req
+        .start_date
+        .as_deref()
+        .map(|d| parse_task_date(Some(d), "start"))
+        .transpose()?;
+    if let (Some(Some(start)), Some(Some(due))) = (&start_date, &due_date) {
+        if start > due {
+            return Err("Start date must not be after the due date".to_string());
+        }
+    }
+    if let Some(priority) = req.priority.filter(|p| *p >= 0) {
+        validate_priority(priority)?;
+    }
+    let was_done = sqlx::query_scalar::<_, bool>("SELECT done FROM project_tasks WHERE id = ?")
+        .bind(req.id)
+        .fetch_optional(&state.db)
+        .await
+        .map_err(|e| format!("Failed to fetch project task: {}", e))?
+        .ok_or_else(|| format!("Project task {} not found", req.id))?;
+
     let mut updates = Vec::new();
     if req.title.is_some() {
         updates.push("title = ?");
     }
     if req.done.is_some() {
         updates.push("done = ?");
+        // Keep the original completion time when an already done task is re-saved
+        updates.push("completed_at = CASE WHEN ? THEN COALESCE(completed_at, ?) ELSE NULL END");
     }
     if req.order.is_some() {
         updates.push("\"order\" = ?");
     }
+    if due_date.is_some() {
+        updates.push("due_date = ?");
+    }
+    if start_date.is_some() {
+        updates.push("start_date = ?");
+    }
+    if req.priority.is_some() {
+        updates.push("priority = ?");
+    }
+    if req.estimated_minutes.is_some() {
+        updates.push("estimated_minutes = ?");
+    }
     updates.push("updated_at = ?");
 
     let query = format!(
@@ -208,31 +568,338 @@ pub async fn update_project_task(
     }
     if let Some(done) = &req.done {
         q = q.bind(if *done { 1 } else { 0 });
+        q = q.bind(if *done { 1 } else { 0 });
+        q = q.bind(Utc::now().to_rfc3339());
     }
     if let Some(order) = &req.order {
         q = q.bind(order);
     }
+    if let Some(due_date) = &due_date {
+        q = q.bind(due_date.clone());
+    }
+    if let Some(start_date) = &start_date {
+        q = q.bind(start_date.clone());
+    }
+    if let Some(priority) = req.priority {
+        q = q.bind(Some(priority).filter(|p| *p >= 0));
+    }
+    if let Some(minutes) = req.estimated_minutes {
+        q = q.bind(Some(minutes).filter(|m| *m >= 0));
+    }
     q = q.bind(Utc::now().to_rfc3339());
     q = q.bind(req.id);
 
-    q.execute(&state.db)
+    let mut tx = state
+        .db
+        .begin()
+        .await
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+
+    q.execute(&mut *tx)
         .await
         .map_err(|e| format!("Failed to update project task: {}", e))?;
 
+    let completed = req.done.filter(|d| *d != was_done);
+    if let Some(done) = completed {
+        sync_task_column(&mut tx, req.id, done).await?;
+    }
+
+    tx.commit()
+        .await
+        .map_err(|e| format!("Failed to commit project task: {}", e))?;
+
+    if completed == Some(true) {
+        after_task_completed(&app, &state.db, req.id).await;
+    }
+
+    Ok(())
+}
+
+/// Follow-ups once a task has just been marked done: surface the tasks it
+/// unblocked and create the next occurrence of a recurring task. The
+/// completion is already saved, so failures here are only logged.
+pub(crate) async fn after_task_completed(app: &AppHandle, db: &SqlitePool, task_id: i64) {
+    if let Err(e) = create_next_occurrence(db, task_id).await {
+        warn!("phase=task_next_occurrence_failed task={} error={}", task_id, e);
+    }
+    if let Err(e) = announce_unblocked(app, db, task_id).await {
+        warn!("phase=tasks_unblocked_failed task={} error={}", task_id, e);
+    }
+}
+
+/// A usable budget, or `None` for negative or non-finite input.
+fn normalize_budget(budget: f64) -> Option<f64> {
+    Some(budget).filter(|b| b.is_finite() && *b >= 0.0)
+}
+
+/// Upper-cased currency code, or `None` when blank.
+fn normalize_currency(code: &str) -> Option<String> {
+    Some(code.trim().to_uppercase()).filter(|c| !c.is_empty())
+}
+
+pub(crate) fn parse_task_date(raw: Option<&str>, label: &str) -> Result<Option<String>, String> {
+    match raw.map(str::trim).filter(|d| !d.is_empty()) {
+        None => Ok(None),
+        Some(date) => NaiveDate::parse_from_str(date, "%Y-%m-%d")
+            .map(|d| Some(d.format("%Y-%m-%d").to_string()))
+            .map_err(|_| format!("Invalid {} date: {}", label, date)),
+    }
+}
+
+fn validate_priority(priority: i64) -> Result<(), String> {
+    if (0..=3).contains(&priority) {
+        Ok(())
+    } else {
+        Err(format!("Priority must be between P0 and P3, got {}", priority))
+    }
+}
+
+/// Selects the computed `blocked` flag for a task aliased as `t`.
+pub(crate) const BLOCKED_COLUMN: &str = r#"
+    EXISTS (
+        SELECT 1 FROM project_task_dependencies d
+        JOIN project_tasks b ON b.id = d.blocked_by_task_id
+        WHERE d.task_id = t.id AND b.done = 0
+    ) AS blocked
+"#;
+
+/// Tasks joined with their project name, filtered by `condition`.
+pub(crate) fn scheduled_tasks_query(condition: &str) -> String {
+    format!(
+        "SELECT t.*, p.name AS project_name, {} \
+         FROM project_tasks t JOIN projects p ON p.id = t.project_id \
+         WHERE {}",
+        BLOCKED_COLUMN, condition
+    )
+}
+
+const OPEN_TASKS: &str = "t.done = 0 AND p.status != 'archived'";
+
+/// Open tasks whose due date has passed, oldest first.
+#[tauri::command]
+pub async fn list_overdue_project_tasks(
+    state: State<'_, crate::AppState>,
+) -> Result<Vec<ScheduledProjectTask>, String> {
+    let today = Utc::now().date_naive().format("%Y-%m-%d").to_string();
+    let query = scheduled_tasks_query(&format!(
+        "{} AND t.due_date < ? ORDER BY t.due_date ASC, COALESCE(t.priority, 4) ASC",
+        OPEN_TASKS
+    ));
+
+    sqlx::query_as::<_, ScheduledProjectTask>(&query)
+        .bind(today)
+        .fetch_all(&state.db)
+        .await
+        .map_err(|e| format!("Failed to fetch overdue tasks: {}", e))
+}
+
+/// Open tasks due from today through Sunday.
+#[tauri::command]
+pub async fn list_project_tasks_due_this_week(
+    state: State<'_, crate::AppState>,
+) -> Result<Vec<ScheduledProjectTask>, String> {
+    let today = Utc::now().date_naive();
+    let sunday = today + chrono::Duration::days(6 - today.weekday().num_days_from_monday() as i64);
+    let query = scheduled_tasks_query(&format!(
+        "{} AND t.due_date BETWEEN ? AND ? ORDER BY t.due_date ASC, COALESCE(t.priority, 4) ASC",
+        OPEN_TASKS
+    ));
+
+    sqlx::query_as::<_, ScheduledProjectTask>(&query)
+        .bind(today.format("%Y-%m-%d").to_string())
+        .bind(sunday.format("%Y-%m-%d").to_string())
+        .fetch_all(&state.db)
+        .await
+        .map_err(|e| format!("Failed to fetch tasks due this week: {}", e))
+}
+
+/// Open tasks ordered by priority, optionally limited to one priority.
+/// Unprioritized tasks come last.
+#[tauri::command]
+pub async fn list_project_tasks_by_priority(
+    state: State<'_, crate::AppState>,
+    priority: Option<i64>,
+) -> Result<Vec<ScheduledProjectTask>, String> {
+    if let Some(priority) = priority {
+        validate_priority(priority)?;
+    }
+    let query = scheduled_tasks_query(&format!(
+        "{} AND (? IS NULL OR t.priority = ?) \
+         ORDER BY COALESCE(t.priority, 4) ASC, t.due_date IS NULL, t.due_date ASC",
+        OPEN_TASKS
+    ));
+
+    sqlx::query_as::<_, ScheduledProjectTask>(&query)
+        .bind(priority)
+        .bind(priority)
+        .fetch_all(&state.db)
+        .await
+        .map_err(|e| format!("Failed to fetch tasks by priority: {}", e))
+}
+
+/// Moves a task under another task of the same project, or to the top level
+/// when `parent_task_id` is `None`.
+#[tauri::command]
+pub async fn move_project_task(
+    state: State<'_, crate::AppState>,
+    id: i64,
+    parent_task_id: Option<i64>,
+    order: Option<i64>,
+) -> Result<(), String> {
+    let project_id = sqlx::query_scalar::<_, i64>("SELECT project_id FROM project_tasks WHERE id = ?")
+        .bind(id)
+        .fetch_optional(&state.db)
+        .await
+        .map_err(|e| format!("Failed to fetch project task: {}", e))?
+        .ok_or_else(|| format!("Project task {} not found", id))?;
+    if let Some(parent_id) = parent_task_id {
+        validate_parent(&state.db, project_id, parent_id, Some(id)).await?;
+    }
+
+    sqlx::query(
+        r#"
+        UPDATE project_tasks SET parent_task_id = ?, "order" = COALESCE(?, "order"), updated_at = ?
+        WHERE id = ?
+        "#,
+    )
+    .bind(parent_task_id)
+    .bind(order)
+    .bind(Utc::now().to_rfc3339())
+    .bind(id)
+    .execute(&state.db)
+    .await
+    .map_err(|e| format!("Failed to move project task: {}", e))?;
+
     Ok(())
 }
 
+/// Deletes a task. Its subtasks are deleted with it when `cascade` is set,
+/// otherwise they move up to the deleted task's parent.
 #[tauri::command]
 pub async fn delete_project_task(
     state: State<'_, crate::AppState>,
     id: i64,
+    cascade: Option<bool>,
 ) -> Result<(), String> {
+    let mut tx = state
+        .db
+        .begin()
+        .await
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+
+    if !cascade.unwrap_or(false) {
+        sqlx::query(
+            r#"
+            UPDATE project_tasks
+            SET parent_task_id = (SELECT parent_task_id FROM project_tasks WHERE id = ?), updated_at = ?
+            WHERE parent_task_id = ?
+            "#,
+        )
+        .bind(id)
+        .bind(Utc::now().to_rfc3339())
+        .bind(id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to re-parent subtasks: {}", e))?;
+    }
+
     sqlx::query("DELETE FROM project_tasks WHERE id = ?")
         .bind(id)
-        .execute(&state.db)
+        .execute(&mut *tx)
         .await
         .map_err(|e| format!("Failed to delete project task: {}", e))?;
 
+    tx.commit()
+        .await
+        .map_err(|e| format!("Failed to commit task deletion: {}", e))?;
+
     Ok(())
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn task(id: i64, parent_task_id: Option<i64>, done: bool) -> ProjectTask {
+        let now = Utc::now();
+        ProjectTask {
+            id,
+            project_id: 1,
+            title: format!("task {}", id),
+            done,
+            order: id,
+            parent_task_id,
+            due_date: None,
+            start_date: None,
+            priority: None,
+            estimated_minutes: None,
+            completed_at: None,
+            milestone_id: None,
+            recurrence: None,
+            next_occurrence_id: None,
+            column_id: None,
+            column_order: 0,
+            blocked: false,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    #[test]
+    fn completion_rolls_up_from_leaves() {
+        // 1 ─┬─ 2 (done)
+        //    └─ 3 ─┬─ 4 (done)
+        //          └─ 5
+        // 6 (done, no children)
+        let tree = build_task_tree(vec![
+            task(1, None, false),
+            task(2, Some(1), true),
+            task(3, Some(1), false),
+            task(4, Some(3), true),
+            task(5, Some(3), false),
+            task(6, None, true),
+        ]);
+
+        assert_eq!(tree.len(), 2);
+        let root = &tree[0];
+        assert_eq!((root.total_leaves, root.done_leaves), (3, 2));
+        assert!(!root.rolled_up_done);
+        assert!((root.completion - 200.0 / 3.0).abs() < 1e-9);
+        let middle = &root.children[1];
+        assert_eq!((middle.total_leaves, middle.done_leaves, middle.completion), (2, 1, 50.0));
+        let leaf = &tree[1];
+        assert_eq!((leaf.total_leaves, leaf.done_leaves, leaf.completion), (1, 1, 100.0));
+        assert!(leaf.rolled_up_done);
+    }
+
+    #[test]
+    fn parent_done_flag_does_not_count_when_it_has_children() {
+        let tree = build_task_tree(vec![task(1, None, true), task(2, Some(1), false), task(3, Some(1), true)]);
+
+        assert_eq!((tree[0].total_leaves, tree[0].done_leaves), (2, 1));
+        assert!(!tree[0].rolled_up_done);
+
+        let tree = build_task_tree(vec![task(1, None, false), task(2, Some(1), true)]);
+        assert!(tree[0].rolled_up_done);
+        assert!(!tree[0].task.done);
+    }
+
+    #[tokio::test]
+    async fn migrations_repair_unknown_statuses() {
+        let db = crate::test_support::memory_db().await;
+        let now = Utc::now();
+        sqlx::query("INSERT INTO projects (name, status, created_at, updated_at) VALUES ('Legacy', 'on hold', ?, ?)")
+            .bind(now)
+            .bind(now)
+            .execute(&db)
+            .await
+            .unwrap();
+
+        crate::database::migrate(&db).await.unwrap();
+
+        let projects = sqlx::query_as::<_, Project>("SELECT * FROM projects").fetch_all(&db).await.unwrap();
+        assert_eq!(projects.len(), 1);
+        assert_eq!(projects[0].status, ProjectStatus::Active);
+    }
+}
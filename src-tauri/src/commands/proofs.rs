@@ -0,0 +1,393 @@
+use log::{info, warn};
+use tauri::State;
+use sqlx::SqliteExecutor;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use crate::commands::transactions::is_tx_hash;
+use crate::models::CompletionProof;
+
+/// Screenshots are copied under `<app data dir>/proofs/<completion id>/`.
+const PROOFS_DIR: &str = "proofs";
+const SCREENSHOT_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg", "gif", "webp", "bmp"];
+
+#[derive(Debug, Deserialize)]
+pub struct AttachCompletionProofRequest {
+    pub completion_id: i64,
+    /// `tx_hash`, `url` or `screenshot`
+    pub kind: String,
+    /// The hash, the URL, or the path of the screenshot to copy in
+    pub value: String,
+    /// Chain used to validate a tx hash; defaults to the airdrop's chain
+    pub chain: Option<String>,
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CompletionProofView {
+    #[serde(flatten)]
+    pub proof: CompletionProof,
+    /// Absolute path of a screenshot, for thumbnails
+    pub file_path: Option<String>,
+}
+
+/// Which proofs to collect screenshot files for before their rows are deleted.
+pub(crate) enum ProofScope {
+    Proof(i64),
+    Completion(i64),
+    Task(i64),
+    Airdrop(i64),
+}
+
+#[tauri::command]
+pub async fn attach_completion_proof(
+    state: State<'_, crate::AppState>,
+    req: AttachCompletionProofRequest,
+) -> Result<i64, String> {
+    let airdrop_chain = sqlx::query_scalar::<_, Option<String>>(
+        r#"
+        SELECT a.chain FROM task_completions tc
+        JOIN airdrops a ON a.id = tc.airdrop_id
+        WHERE tc.id = ?
+        "#,
+    )
+    .bind(req.completion_id)
+    .fetch_optional(&state.db)
+    .await
+    .map_err(|e| format!("Failed to fetch completion: {}", e))?
+    .ok_or_else(|| format!("Completion {} not found", req.completion_id))?;
+
+    let chain = req.chain.clone().or(airdrop_chain);
+    let kind = req.kind.trim().to_lowercase();
+    let value = match kind.as_str() {
+        "tx_hash" => validate_tx_hash(chain.as_deref(), &req.value)?,
+        "url" => {
+            let url = url::Url::parse(req.value.trim()).map_err(|e| format!("Invalid URL: {}", e))?;
+            if !matches!(url.scheme(), "http" | "https") {
+                return Err("Proof URLs must be http or https".to_string());
+            }
+            url.to_string()
+        }
+        "screenshot" => copy_screenshot(&state.data_dir, req.completion_id, Path::new(req.value.trim()))?,
+        other => return Err(format!("Unsupported proof kind: {}", other)),
+    };
+
+    let id = sqlx::query(
+        r#"
+        INSERT INTO completion_proofs (completion_id, kind, value, chain, note, created_at)
+        VALUES (?, ?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(req.completion_id)
+    .bind(&kind)
+    .bind(&value)
+    .bind(&chain)
+    .bind(&req.note)
+    .bind(Utc::now().to_rfc3339())
+    .execute(&state.db)
+    .await
+    .map_err(|e| format!("Failed to attach proof: {}", e))?
+    .last_insert_rowid();
+
+    info!(
+        "phase=completion_proof_attached completion_id={} kind={}",
+        req.completion_id, kind
+    );
+    Ok(id)
+}
+
+#[tauri::command]
+pub async fn list_completion_proofs(
+    state: State<'_, crate::AppState>,
+    completion_id: i64,
+) -> Result<Vec<CompletionProofView>, String> {
+    let proofs = sqlx::query_as::<_, CompletionProof>(
+        "SELECT * FROM completion_proofs WHERE completion_id = ? ORDER BY created_at ASC",
+    )
+    .bind(completion_id)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|e| format!("Failed to fetch proofs: {}", e))?;
+
+    Ok(proofs
+        .into_iter()
+        .map(|proof| {
+            let file_path = (proof.kind == "screenshot")
+                .then(|| state.data_dir.join(&proof.value).to_string_lossy().to_string());
+            CompletionProofView { proof, file_path }
+        })
+        .collect())
+}
+
+#[tauri::command]
+pub async fn delete_completion_proof(state: State<'_, crate::AppState>, id: i64) -> Result<(), String> {
+    let mut tx = state
+        .db
+        .begin()
+        .await
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+    let files = proof_files(&mut *tx, ProofScope::Proof(id)).await?;
+
+    sqlx::query("DELETE FROM completion_proofs WHERE id = ?")
+        .bind(id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to delete proof: {}", e))?;
+
+    tx.commit()
+        .await
+        .map_err(|e| format!("Failed to commit proof deletion: {}", e))?;
+    remove_proof_files(&state.data_dir, &files);
+
+    Ok(())
+}
+
+/// Screenshot paths of every proof in `scope`. Read them in the transaction
+/// that deletes the rows (usually through `ON DELETE CASCADE`), and pass them
+/// to `remove_proof_files` once it has committed.
+pub(crate) async fn proof_files(db: impl SqliteExecutor<'_>, scope: ProofScope) -> Result<Vec<String>, String> {
+    let (query, id) = match scope {
+        ProofScope::Proof(id) => (
+            "SELECT value FROM completion_proofs WHERE kind = 'screenshot' AND id = ?",
+            id,
+        ),
+        ProofScope::Completion(id) => (
+            "SELECT value FROM completion_proofs WHERE kind = 'screenshot' AND completion_id = ?",
+            id,
+        ),
+        ProofScope::Task(id) => (
+            r#"
+            SELECT cp.value FROM completion_proofs cp
+            JOIN task_completions tc ON tc.id = cp.completion_id
+            WHERE cp.kind = 'screenshot' AND tc.task_id = ?
+            "#,
+            id,
+        ),
+        ProofScope::Airdrop(id) => (
+            r#"
+            SELECT cp.value FROM completion_proofs cp
+            JOIN task_completions tc ON tc.id = cp.completion_id
+            WHERE cp.kind = 'screenshot' AND tc.airdrop_id = ?
+            "#,
+            id,
+        ),
+    };
+
+    sqlx::query_scalar::<_, String>(query)
+        .bind(id)
+        .fetch_all(db)
+        .await
+        .map_err(|e| format!("Failed to fetch proof files: {}", e))
+}
+
+/// Deletes screenshot files whose rows are already gone. A file that cannot
+/// be removed is only logged; the proof itself no longer exists.
+pub(crate) fn remove_proof_files(data_dir: &Path, paths: &[String]) {
+    let proofs_root = data_dir.join(PROOFS_DIR);
+    for relative in paths {
+        let path = data_dir.join(relative);
+        // Never touch anything outside the proofs directory.
+        if !path.starts_with(&proofs_root) || relative.contains("..") {
+            warn!("phase=proof_cleanup_skipped path={}", relative);
+            continue;
+        }
+        if let Err(e) = std::fs::remove_file(&path) {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                warn!("phase=proof_cleanup_failed path={} error={}", relative, e);
+            }
+        }
+        if let Some(dir) = path.parent() {
+            // Only succeeds once the directory is empty.
+            let _ = std::fs::remove_dir(dir);
+        }
+    }
+}
+
+fn copy_screenshot(data_dir: &Path, completion_id: i64, source: &Path) -> Result<String, String> {
+    let extension = source
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_lowercase())
+        .filter(|e| SCREENSHOT_EXTENSIONS.contains(&e.as_str()))
+        .ok_or_else(|| format!("Unsupported screenshot file: {}", source.display()))?;
+    if !source.is_file() {
+        return Err(format!("Screenshot not found: {}", source.display()));
+    }
+
+    let relative: PathBuf = [
+        PROOFS_DIR.to_string(),
+        completion_id.to_string(),
+        format!("{}.{}", uuid::Uuid::new_v4(), extension),
+    ]
+    .iter()
+    .collect();
+    let target = data_dir.join(&relative);
+
+    if let Some(parent) = target.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create proofs directory: {}", e))?;
+    }
+    std::fs::copy(source, &target).map_err(|e| format!("Failed to copy screenshot: {}", e))?;
+
+    Ok(relative.to_string_lossy().to_string())
+}
+
+/// Validates a transaction hash against the format used by `chain` and
+/// returns it normalized. Unknown chains are treated as EVM.
+pub(crate) fn validate_tx_hash(chain: Option<&str>, hash: &str) -> Result<String, String> {
+    let hash = hash.trim();
+    let family = chain.map(|c| c.trim().to_lowercase()).unwrap_or_default();
+
+    let valid = match family.as_str() {
+        "solana" | "sol" => is_base58(hash) && (64..=88).contains(&hash.len()),
+        "sui" => is_base58(hash) && (43..=44).contains(&hash.len()),
+        "bitcoin" | "btc" => hash.len() == 64 && hash.chars().all(|c| c.is_ascii_hexdigit()),
+        "cosmos" | "atom" | "osmosis" | "celestia" | "tia" | "injective" => {
+            hash.len() == 64 && hash.chars().all(|c| c.is_ascii_hexdigit())
+        }
+        "starknet" => {
+            hash.starts_with("0x")
+                && (3..=66).contains(&hash.len())
+                && hash[2..].chars().all(|c| c.is_ascii_hexdigit())
+        }
+        _ => is_tx_hash(&hash.to_lowercase()),
+    };
+
+    if !valid {
+        return Err(format!(
+            "Invalid transaction hash for {}: {}",
+            chain.unwrap_or("EVM"),
+            hash
+        ));
+    }
+
+    // Base58 hashes are case-sensitive; hex ones are normalized to lowercase.
+    if matches!(family.as_str(), "solana" | "sol" | "sui") {
+        Ok(hash.to_string())
+    } else {
+        Ok(hash.to_lowercase())
+    }
+}
+
+fn is_base58(value: &str) -> bool {
+    !value.is_empty()
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() && !matches!(c, '0' | 'O' | 'I' | 'l'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::memory_db;
+
+    fn scratch_dir() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("proofs-test-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn tx_hashes_are_checked_per_chain() {
+        let evm = format!("0x{}", "AB".repeat(32));
+        assert_eq!(validate_tx_hash(None, &format!(" {} ", evm)).unwrap(), evm.to_lowercase());
+        assert_eq!(validate_tx_hash(Some("arbitrum"), &evm).unwrap(), evm.to_lowercase());
+        assert!(validate_tx_hash(None, &evm[..40]).is_err());
+
+        let solana = "5VERv8NMvzbJMEkV8xnrLkEaWRtSz9CosKDYjCJjBRnbJLgp8uirBgmQpjKhoR4tjF3ZpRzrFmBV6UjKdiSZkQUW";
+        assert_eq!(validate_tx_hash(Some("Solana"), solana).unwrap(), solana);
+        // 0, O, I and l are not base58
+        assert!(validate_tx_hash(Some("solana"), &solana.replace('5', "0")).is_err());
+        assert!(validate_tx_hash(Some("solana"), &evm).is_err());
+
+        let btc = "A1".repeat(32);
+        assert_eq!(validate_tx_hash(Some("btc"), &btc).unwrap(), btc.to_lowercase());
+        assert!(validate_tx_hash(Some("bitcoin"), &format!("0x{}", btc)).is_err());
+        assert!(validate_tx_hash(Some("starknet"), "0x4a5").is_ok());
+        assert!(validate_tx_hash(Some("starknet"), "4a5").is_err());
+    }
+
+    #[test]
+    fn screenshots_are_copied_in_and_cleaned_up() {
+        let root = scratch_dir();
+        let data_dir = root.join("data");
+        let source = root.join("Shot.PNG");
+        std::fs::write(&source, b"png").unwrap();
+        let outside = root.join("keep.png");
+        std::fs::write(&outside, b"png").unwrap();
+
+        let relative = copy_screenshot(&data_dir, 7, &source).unwrap();
+        assert!(relative.starts_with(&format!("{}{}7", PROOFS_DIR, std::path::MAIN_SEPARATOR)));
+        assert!(relative.ends_with(".png"));
+        assert_eq!(std::fs::read(data_dir.join(&relative)).unwrap(), b"png");
+
+        assert!(copy_screenshot(&data_dir, 7, &root.join("notes.txt")).is_err());
+        assert!(copy_screenshot(&data_dir, 7, &root.join("missing.png")).is_err());
+
+        remove_proof_files(&data_dir, &[relative.clone(), "../keep.png".to_string()]);
+        assert!(!data_dir.join(&relative).exists());
+        // The emptied completion directory goes too; files outside the proofs directory stay
+        assert!(!data_dir.join(PROOFS_DIR).join("7").exists());
+        assert!(outside.exists());
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[tokio::test]
+    async fn proof_files_are_read_before_the_rows_cascade_away() {
+        let db = memory_db().await;
+        let now = Utc::now().to_rfc3339();
+        let airdrop_id = sqlx::query("INSERT INTO airdrops (name, url, created_at, updated_at) VALUES ('Drop', 'https://drop.xyz', ?, ?)")
+            .bind(&now)
+            .bind(&now)
+            .execute(&db)
+            .await
+            .unwrap()
+            .last_insert_rowid();
+        let task_id = sqlx::query("INSERT INTO airdrop_daily_tasks (airdrop_id, title, created_at, updated_at) VALUES (?, 'Swap', ?, ?)")
+            .bind(airdrop_id)
+            .bind(&now)
+            .bind(&now)
+            .execute(&db)
+            .await
+            .unwrap()
+            .last_insert_rowid();
+        let completion_id = sqlx::query(
+            "INSERT INTO task_completions (task_id, airdrop_id, completed_on, completed_at) VALUES (?, ?, '2024-05-01', ?)",
+        )
+        .bind(task_id)
+        .bind(airdrop_id)
+        .bind(&now)
+        .execute(&db)
+        .await
+        .unwrap()
+        .last_insert_rowid();
+        for (kind, value) in [("screenshot", "proofs/1/a.png"), ("url", "https://x.com/post/1")] {
+            sqlx::query("INSERT INTO completion_proofs (completion_id, kind, value, created_at) VALUES (?, ?, ?, ?)")
+                .bind(completion_id)
+                .bind(kind)
+                .bind(value)
+                .bind(&now)
+                .execute(&db)
+                .await
+                .unwrap();
+        }
+
+        let mut tx = db.begin().await.unwrap();
+        let files = proof_files(&mut *tx, ProofScope::Airdrop(airdrop_id)).await.unwrap();
+        sqlx::query("DELETE FROM airdrops WHERE id = ?")
+            .bind(airdrop_id)
+            .execute(&mut *tx)
+            .await
+            .unwrap();
+        tx.commit().await.unwrap();
+
+        assert_eq!(files, vec!["proofs/1/a.png"]);
+        let left: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM completion_proofs")
+            .fetch_one(&db)
+            .await
+            .unwrap();
+        assert_eq!(left, 0);
+    }
+}
@@ -0,0 +1,363 @@
+use log::info;
+use tauri::State;
+use sqlx::SqlitePool;
+use chrono::{Datelike, Duration, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::commands::project_columns::file_task;
+use crate::models::ProjectTask;
+
+/// When the next occurrence of a recurring task is due.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum RecurrenceRule {
+    Daily,
+    /// ISO weekdays, 1 = Monday through 7 = Sunday
+    Weekly { weekdays: Vec<u32> },
+    /// Day of the month, clamped to the month's last day
+    Monthly { day: u32 },
+    /// N days after the previous occurrence was completed
+    AfterCompletion { days: i64 },
+}
+
+impl RecurrenceRule {
+    fn validate(&self) -> Result<(), String> {
+        match self {
+            RecurrenceRule::Daily => Ok(()),
+            RecurrenceRule::Weekly { weekdays } => {
+                if weekdays.is_empty() || weekdays.iter().any(|d| !(1..=7).contains(d)) {
+                    Err("Weekly recurrence needs weekdays between 1 (Monday) and 7 (Sunday)".to_string())
+                } else {
+                    Ok(())
+                }
+            }
+            RecurrenceRule::Monthly { day } => {
+                if (1..=31).contains(day) {
+                    Ok(())
+                } else {
+                    Err(format!("Invalid day of month: {}", day))
+                }
+            }
+            RecurrenceRule::AfterCompletion { days } => {
+                if *days >= 1 {
+                    Ok(())
+                } else {
+                    Err("Recurrence interval must be at least one day".to_string())
+                }
+            }
+        }
+    }
+
+    /// First matching date strictly after `from`.
+    fn step(&self, from: NaiveDate) -> NaiveDate {
+        match self {
+            RecurrenceRule::Daily => from + Duration::days(1),
+            RecurrenceRule::Weekly { weekdays } => (1..=7)
+                .map(|offset| from + Duration::days(offset))
+                .find(|d| weekdays.contains(&d.weekday().number_from_monday()))
+                .unwrap_or(from + Duration::days(7)),
+            RecurrenceRule::Monthly { day } => {
+                let this_month = day_in_month(from.year(), from.month(), *day);
+                if this_month > from {
+                    this_month
+                } else if from.month() == 12 {
+                    day_in_month(from.year() + 1, 1, *day)
+                } else {
+                    day_in_month(from.year(), from.month() + 1, *day)
+                }
+            }
+            RecurrenceRule::AfterCompletion { days } => from + Duration::days(*days),
+        }
+    }
+
+    /// Due date of the occurrence after one due on `due` and completed on
+    /// `completed`. Calendar rules skip dates already in the past so a late
+    /// completion does not spawn an overdue task.
+    fn next_due(&self, due: Option<NaiveDate>, completed: NaiveDate, today: NaiveDate) -> NaiveDate {
+        if let RecurrenceRule::AfterCompletion { .. } = self {
+            return self.step(completed);
+        }
+        let mut next = self.step(due.unwrap_or(completed));
+        while next < today {
+            next = self.step(next);
+        }
+        next
+    }
+}
+
+fn day_in_month(year: i32, month: u32, day: u32) -> NaiveDate {
+    (1..=day)
+        .rev()
+        .find_map(|d| NaiveDate::from_ymd_opt(year, month, d))
+        .expect("every month has a first day")
+}
+
+fn parse_date(raw: Option<&str>) -> Option<NaiveDate> {
+    raw.and_then(|d| NaiveDate::parse_from_str(d, "%Y-%m-%d").ok())
+}
+
+/// Sets or clears (`rule` omitted) the recurrence of a task.
+#[tauri::command]
+pub async fn set_task_recurrence(
+    state: State<'_, crate::AppState>,
+    task_id: i64,
+    rule: Option<RecurrenceRule>,
+) -> Result<(), String> {
+    if let Some(rule) = &rule {
+        rule.validate()?;
+    }
+    let value = rule
+        .map(|r| serde_json::to_string(&r))
+        .transpose()
+        .map_err(|e| format!("Invalid recurrence: {}", e))?;
+
+    sqlx::query("UPDATE project_tasks SET recurrence = ?, updated_at = ? WHERE id = ?")
+        .bind(value)
+        .bind(Utc::now().to_rfc3339())
+        .bind(task_id)
+        .execute(&state.db)
+        .await
+        .map_err(|e| format!("Failed to set task recurrence: {}", e))?;
+
+    Ok(())
+}
+
+/// Creates the next occurrence of a recurring task that was just completed,
+/// copying its title, parent, priority, estimate, milestone and rule, after
+/// its siblings. Does nothing for one-off tasks or when the next occurrence
+/// already exists.
+pub(crate) async fn create_next_occurrence(db: &SqlitePool, task_id: i64) -> Result<Option<i64>, String> {
+    let task = sqlx::query_as::<_, ProjectTask>("SELECT * FROM project_tasks WHERE id = ?")
+        .bind(task_id)
+        .fetch_optional(db)
+        .await
+        .map_err(|e| format!("Failed to fetch project task: {}", e))?
+        .ok_or_else(|| format!("Project task {} not found", task_id))?;
+    if task.next_occurrence_id.is_some() {
+        return Ok(None);
+    }
+    let Some(rule) = task
+        .recurrence
+        .clone()
+        .and_then(|r| serde_json::from_value::<RecurrenceRule>(r).ok())
+    else {
+        return Ok(None);
+    };
+
+    let today = Utc::now().date_naive();
+    let completed = task.completed_at.map(|c| c.date_naive()).unwrap_or(today);
+    let due = parse_date(task.due_date.as_deref());
+    let next_due = rule.next_due(due, completed, today);
+    // Keep the lead time between start and due date
+    let next_start = parse_date(task.start_date.as_deref())
+        .map(|start| next_due - (due.unwrap_or(completed) - start));
+
+    let mut tx = db
+        .begin()
+        .await
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+
+    let now = Utc::now();
+    let next_id = sqlx::query(
+        r#"
+        INSERT INTO project_tasks (
+            project_id, title, done, "order", parent_task_id, due_date, start_date, priority,
+            estimated_minutes, milestone_id, recurrence, created_at, updated_at
+        )
+        SELECT ?, ?, 0, COALESCE(MAX("order"), 0) + 1, ?, ?, ?, ?, ?, ?, ?, ?, ?
+        FROM project_tasks WHERE project_id = ? AND parent_task_id IS ?
+        "#,
+    )
+    .bind(task.project_id)
+    .bind(&task.title)
+    .bind(task.parent_task_id)
+    .bind(next_due.format("%Y-%m-%d").to_string())
+    .bind(next_start.map(|d| d.format("%Y-%m-%d").to_string()))
+    .bind(task.priority)
+    .bind(task.estimated_minutes)
+    .bind(task.milestone_id)
+    .bind(serde_json::to_string(&rule).map_err(|e| format!("Invalid recurrence: {}", e))?)
+    .bind(now.to_rfc3339())
+    .bind(now.to_rfc3339())
+    .bind(task.project_id)
+    .bind(task.parent_task_id)
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| format!("Failed to create next occurrence: {}", e))?
+    .last_insert_rowid();
+
+    sqlx::query("UPDATE project_tasks SET next_occurrence_id = ? WHERE id = ?")
+        .bind(next_id)
+        .bind(task_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to link next occurrence: {}", e))?;
+
+    file_task(&mut tx, next_id).await?;
+
+    tx.commit()
+        .await
+        .map_err(|e| format!("Failed to commit next occurrence: {}", e))?;
+
+    info!("phase=task_recur_ok task={} next={} due={}", task_id, next_id, next_due);
+    Ok(Some(next_id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::project_columns::ensure_project_columns;
+    use crate::test_support::memory_db;
+
+    fn date(raw: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(raw, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn day_in_month_clamps_to_the_last_day() {
+        assert_eq!(day_in_month(2023, 2, 31), date("2023-02-28"));
+        assert_eq!(day_in_month(2024, 2, 31), date("2024-02-29"));
+        assert_eq!(day_in_month(2024, 4, 31), date("2024-04-30"));
+        assert_eq!(day_in_month(2024, 1, 15), date("2024-01-15"));
+    }
+
+    #[test]
+    fn monthly_on_the_31st_lands_on_february_end_then_recovers() {
+        let rule = RecurrenceRule::Monthly { day: 31 };
+
+        let february = rule.step(date("2024-01-31"));
+        assert_eq!(february, date("2024-02-29"));
+        assert_eq!(rule.step(february), date("2024-03-31"));
+        assert_eq!(rule.step(date("2023-01-31")), date("2023-02-28"));
+    }
+
+    #[test]
+    fn monthly_rolls_over_into_the_next_year() {
+        let rule = RecurrenceRule::Monthly { day: 5 };
+
+        assert_eq!(rule.step(date("2024-12-05")), date("2025-01-05"));
+        assert_eq!(rule.step(date("2024-12-20")), date("2025-01-05"));
+        assert_eq!(rule.step(date("2024-12-01")), date("2024-12-05"));
+    }
+
+    #[test]
+    fn weekly_wraps_around_to_next_week() {
+        // 2024-05-03 is a Friday
+        let rule = RecurrenceRule::Weekly { weekdays: vec![1, 3] };
+        assert_eq!(rule.step(date("2024-05-03")), date("2024-05-06"));
+        assert_eq!(rule.step(date("2024-05-06")), date("2024-05-08"));
+
+        let fridays = RecurrenceRule::Weekly { weekdays: vec![5] };
+        assert_eq!(fridays.step(date("2024-05-03")), date("2024-05-10"));
+        // Sunday to Monday crosses the ISO week boundary
+        let mondays = RecurrenceRule::Weekly { weekdays: vec![1] };
+        assert_eq!(mondays.step(date("2024-05-05")), date("2024-05-06"));
+    }
+
+    #[test]
+    fn late_completion_skips_dates_already_past() {
+        let today = date("2024-05-20");
+
+        let daily = RecurrenceRule::Daily;
+        assert_eq!(daily.next_due(Some(date("2024-05-01")), today, today), today);
+
+        let monthly = RecurrenceRule::Monthly { day: 10 };
+        assert_eq!(monthly.next_due(Some(date("2024-03-10")), today, today), date("2024-06-10"));
+
+        let weekly = RecurrenceRule::Weekly { weekdays: vec![2] };
+        assert_eq!(weekly.next_due(Some(date("2024-04-02")), today, today), date("2024-05-21"));
+    }
+
+    #[test]
+    fn on_time_completion_keeps_the_schedule() {
+        let today = date("2024-05-09");
+        let monthly = RecurrenceRule::Monthly { day: 10 };
+
+        assert_eq!(monthly.next_due(Some(date("2024-05-10")), today, today), date("2024-06-10"));
+        // Without a due date the schedule starts from the completion day
+        assert_eq!(monthly.next_due(None, today, today), date("2024-05-10"));
+    }
+
+    #[test]
+    fn after_completion_counts_from_the_completion_day() {
+        let rule = RecurrenceRule::AfterCompletion { days: 3 };
+        let completed = date("2024-05-20");
+
+        assert_eq!(rule.next_due(Some(date("2024-05-01")), completed, completed), date("2024-05-23"));
+    }
+
+    #[test]
+    fn invalid_rules_are_rejected() {
+        assert!(RecurrenceRule::Weekly { weekdays: vec![] }.validate().is_err());
+        assert!(RecurrenceRule::Weekly { weekdays: vec![0, 8] }.validate().is_err());
+        assert!(RecurrenceRule::Monthly { day: 32 }.validate().is_err());
+        assert!(RecurrenceRule::AfterCompletion { days: 0 }.validate().is_err());
+        assert!(RecurrenceRule::Monthly { day: 31 }.validate().is_ok());
+    }
+
+    async fn add_task(db: &SqlitePool, project_id: i64, parent: Option<i64>, order: i64) -> i64 {
+        let now = Utc::now().to_rfc3339();
+        sqlx::query("INSERT INTO project_tasks (project_id, title, done, \"order\", parent_task_id, created_at, updated_at) VALUES (?, 'task', 0, ?, ?, ?, ?)")
+            .bind(project_id)
+            .bind(order)
+            .bind(parent)
+            .bind(&now)
+            .bind(&now)
+            .execute(db)
+            .await
+            .unwrap()
+            .last_insert_rowid()
+    }
+
+    #[tokio::test]
+    async fn completing_a_recurring_task_with_todo_full_still_creates_the_next_one() {
+        let db = memory_db().await;
+        let now = Utc::now().to_rfc3339();
+        let project_id = sqlx::query("INSERT INTO projects (name, status, created_at, updated_at) VALUES ('Chores', 'active', ?, ?)")
+            .bind(&now)
+            .bind(&now)
+            .execute(&db)
+            .await
+            .unwrap()
+            .last_insert_rowid();
+        let parent = add_task(&db, project_id, None, 0).await;
+        add_task(&db, project_id, None, 9).await;
+        add_task(&db, project_id, Some(parent), 0).await;
+        let recurring = add_task(&db, project_id, Some(parent), 1).await;
+        sqlx::query("UPDATE project_tasks SET done = 1, completed_at = ?, recurrence = ? WHERE id = ?")
+            .bind(&now)
+            .bind(serde_json::to_string(&RecurrenceRule::Daily).unwrap())
+            .bind(recurring)
+            .execute(&db)
+            .await
+            .unwrap();
+        ensure_project_columns(&db, project_id).await.unwrap();
+        // Todo holds the three open tasks
+        sqlx::query("UPDATE project_columns SET wip_limit = 3 WHERE project_id = ? AND name = 'Todo'")
+            .bind(project_id)
+            .execute(&db)
+            .await
+            .unwrap();
+
+        let next = create_next_occurrence(&db, recurring).await.unwrap().expect("next occurrence");
+
+        let (column_id, order, parent_task_id) = sqlx::query_as::<_, (Option<i64>, i64, Option<i64>)>(
+            "SELECT column_id, \"order\", parent_task_id FROM project_tasks WHERE id = ?",
+        )
+        .bind(next)
+        .fetch_one(&db)
+        .await
+        .unwrap();
+        assert_eq!(column_id, None);
+        assert_eq!(parent_task_id, Some(parent));
+        // After its siblings, not after every task of the project
+        assert_eq!(order, 2);
+
+        let linked: Option<i64> = sqlx::query_scalar("SELECT next_occurrence_id FROM project_tasks WHERE id = ?")
+            .bind(recurring)
+            .fetch_one(&db)
+            .await
+            .unwrap();
+        assert_eq!(linked, Some(next));
+    }
+}
@@ -0,0 +1,342 @@
+use log::info;
+use tauri::State;
+use sqlx::SqlitePool;
+use chrono::{NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::commands::prices::{base_currency, PriceBook};
+use crate::commands::settings::{read_setting, write_setting};
+use crate::models::{Airdrop, AirdropExpense};
+
+pub(crate) const SCORING_WEIGHTS_KEY: &str = "scoring_weights";
+
+/// Tunable inputs of the opportunity score, stored under `scoring_weights`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScoringWeights {
+    /// Effort assumed for one daily task
+    pub minutes_per_task: f64,
+    /// Used when an airdrop has no confidence estimate
+    pub default_confidence: f64,
+    /// Exponent on confidence; above 1 punishes long shots harder
+    pub confidence_weight: f64,
+    /// Share of the cost to date counted against the expected value
+    pub cost_weight: f64,
+    /// Days of effort assumed when no snapshot date is known
+    pub default_horizon_days: i64,
+    /// Value per minute below which an airdrop is marked for dropping
+    pub drop_below_per_minute: f64,
+    /// Value per minute below which an airdrop is marked for review
+    pub review_below_per_minute: f64,
+}
+
+impl Default for ScoringWeights {
+    fn default() -> Self {
+        Self {
+            minutes_per_task: 5.0,
+            default_confidence: 0.5,
+            confidence_weight: 1.0,
+            cost_weight: 1.0,
+            default_horizon_days: 30,
+            drop_below_per_minute: 0.1,
+            review_below_per_minute: 0.5,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AirdropRecommendation {
+    Keep,
+    Review,
+    Drop,
+    /// No expected value yet, so nothing to rank on
+    NeedsEstimate,
+    /// No daily tasks, so there's no effort to weigh the value against
+    NoTasks,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AirdropScore {
+    pub airdrop_id: i64,
+    pub airdrop_name: String,
+    pub expected_value: Option<f64>,
+    pub confidence: f64,
+    /// Expenses converted into `currency`
+    pub cost_to_date: f64,
+    /// Expenses with no price to convert them; left out of `cost_to_date`
+    pub unpriced_expenses: i64,
+    /// Base currency of the value and cost figures
+    pub currency: String,
+    pub daily_tasks: i64,
+    pub effort_minutes_per_day: f64,
+    pub days_to_snapshot: Option<i64>,
+    pub remaining_effort_minutes: f64,
+    /// Confidence-weighted value minus weighted cost
+    pub expected_net: Option<f64>,
+    pub roi_per_minute: Option<f64>,
+    pub recommendation: AirdropRecommendation,
+}
+
+#[tauri::command]
+pub async fn get_scoring_weights(state: State<'_, crate::AppState>) -> Result<ScoringWeights, String> {
+    read_setting(&state.db, SCORING_WEIGHTS_KEY).await
+}
+
+#[tauri::command]
+pub async fn update_scoring_weights(
+    state: State<'_, crate::AppState>,
+    weights: ScoringWeights,
+) -> Result<(), String> {
+    validate_weights(&weights)?;
+    write_setting(&state.db, SCORING_WEIGHTS_KEY, &weights).await
+}
+
+fn validate_weights(weights: &ScoringWeights) -> Result<(), String> {
+    if weights.minutes_per_task <= 0.0 || weights.default_horizon_days <= 0 {
+        return Err("Minutes per task and the default horizon must be positive".to_string());
+    }
+    if !(0.0..=1.0).contains(&weights.default_confidence) {
+        return Err("Default confidence must be between 0 and 1".to_string());
+    }
+    // A negative exponent would favour long shots, a negative share turn cost into value.
+    if !(weights.confidence_weight >= 0.0 && weights.cost_weight >= 0.0) {
+        return Err("Confidence and cost weights can't be negative".to_string());
+    }
+    Ok(())
+}
+
+/// Active airdrops sorted by expected value per remaining minute of effort,
+/// best first. Airdrops without tasks or without an estimate come last.
+#[tauri::command]
+pub async fn rank_airdrops(state: State<'_, crate::AppState>) -> Result<Vec<AirdropScore>, String> {
+    let scores = score_airdrops(&state.db).await?;
+
+    let drop = scores
+        .iter()
+        .filter(|s| matches!(s.recommendation, AirdropRecommendation::Drop))
+        .count();
+    info!("phase=rank_airdrops_done airdrops={} drop={}", scores.len(), drop);
+    Ok(scores)
+}
+
+pub(crate) async fn score_airdrops(db: &SqlitePool) -> Result<Vec<AirdropScore>, String> {
+    let weights: ScoringWeights = read_setting(db, SCORING_WEIGHTS_KEY).await?;
+
+    let airdrops = sqlx::query_as::<_, Airdrop>("SELECT * FROM airdrops WHERE active = 1")
+        .fetch_all(db)
+        .await
+        .map_err(|e| format!("Failed to fetch airdrops: {}", e))?;
+
+    let task_counts: HashMap<i64, i64> = sqlx::query_as::<_, (i64, i64)>(
+        "SELECT airdrop_id, COUNT(*) FROM airdrop_daily_tasks GROUP BY airdrop_id",
+    )
+    .fetch_all(db)
+    .await
+    .map_err(|e| format!("Failed to count daily tasks: {}", e))?
+    .into_iter()
+    .collect();
+
+    // Costs are converted into the base currency, the unit of expected value.
+    let currency = base_currency(db).await?;
+    let prices = PriceBook::load(db).await?;
+    let expenses = sqlx::query_as::<_, AirdropExpense>("SELECT * FROM airdrop_expenses")
+        .fetch_all(db)
+        .await
+        .map_err(|e| format!("Failed to fetch expenses: {}", e))?;
+    let mut costs: HashMap<i64, f64> = HashMap::new();
+    let mut unpriced: HashMap<i64, i64> = HashMap::new();
+    for expense in &expenses {
+        let day = NaiveDate::parse_from_str(&expense.spent_on, "%Y-%m-%d")
+            .unwrap_or_else(|_| expense.created_at.date_naive());
+        match prices.convert(expense.amount, &expense.currency, &currency, day) {
+            Some(amount) => *costs.entry(expense.airdrop_id).or_default() += amount,
+            None => *unpriced.entry(expense.airdrop_id).or_default() += 1,
+        }
+    }
+
+    let today = Utc::now().date_naive();
+    let mut scores: Vec<AirdropScore> = airdrops
+        .iter()
+        .map(|airdrop| {
+            let daily_tasks = task_counts.get(&airdrop.id).copied().unwrap_or(0);
+            let cost_to_date = costs.get(&airdrop.id).copied().unwrap_or(0.0);
+            let days_to_snapshot = airdrop
+                .snapshot_date
+                .as_deref()
+                .and_then(|d| NaiveDate::parse_from_str(d, "%Y-%m-%d").ok())
+                .map(|d| (d - today).num_days());
+            let unpriced_expenses = unpriced.get(&airdrop.id).copied().unwrap_or(0);
+            score_airdrop(
+                airdrop,
+                daily_tasks,
+                (cost_to_date, unpriced_expenses),
+                days_to_snapshot,
+                &currency,
+                &weights,
+            )
+        })
+        .collect();
+
+    scores.sort_by(|a, b| match (a.roi_per_minute, b.roi_per_minute) {
+        (Some(a), Some(b)) => b.total_cmp(&a),
+        (Some(_), None) => std::cmp::Ordering::Less,
+        (None, Some(_)) => std::cmp::Ordering::Greater,
+        (None, None) => a.airdrop_name.cmp(&b.airdrop_name),
+    });
+    Ok(scores)
+}
+
+fn score_airdrop(
+    airdrop: &Airdrop,
+    daily_tasks: i64,
+    (cost_to_date, unpriced_expenses): (f64, i64),
+    days_to_snapshot: Option<i64>,
+    currency: &str,
+    weights: &ScoringWeights,
+) -> AirdropScore {
+    let confidence = airdrop
+        .confidence
+        .unwrap_or(weights.default_confidence)
+        .clamp(0.0, 1.0);
+    let effort_minutes_per_day = daily_tasks as f64 * weights.minutes_per_task;
+    // A snapshot that already passed leaves one last day of effort at most.
+    let days_left = days_to_snapshot
+        .unwrap_or(weights.default_horizon_days)
+        .max(1);
+    let remaining_effort_minutes = effort_minutes_per_day * days_left as f64;
+
+    let expected_net = airdrop.expected_value.map(|value| {
+        value * confidence.powf(weights.confidence_weight) - cost_to_date * weights.cost_weight
+    });
+    // With no tasks there's no effort to divide by, and no rate to rank on.
+    let roi_per_minute = expected_net
+        .filter(|_| remaining_effort_minutes > 0.0)
+        .map(|net| net / remaining_effort_minutes);
+
+    let recommendation = match roi_per_minute {
+        None if expected_net.is_none() => AirdropRecommendation::NeedsEstimate,
+        None => AirdropRecommendation::NoTasks,
+        Some(roi) if roi < weights.drop_below_per_minute => AirdropRecommendation::Drop,
+        Some(roi) if roi < weights.review_below_per_minute => AirdropRecommendation::Review,
+        Some(_) => AirdropRecommendation::Keep,
+    };
+
+    AirdropScore {
+        airdrop_id: airdrop.id,
+        airdrop_name: airdrop.name.clone(),
+        expected_value: airdrop.expected_value,
+        confidence,
+        cost_to_date,
+        unpriced_expenses,
+        currency: currency.to_string(),
+        daily_tasks,
+        effort_minutes_per_day,
+        days_to_snapshot,
+        remaining_effort_minutes,
+        expected_net,
+        roi_per_minute,
+        recommendation,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn airdrop(expected_value: Option<f64>, confidence: Option<f64>) -> Airdrop {
+        Airdrop {
+            id: 1,
+            name: "Campaign".to_string(),
+            url: "https://example.com".to_string(),
+            airdrop_type_id: None,
+            chain: None,
+            wallet_address: None,
+            position: 0,
+            notes: None,
+            active: true,
+            expected_value,
+            confidence,
+            snapshot_date: None,
+            template_version: None,
+            browser_profile_id: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn score(airdrop: &Airdrop, daily_tasks: i64, cost: f64, weights: &ScoringWeights) -> AirdropScore {
+        score_airdrop(airdrop, daily_tasks, (cost, 0), Some(10), "USD", weights)
+    }
+
+    #[test]
+    fn an_airdrop_without_tasks_has_no_rate() {
+        let weights = ScoringWeights::default();
+        let scored = score(&airdrop(Some(1000.0), Some(1.0)), 0, 0.0, &weights);
+
+        assert_eq!(scored.remaining_effort_minutes, 0.0);
+        assert_eq!(scored.expected_net, Some(1000.0));
+        assert_eq!(scored.roi_per_minute, None);
+        assert!(matches!(scored.recommendation, AirdropRecommendation::NoTasks));
+
+        let unestimated = score(&airdrop(None, Some(1.0)), 0, 0.0, &weights);
+        assert!(matches!(unestimated.recommendation, AirdropRecommendation::NeedsEstimate));
+    }
+
+    #[test]
+    fn rate_divides_the_net_value_by_the_remaining_effort() {
+        let weights = ScoringWeights::default();
+        // 2 tasks × 5 minutes × 10 days = 100 minutes
+        let scored = score(&airdrop(Some(100.0), Some(1.0)), 2, 0.0, &weights);
+
+        assert_eq!(scored.remaining_effort_minutes, 100.0);
+        assert_eq!(scored.roi_per_minute, Some(1.0));
+        assert!(matches!(scored.recommendation, AirdropRecommendation::Keep));
+    }
+
+    #[test]
+    fn confidence_and_cost_weights_shape_the_net_value() {
+        let mut weights = ScoringWeights {
+            confidence_weight: 2.0,
+            cost_weight: 0.5,
+            ..ScoringWeights::default()
+        };
+        // 1000 × 0.5² − 100 × 0.5
+        let scored = score(&airdrop(Some(1000.0), Some(0.5)), 1, 100.0, &weights);
+        assert_eq!(scored.expected_net, Some(200.0));
+
+        weights.confidence_weight = 0.0;
+        weights.cost_weight = 0.0;
+        let unweighted = score(&airdrop(Some(1000.0), Some(0.5)), 1, 100.0, &weights);
+        assert_eq!(unweighted.expected_net, Some(1000.0));
+
+        // A missing confidence falls back to the default
+        let defaulted = score(&airdrop(Some(1000.0), None), 1, 0.0, &ScoringWeights::default());
+        assert_eq!(defaulted.confidence, 0.5);
+        assert_eq!(defaulted.expected_net, Some(500.0));
+    }
+
+    #[test]
+    fn a_net_loss_is_marked_for_dropping() {
+        let weights = ScoringWeights::default();
+        let scored = score(&airdrop(Some(50.0), Some(1.0)), 1, 100.0, &weights);
+
+        assert_eq!(scored.expected_net, Some(-50.0));
+        assert!(matches!(scored.recommendation, AirdropRecommendation::Drop));
+    }
+
+    #[test]
+    fn negative_weights_are_rejected() {
+        assert!(validate_weights(&ScoringWeights::default()).is_ok());
+
+        let negative_confidence = ScoringWeights { confidence_weight: -1.0, ..ScoringWeights::default() };
+        assert!(validate_weights(&negative_confidence).is_err());
+
+        let negative_cost = ScoringWeights { cost_weight: -0.5, ..ScoringWeights::default() };
+        assert!(validate_weights(&negative_cost).is_err());
+
+        let nan_cost = ScoringWeights { cost_weight: f64::NAN, ..ScoringWeights::default() };
+        assert!(validate_weights(&nan_cost).is_err());
+    }
+}
@@ -0,0 +1,94 @@
+use tauri::State;
+use sqlx::SqlitePool;
+use chrono::Utc;
+use serde::{de::DeserializeOwned, Serialize};
+
+/// Reads a JSON setting, falling back to `T::default()` when it is missing
+/// or no longer matches the expected shape.
+pub(crate) async fn read_setting<T: DeserializeOwned + Default>(db: &SqlitePool, key: &str) -> Result<T, String> {
+    let value = sqlx::query_scalar::<_, String>("SELECT value FROM settings WHERE key = ?")
+        .bind(key)
+        .fetch_optional(db)
+        .await
+        .map_err(|e| format!("Failed to fetch setting {}: {}", key, e))?;
+
+    Ok(value
+        .and_then(|v| serde_json::from_str(&v).ok())
+        .unwrap_or_default())
+}
+
+pub(crate) async fn write_setting<T: Serialize>(db: &SqlitePool, key: &str, value: &T) -> Result<(), String> {
+    let value = serde_json::to_string(value).map_err(|e| format!("Invalid setting {}: {}", key, e))?;
+
+    sqlx::query(
+        r#"
+        INSERT INTO settings (key, value, updated_at) VALUES (?, ?, ?)
+        ON CONFLICT(key) DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at
+        "#,
+    )
+    .bind(key)
+    .bind(value)
+    .bind(Utc::now().to_rfc3339())
+    .execute(db)
+    .await
+    .map_err(|e| format!("Failed to save setting {}: {}", key, e))?;
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_setting(
+    state: State<'_, crate::AppState>,
+    key: String,
+) -> Result<Option<serde_json::Value>, String> {
+    read_setting::<Option<serde_json::Value>>(&state.db, &key).await
+}
+
+/// Keys owned by commands that validate them; `set_setting` leaves them alone.
+const RESERVED_KEYS: [&str; 5] = [
+    crate::commands::feeds::FEED_SETTINGS_KEY,
+    crate::commands::focus::FOCUS_SETTINGS_KEY,
+    crate::commands::milestones::PLANNING_SETTINGS_KEY,
+    crate::commands::prices::CURRENCY_SETTINGS_KEY,
+    crate::commands::scoring::SCORING_WEIGHTS_KEY,
+];
+
+#[tauri::command]
+pub async fn set_setting(
+    state: State<'_, crate::AppState>,
+    key: String,
+    value: serde_json::Value,
+) -> Result<(), String> {
+    set_free_setting(&state.db, &key, &value).await
+}
+
+pub(crate) async fn set_free_setting(db: &SqlitePool, key: &str, value: &serde_json::Value) -> Result<(), String> {
+    if RESERVED_KEYS.contains(&key.trim()) {
+        return Err(format!("{} can only be changed from its own settings", key.trim()));
+    }
+    write_setting(db, key, value).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::memory_db;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn reserved_keys_are_rejected() {
+        let db = memory_db().await;
+
+        for key in RESERVED_KEYS {
+            let err = set_free_setting(&db, key, &json!({ "bogus": true })).await.unwrap_err();
+            assert!(err.contains(key), "{}", err);
+            let stored: Option<serde_json::Value> = read_setting(&db, key).await.unwrap();
+            assert_eq!(stored, None);
+        }
+        assert!(set_free_setting(&db, " scoring_weights ", &json!(1)).await.is_err());
+
+        set_free_setting(&db, "theme", &json!("dark")).await.unwrap();
+        let theme: Option<serde_json::Value> = read_setting(&db, "theme").await.unwrap();
+        assert_eq!(theme, Some(json!("dark")));
+    }
+}
@@ -0,0 +1,514 @@
+use log::info;
+use tauri::State;
+use sqlx::SqlitePool;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use crate::commands::identities::{identity_usages, reuse_warnings};
+use crate::models::{Airdrop, AirdropDailyTask, TaskCompletion, Wallet};
+
+/// Completions by separate wallets closer together than this look coordinated.
+const SYNC_WINDOW_MINUTES: i64 = 10;
+/// Completion patterns shorter than this are too common to mean anything.
+const MIN_IDENTICAL_PATTERN: usize = 3;
+
+const SYNC_BASE_POINTS: u32 = 10;
+const SYNC_POINTS_PER_EVENT: u32 = 5;
+const SYNC_MAX_POINTS: u32 = 35;
+const IDENTICAL_SET_POINTS: u32 = 20;
+const SHARED_FUNDING_POINTS: u32 = 25;
+const IDENTITY_REUSE_POINTS: u32 = 25;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SybilFindingKind {
+    SynchronizedCompletions,
+    IdenticalTaskSets,
+    SharedFundingSource,
+    IdentityReuse,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SybilRiskLevel {
+    Low,
+    Medium,
+    High,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SybilFinding {
+    pub kind: SybilFindingKind,
+    pub points: u32,
+    pub wallet_ids: Vec<i64>,
+    pub explanation: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AirdropSybilRisk {
+    pub airdrop_id: i64,
+    pub airdrop_name: String,
+    pub score: u32,
+    pub level: SybilRiskLevel,
+    pub wallet_ids: Vec<i64>,
+    pub findings: Vec<SybilFinding>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SybilAuditReport {
+    pub generated_at: DateTime<Utc>,
+    pub airdrops: Vec<AirdropSybilRisk>,
+}
+
+#[tauri::command]
+pub async fn sybil_audit_report(
+    state: State<'_, crate::AppState>,
+    airdrop_id: Option<i64>,
+) -> Result<SybilAuditReport, String> {
+    let report = build_report(&state.db, airdrop_id).await?;
+
+    let flagged = report
+        .airdrops
+        .iter()
+        .filter(|a| !matches!(a.level, SybilRiskLevel::Low))
+        .count();
+    info!(
+        "phase=sybil_audit_done airdrops={} flagged={}",
+        report.airdrops.len(),
+        flagged
+    );
+    Ok(report)
+}
+
+pub(crate) async fn build_report(
+    db: &SqlitePool,
+    airdrop_id: Option<i64>,
+) -> Result<SybilAuditReport, String> {
+    let mut airdrops = sqlx::query_as::<_, Airdrop>(
+        "SELECT * FROM airdrops ORDER BY position ASC, created_at ASC",
+    )
+    .fetch_all(db)
+    .await
+    .map_err(|e| format!("Failed to fetch airdrops: {}", e))?;
+    if let Some(id) = airdrop_id {
+        airdrops.retain(|a| a.id == id);
+    }
+
+    let wallets = sqlx::query_as::<_, Wallet>("SELECT * FROM wallets")
+        .fetch_all(db)
+        .await
+        .map_err(|e| format!("Failed to fetch wallets: {}", e))?;
+    let by_id: HashMap<i64, &Wallet> = wallets.iter().map(|w| (w.id, w)).collect();
+    let by_address: HashMap<String, i64> = wallets
+        .iter()
+        .map(|w| (w.address.to_lowercase(), w.id))
+        .collect();
+
+    let completions = sqlx::query_as::<_, TaskCompletion>(
+        "SELECT * FROM task_completions WHERE wallet_id IS NOT NULL ORDER BY completed_at ASC",
+    )
+    .fetch_all(db)
+    .await
+    .map_err(|e| format!("Failed to fetch completions: {}", e))?;
+
+    let tasks = sqlx::query_as::<_, AirdropDailyTask>("SELECT * FROM airdrop_daily_tasks")
+        .fetch_all(db)
+        .await
+        .map_err(|e| format!("Failed to fetch daily tasks: {}", e))?;
+    let task_titles: HashMap<i64, String> = tasks.into_iter().map(|t| (t.id, t.title)).collect();
+
+    // Without a recorded funding source, fall back to the sender of the
+    // wallet's first incoming transfer.
+    let incoming = sqlx::query_as::<_, (i64, String)>(
+        r#"
+        SELECT wt.wallet_id, wt.from_address FROM wallet_transactions wt
+        JOIN wallets w ON w.id = wt.wallet_id
+        WHERE LOWER(wt.to_address) = LOWER(w.address)
+          AND wt.from_address IS NOT NULL
+          AND CAST(wt.value AS REAL) > 0
+        ORDER BY COALESCE(wt.timestamp, wt.created_at) ASC
+        "#,
+    )
+    .fetch_all(db)
+    .await
+    .map_err(|e| format!("Failed to fetch transactions: {}", e))?;
+    let mut funding_sources: HashMap<i64, String> = HashMap::new();
+    for wallet in &wallets {
+        if let Some(source) = wallet.funding_source.as_ref().filter(|s| !s.trim().is_empty()) {
+            funding_sources.insert(wallet.id, source.trim().to_lowercase());
+        }
+    }
+    for (wallet_id, from_address) in incoming {
+        funding_sources
+            .entry(wallet_id)
+            .or_insert_with(|| from_address.to_lowercase());
+    }
+
+    let identity_warnings = reuse_warnings(db).await?;
+    let identity_links = identity_usages(db).await?;
+
+    let mut risks = Vec::with_capacity(airdrops.len());
+    for airdrop in &airdrops {
+        let airdrop_completions: Vec<&TaskCompletion> = completions
+            .iter()
+            .filter(|c| c.airdrop_id == airdrop.id)
+            .collect();
+
+        let mut participants: BTreeSet<i64> = airdrop_completions
+            .iter()
+            .filter_map(|c| c.wallet_id)
+            .collect();
+        participants.extend(
+            identity_links
+                .iter()
+                .filter(|u| u.airdrop_id == airdrop.id)
+                .filter_map(|u| u.wallet_id),
+        );
+        if let Some(id) = airdrop
+            .wallet_address
+            .as_ref()
+            .and_then(|address| by_address.get(&address.trim().to_lowercase()))
+        {
+            participants.insert(*id);
+        }
+
+        let separate = |a: i64, b: i64| match (by_id.get(&a), by_id.get(&b)) {
+            (Some(a), Some(b)) => a.must_stay_separate_from(b),
+            _ => false,
+        };
+        let label = |id: i64| {
+            by_id
+                .get(&id)
+                .map(|w| w.label.clone())
+                .unwrap_or_else(|| format!("wallet #{}", id))
+        };
+
+        let mut findings = Vec::new();
+
+        // Separate wallets finishing the same task within minutes of each other.
+        let bursts = completion_bursts(&airdrop_completions);
+        let mut sync_events = 0u32;
+        let mut sync_wallets = BTreeSet::new();
+        let mut sync_example = None;
+        for group in &bursts {
+            let mut hit = false;
+            for (i, a) in group.iter().enumerate() {
+                for b in &group[i + 1..] {
+                    let (Some(wa), Some(wb)) = (a.wallet_id, b.wallet_id) else {
+                        continue;
+                    };
+                    let gap = (b.completed_at - a.completed_at).num_minutes().abs();
+                    if gap <= SYNC_WINDOW_MINUTES && separate(wa, wb) {
+                        hit = true;
+                        sync_wallets.insert(wa);
+                        sync_wallets.insert(wb);
+                    }
+                }
+            }
+            if hit {
+                sync_events += 1;
+                if sync_example.is_none() {
+                    let title = task_titles.get(&group[0].task_id).cloned().unwrap_or_default();
+                    sync_example = Some(format!("\"{}\" at {}", title, group[0].completed_at.format("%Y-%m-%d %H:%M UTC")));
+                }
+            }
+        }
+        if sync_events > 0 {
+            findings.push(SybilFinding {
+                kind: SybilFindingKind::SynchronizedCompletions,
+                points: (SYNC_BASE_POINTS + SYNC_POINTS_PER_EVENT * sync_events).min(SYNC_MAX_POINTS),
+                explanation: format!(
+                    "{} time(s) separate wallets ({}) finished the same task within {} minutes, e.g. {}",
+                    sync_events,
+                    sync_wallets.iter().map(|id| label(*id)).collect::<Vec<_>>().join(", "),
+                    SYNC_WINDOW_MINUTES,
+                    sync_example.unwrap_or_default()
+                ),
+                wallet_ids: sync_wallets.into_iter().collect(),
+            });
+        }
+
+        // Separate wallets with the exact same completion pattern: the same
+        // tasks, each within the same burst.
+        let mut patterns: BTreeMap<i64, BTreeSet<usize>> = BTreeMap::new();
+        for (index, group) in bursts.iter().enumerate() {
+            for wallet_id in group.iter().filter_map(|c| c.wallet_id) {
+                patterns.entry(wallet_id).or_default().insert(index);
+            }
+        }
+        let mut identical: BTreeMap<&BTreeSet<usize>, Vec<i64>> = BTreeMap::new();
+        for (wallet_id, pattern) in &patterns {
+            if pattern.len() >= MIN_IDENTICAL_PATTERN {
+                identical.entry(pattern).or_default().push(*wallet_id);
+            }
+        }
+        for (pattern, wallet_ids) in identical {
+            if has_separate_pair(&wallet_ids, &separate) {
+                findings.push(SybilFinding {
+                    kind: SybilFindingKind::IdenticalTaskSets,
+                    points: IDENTICAL_SET_POINTS,
+                    explanation: format!(
+                        "Wallets {} completed exactly the same {} task(s), each within {} minutes of each other",
+                        wallet_ids.iter().map(|id| label(*id)).collect::<Vec<_>>().join(", "),
+                        pattern.len(),
+                        SYNC_WINDOW_MINUTES
+                    ),
+                    wallet_ids,
+                });
+            }
+        }
+
+        // Separate wallets funded from the same source.
+        let mut funding: BTreeMap<String, Vec<i64>> = BTreeMap::new();
+        for wallet_id in &participants {
+            if let Some(source) = funding_sources.get(wallet_id) {
+                funding.entry(source.clone()).or_default().push(*wallet_id);
+            }
+        }
+        for (source, wallet_ids) in funding {
+            if has_separate_pair(&wallet_ids, &separate) {
+                findings.push(SybilFinding {
+                    kind: SybilFindingKind::SharedFundingSource,
+                    points: SHARED_FUNDING_POINTS,
+                    explanation: format!(
+                        "Wallets {} share the funding source {}",
+                        wallet_ids.iter().map(|id| label(*id)).collect::<Vec<_>>().join(", "),
+                        source
+                    ),
+                    wallet_ids,
+                });
+            }
+        }
+
+        // Identities reused across separate wallets that touch this airdrop.
+        for warning in identity_warnings
+            .iter()
+            .filter(|w| w.airdrop_ids.contains(&airdrop.id))
+        {
+            findings.push(SybilFinding {
+                kind: SybilFindingKind::IdentityReuse,
+                points: IDENTITY_REUSE_POINTS,
+                explanation: warning.message.clone(),
+                wallet_ids: warning.wallets.iter().map(|w| w.id).collect(),
+            });
+        }
+
+        let score = findings.iter().map(|f| f.points).sum::<u32>().min(100);
+        risks.push(AirdropSybilRisk {
+            airdrop_id: airdrop.id,
+            airdrop_name: airdrop.name.clone(),
+            score,
+            level: risk_level(score),
+            wallet_ids: participants.into_iter().collect(),
+            findings,
+        });
+    }
+
+    risks.sort_by_key(|r| std::cmp::Reverse(r.score));
+    Ok(SybilAuditReport {
+        generated_at: Utc::now(),
+        airdrops: risks,
+    })
+}
+
+/// Completions of the same task split wherever more than `SYNC_WINDOW_MINUTES`
+/// pass between one and the next, so a burst can span midnight.
+fn completion_bursts<'a>(completions: &[&'a TaskCompletion]) -> Vec<Vec<&'a TaskCompletion>> {
+    let mut by_task: BTreeMap<i64, Vec<&TaskCompletion>> = BTreeMap::new();
+    for completion in completions {
+        by_task.entry(completion.task_id).or_default().push(completion);
+    }
+
+    let mut bursts = Vec::new();
+    for (_, mut group) in by_task {
+        group.sort_by_key(|c| c.completed_at);
+        let mut current: Vec<&TaskCompletion> = Vec::new();
+        for completion in group {
+            if current
+                .last()
+                .is_some_and(|last| (completion.completed_at - last.completed_at).num_minutes() > SYNC_WINDOW_MINUTES)
+            {
+                bursts.push(std::mem::take(&mut current));
+            }
+            current.push(completion);
+        }
+        if !current.is_empty() {
+            bursts.push(current);
+        }
+    }
+    bursts
+}
+
+fn has_separate_pair(wallet_ids: &[i64], separate: &impl Fn(i64, i64) -> bool) -> bool {
+    wallet_ids
+        .iter()
+        .enumerate()
+        .any(|(i, a)| wallet_ids[i + 1..].iter().any(|b| separate(*a, *b)))
+}
+
+fn risk_level(score: u32) -> SybilRiskLevel {
+    match score {
+        0..=24 => SybilRiskLevel::Low,
+        25..=59 => SybilRiskLevel::Medium,
+        _ => SybilRiskLevel::High,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::memory_db;
+    use chrono::NaiveDate;
+
+    async fn seed_airdrop(db: &SqlitePool) -> i64 {
+        let now = Utc::now().to_rfc3339();
+        sqlx::query("INSERT INTO airdrops (name, url, created_at, updated_at) VALUES ('Drop', 'https://drop.example', ?, ?)")
+            .bind(&now)
+            .bind(&now)
+            .execute(db)
+            .await
+            .unwrap()
+            .last_insert_rowid()
+    }
+
+    async fn seed_wallet(db: &SqlitePool, address: &str) -> i64 {
+        let now = Utc::now().to_rfc3339();
+        sqlx::query("INSERT INTO wallets (label, address, created_at, updated_at) VALUES (?, ?, ?, ?)")
+            .bind(address)
+            .bind(address)
+            .bind(&now)
+            .bind(&now)
+            .execute(db)
+            .await
+            .unwrap()
+            .last_insert_rowid()
+    }
+
+    async fn seed_task(db: &SqlitePool, airdrop_id: i64, title: &str) -> i64 {
+        let now = Utc::now().to_rfc3339();
+        sqlx::query("INSERT INTO airdrop_daily_tasks (airdrop_id, title, created_at, updated_at) VALUES (?, ?, ?, ?)")
+            .bind(airdrop_id)
+            .bind(title)
+            .bind(&now)
+            .bind(&now)
+            .execute(db)
+            .await
+            .unwrap()
+            .last_insert_rowid()
+    }
+
+    async fn complete(db: &SqlitePool, airdrop_id: i64, task_id: i64, wallet_id: i64, completed_at: &str) {
+        sqlx::query(
+            "INSERT INTO task_completions (task_id, airdrop_id, wallet_id, completed_on, completed_at) VALUES (?, ?, ?, ?, ?)",
+        )
+        .bind(task_id)
+        .bind(airdrop_id)
+        .bind(wallet_id)
+        .bind(&completed_at[..10])
+        .bind(completed_at)
+        .execute(db)
+        .await
+        .unwrap();
+    }
+
+    async fn receive(db: &SqlitePool, wallet_id: i64, address: &str, from: &str, value: &str, timestamp: &str) {
+        sqlx::query(
+            r#"
+            INSERT INTO wallet_transactions (wallet_id, chain, tx_hash, timestamp, from_address, to_address, value, created_at)
+            VALUES (?, 'ethereum', ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(wallet_id)
+        .bind(format!("0x{}{}", wallet_id, timestamp))
+        .bind(timestamp)
+        .bind(from)
+        .bind(address)
+        .bind(value)
+        .bind(timestamp)
+        .execute(db)
+        .await
+        .unwrap();
+    }
+
+    fn findings(report: &SybilAuditReport, kind: &str) -> Vec<SybilFinding> {
+        report.airdrops[0]
+            .findings
+            .iter()
+            .filter(|f| format!("{:?}", f.kind) == kind)
+            .cloned()
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn synchronized_completions_are_found_across_midnight() {
+        let db = memory_db().await;
+        let airdrop_id = seed_airdrop(&db).await;
+        let task_id = seed_task(&db, airdrop_id, "Bridge").await;
+        let a = seed_wallet(&db, "0xaaa").await;
+        let b = seed_wallet(&db, "0xbbb").await;
+        complete(&db, airdrop_id, task_id, a, "2024-05-01T23:58:00+00:00").await;
+        complete(&db, airdrop_id, task_id, b, "2024-05-02T00:03:00+00:00").await;
+        // Same day, hours apart
+        complete(&db, airdrop_id, task_id, a, "2024-05-03T08:00:00+00:00").await;
+        complete(&db, airdrop_id, task_id, b, "2024-05-03T17:00:00+00:00").await;
+
+        let report = build_report(&db, Some(airdrop_id)).await.unwrap();
+
+        let sync = findings(&report, "SynchronizedCompletions");
+        assert_eq!(sync.len(), 1);
+        assert_eq!(sync[0].points, SYNC_BASE_POINTS + SYNC_POINTS_PER_EVENT);
+        assert_eq!(sync[0].wallet_ids, vec![a, b]);
+        assert!(sync[0].explanation.starts_with("1 time(s)"), "{}", sync[0].explanation);
+        assert!(sync[0].explanation.contains("\"Bridge\" at 2024-05-01 23:58 UTC"), "{}", sync[0].explanation);
+    }
+
+    #[tokio::test]
+    async fn identical_patterns_compare_times_not_calendar_days() {
+        let db = memory_db().await;
+        let airdrop_id = seed_airdrop(&db).await;
+        let a = seed_wallet(&db, "0xaaa").await;
+        let b = seed_wallet(&db, "0xbbb").await;
+        let c = seed_wallet(&db, "0xccc").await;
+        for (day, title) in ["2024-05-01", "2024-05-02", "2024-05-03"].iter().zip(["Swap", "Lend", "Vote"]) {
+            let task_id = seed_task(&db, airdrop_id, title).await;
+            complete(&db, airdrop_id, task_id, a, &format!("{}T23:59:00+00:00", day)).await;
+            // b lands past midnight every time, c hours earlier on the same day
+            let next = NaiveDate::parse_from_str(day, "%Y-%m-%d").unwrap().succ_opt().unwrap();
+            complete(&db, airdrop_id, task_id, b, &format!("{}T00:04:00+00:00", next)).await;
+            complete(&db, airdrop_id, task_id, c, &format!("{}T09:00:00+00:00", day)).await;
+        }
+
+        let report = build_report(&db, Some(airdrop_id)).await.unwrap();
+
+        let identical = findings(&report, "IdenticalTaskSets");
+        assert_eq!(identical.len(), 1);
+        assert_eq!(identical[0].wallet_ids, vec![a, b]);
+    }
+
+    #[tokio::test]
+    async fn funding_fallback_skips_zero_value_transfers() {
+        let db = memory_db().await;
+        let airdrop_id = seed_airdrop(&db).await;
+        let task_id = seed_task(&db, airdrop_id, "Swap").await;
+        let a = seed_wallet(&db, "0xaaa").await;
+        let b = seed_wallet(&db, "0xbbb").await;
+        complete(&db, airdrop_id, task_id, a, "2024-05-01T09:00:00+00:00").await;
+        complete(&db, airdrop_id, task_id, b, "2024-05-03T09:00:00+00:00").await;
+        // Spam dust comes first in both wallets, written the way explorers do
+        receive(&db, a, "0xaaa", "0xspam", "0.00", "2024-04-01T00:00:00Z").await;
+        receive(&db, b, "0xbbb", "0xspam", "0E-18", "2024-04-01T00:00:00Z").await;
+        receive(&db, a, "0xaaa", "0xfunder", "0.05", "2024-04-02T00:00:00Z").await;
+        receive(&db, b, "0xbbb", "0xother", "1000000000000000", "2024-04-02T00:00:00Z").await;
+
+        let report = build_report(&db, Some(airdrop_id)).await.unwrap();
+        assert!(findings(&report, "SharedFundingSource").is_empty());
+
+        receive(&db, b, "0xbbb", "0xfunder", "0.5", "2024-03-01T00:00:00Z").await;
+        let report = build_report(&db, Some(airdrop_id)).await.unwrap();
+        let shared = findings(&report, "SharedFundingSource");
+        assert_eq!(shared.len(), 1);
+        assert_eq!(shared[0].wallet_ids, vec![a, b]);
+        assert!(shared[0].explanation.contains("0xfunder"));
+    }
+}
@@ -0,0 +1,253 @@
+use log::info;
+use tauri::{AppHandle, Emitter, State};
+use sqlx::{Sqlite, SqlitePool, Transaction};
+use chrono::Utc;
+use serde::Serialize;
+
+use crate::commands::projects::scheduled_tasks_query;
+use crate::models::ScheduledProjectTask;
+
+#[derive(Debug, Serialize)]
+pub struct TaskDependencies {
+    /// Tasks this one waits on
+    pub blocked_by: Vec<ScheduledProjectTask>,
+    /// Tasks waiting on this one
+    pub blocking: Vec<ScheduledProjectTask>,
+}
+
+#[tauri::command]
+pub async fn list_task_dependencies(
+    state: State<'_, crate::AppState>,
+    task_id: i64,
+) -> Result<TaskDependencies, String> {
+    let blocked_by = sqlx::query_as::<_, ScheduledProjectTask>(&scheduled_tasks_query(
+        "t.id IN (SELECT blocked_by_task_id FROM project_task_dependencies WHERE task_id = ?) \
+         ORDER BY t.done ASC, t.id ASC",
+    ))
+    .bind(task_id)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|e| format!("Failed to fetch blocking tasks: {}", e))?;
+
+    let blocking = sqlx::query_as::<_, ScheduledProjectTask>(&scheduled_tasks_query(
+        "t.id IN (SELECT task_id FROM project_task_dependencies WHERE blocked_by_task_id = ?) \
+         ORDER BY t.done ASC, t.id ASC",
+    ))
+    .bind(task_id)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|e| format!("Failed to fetch dependent tasks: {}", e))?;
+
+    Ok(TaskDependencies { blocked_by, blocking })
+}
+
+/// Records that `task_id` cannot start until `blocked_by_task_id` is done.
+/// The tasks may belong to different projects.
+#[tauri::command]
+pub async fn add_task_dependency(
+    state: State<'_, crate::AppState>,
+    task_id: i64,
+    blocked_by_task_id: i64,
+) -> Result<(), String> {
+    add_dependency(&state.db, task_id, blocked_by_task_id).await
+}
+
+pub(crate) async fn add_dependency(db: &SqlitePool, task_id: i64, blocked_by_task_id: i64) -> Result<(), String> {
+    if task_id == blocked_by_task_id {
+        return Err("A task cannot block itself".to_string());
+    }
+    for id in [task_id, blocked_by_task_id] {
+        let exists = sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM project_tasks WHERE id = ?")
+            .bind(id)
+            .fetch_one(db)
+            .await
+            .map_err(|e| format!("Failed to fetch project task: {}", e))?;
+        if exists == 0 {
+            return Err(format!("Project task {} not found", id));
+        }
+    }
+
+    // Insert first so the write lock is held while checking; a concurrent
+    // add then sees this edge and cannot close a cycle through it
+    let mut tx = db
+        .begin()
+        .await
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+    sqlx::query(
+        r#"
+        INSERT OR IGNORE INTO project_task_dependencies (task_id, blocked_by_task_id, created_at)
+        VALUES (?, ?, ?)
+        "#,
+    )
+    .bind(task_id)
+    .bind(blocked_by_task_id)
+    .bind(Utc::now().to_rfc3339())
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| format!("Failed to add task dependency: {}", e))?;
+
+    if depends_on(&mut tx, blocked_by_task_id, task_id).await? {
+        return Err("This dependency would create a cycle".to_string());
+    }
+
+    tx.commit()
+        .await
+        .map_err(|e| format!("Failed to commit task dependency: {}", e))?;
+
+    info!("phase=task_dependency_add_ok task={} blocked_by={}", task_id, blocked_by_task_id);
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn remove_task_dependency(
+    state: State<'_, crate::AppState>,
+    task_id: i64,
+    blocked_by_task_id: i64,
+) -> Result<(), String> {
+    sqlx::query("DELETE FROM project_task_dependencies WHERE task_id = ? AND blocked_by_task_id = ?")
+        .bind(task_id)
+        .bind(blocked_by_task_id)
+        .execute(&state.db)
+        .await
+        .map_err(|e| format!("Failed to remove task dependency: {}", e))?;
+
+    Ok(())
+}
+
+/// Open, unblocked leaf tasks of active projects whose start date has come,
+/// most urgent first.
+#[tauri::command]
+pub async fn list_ready_project_tasks(
+    state: State<'_, crate::AppState>,
+) -> Result<Vec<ScheduledProjectTask>, String> {
+    let query = scheduled_tasks_query(
+        r#"
+        t.done = 0 AND p.status = 'active'
+        AND (t.start_date IS NULL OR t.start_date <= ?)
+        AND NOT EXISTS (
+            SELECT 1 FROM project_task_dependencies d
+            JOIN project_tasks b ON b.id = d.blocked_by_task_id
+            WHERE d.task_id = t.id AND b.done = 0
+        )
+        AND NOT EXISTS (SELECT 1 FROM project_tasks c WHERE c.parent_task_id = t.id AND c.done = 0)
+        ORDER BY COALESCE(t.priority, 4) ASC, t.due_date IS NULL, t.due_date ASC, t.id ASC
+        "#,
+    );
+
+    sqlx::query_as::<_, ScheduledProjectTask>(&query)
+        .bind(Utc::now().date_naive().format("%Y-%m-%d").to_string())
+        .fetch_all(&state.db)
+        .await
+        .map_err(|e| format!("Failed to fetch ready tasks: {}", e))
+}
+
+/// Whether `task_id` transitively waits on `other_id`.
+async fn depends_on(tx: &mut Transaction<'_, Sqlite>, task_id: i64, other_id: i64) -> Result<bool, String> {
+    let count = sqlx::query_scalar::<_, i64>(
+        r#"
+        WITH RECURSIVE blockers(id) AS (
+            SELECT ?
+            UNION
+            SELECT d.blocked_by_task_id FROM project_task_dependencies d
+            JOIN blockers b ON d.task_id = b.id
+        )
+        SELECT COUNT(*) FROM blockers WHERE id = ?
+        "#,
+    )
+    .bind(task_id)
+    .bind(other_id)
+    .fetch_one(&mut **tx)
+    .await
+    .map_err(|e| format!("Failed to check task dependencies: {}", e))?;
+
+    Ok(count > 0)
+}
+
+/// Emits `project-tasks-unblocked` with the open tasks that were waiting only
+/// on `task_id`, which has just been completed.
+pub(crate) async fn announce_unblocked(app: &AppHandle, db: &SqlitePool, task_id: i64) -> Result<(), String> {
+    let unblocked = sqlx::query_as::<_, ScheduledProjectTask>(&scheduled_tasks_query(
+        r#"
+        t.done = 0
+        AND t.id IN (SELECT task_id FROM project_task_dependencies WHERE blocked_by_task_id = ?)
+        AND NOT EXISTS (
+            SELECT 1 FROM project_task_dependencies d
+            JOIN project_tasks b ON b.id = d.blocked_by_task_id
+            WHERE d.task_id = t.id AND b.done = 0
+        )
+        ORDER BY t.id ASC
+        "#,
+    ))
+    .bind(task_id)
+    .fetch_all(db)
+    .await
+    .map_err(|e| format!("Failed to fetch unblocked tasks: {}", e))?;
+
+    if !unblocked.is_empty() {
+        info!("phase=tasks_unblocked_ok blocker={} count={}", task_id, unblocked.len());
+        let _ = app.emit("project-tasks-unblocked", unblocked);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::memory_db;
+
+    async fn seed_tasks(db: &SqlitePool, count: usize) -> Vec<i64> {
+        let now = Utc::now().to_rfc3339();
+        let project_id = sqlx::query("INSERT INTO projects (name, status, created_at, updated_at) VALUES ('Deps', 'active', ?, ?)")
+            .bind(&now)
+            .bind(&now)
+            .execute(db)
+            .await
+            .unwrap()
+            .last_insert_rowid();
+        let mut ids = Vec::new();
+        for _ in 0..count {
+            let id = sqlx::query("INSERT INTO project_tasks (project_id, title, done, created_at, updated_at) VALUES (?, 'task', 0, ?, ?)")
+                .bind(project_id)
+                .bind(&now)
+                .bind(&now)
+                .execute(db)
+                .await
+                .unwrap()
+                .last_insert_rowid();
+            ids.push(id);
+        }
+        ids
+    }
+
+    async fn edges(db: &SqlitePool) -> Vec<(i64, i64)> {
+        sqlx::query_as("SELECT task_id, blocked_by_task_id FROM project_task_dependencies ORDER BY task_id, blocked_by_task_id")
+            .fetch_all(db)
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn cycles_are_rejected_and_not_stored() {
+        let db = memory_db().await;
+        let ids = seed_tasks(&db, 3).await;
+        add_dependency(&db, ids[1], ids[0]).await.unwrap();
+        add_dependency(&db, ids[2], ids[1]).await.unwrap();
+
+        let err = add_dependency(&db, ids[0], ids[2]).await.unwrap_err();
+
+        assert!(err.contains("cycle"), "{}", err);
+        assert_eq!(edges(&db).await, vec![(ids[1], ids[0]), (ids[2], ids[1])]);
+    }
+
+    #[tokio::test]
+    async fn adding_an_existing_dependency_again_is_a_no_op() {
+        let db = memory_db().await;
+        let ids = seed_tasks(&db, 2).await;
+        add_dependency(&db, ids[1], ids[0]).await.unwrap();
+        add_dependency(&db, ids[1], ids[0]).await.unwrap();
+
+        assert_eq!(edges(&db).await, vec![(ids[1], ids[0])]);
+        assert!(add_dependency(&db, ids[0], ids[0]).await.is_err());
+    }
+}
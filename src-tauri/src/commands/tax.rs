@@ -0,0 +1,793 @@
+use log::info;
+use tauri::State;
+use sqlx::SqlitePool;
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+
+use crate::commands::prices::{base_currency, PriceBook};
+use crate::models::{AirdropClaim, DisposalLot, TokenDisposal};
+
+/// Amounts below this are treated as fully matched, absorbing float noise.
+const AMOUNT_EPSILON: f64 = 1e-9;
+/// Holdings kept longer than this count as long-term.
+const LONG_TERM_DAYS: i64 = 365;
+
+#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LotMethod {
+    Fifo,
+    /// Uses the lots recorded on each disposal, falling back to FIFO for the rest
+    SpecificId,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TaxCsvLayout {
+    Generic,
+    Koinly,
+    CoinTracking,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TaxReportRequest {
+    /// Fiscal year, named after the calendar year it starts in
+    pub year: i32,
+    pub method: LotMethod,
+    /// 1-12; defaults to January
+    pub fiscal_year_start_month: Option<u32>,
+    /// Currency to report in; defaults to the base currency
+    pub currency: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TaxIncomeRow {
+    pub claim_id: i64,
+    pub airdrop_name: Option<String>,
+    pub token: String,
+    pub amount: f64,
+    pub received_at: DateTime<Utc>,
+    pub fair_value: f64,
+    pub currency: String,
+    pub tx_hash: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TaxDisposalRow {
+    pub disposal_id: i64,
+    /// `None` when no claim lot covered this part of the sale
+    pub claim_id: Option<i64>,
+    pub token: String,
+    pub amount: f64,
+    pub received_at: Option<DateTime<Utc>>,
+    pub disposed_at: DateTime<Utc>,
+    pub cost_basis: f64,
+    /// Share of the proceeds, net of this row's share of the fee
+    pub proceeds: f64,
+    pub fee: f64,
+    pub gain_loss: f64,
+    pub holding_days: Option<i64>,
+    pub long_term: Option<bool>,
+    pub currency: String,
+    pub tx_hash: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TaxReport {
+    pub year: i32,
+    pub method: LotMethod,
+    pub period_start: NaiveDate,
+    pub period_end: NaiveDate,
+    /// Set when every row shares one currency
+    pub currency: Option<String>,
+    pub income: Vec<TaxIncomeRow>,
+    pub disposals: Vec<TaxDisposalRow>,
+    pub total_income: f64,
+    pub total_proceeds: f64,
+    pub total_cost_basis: f64,
+    pub total_gain_loss: f64,
+    pub warnings: Vec<String>,
+}
+
+struct Lot<'a> {
+    claim: &'a AirdropClaim,
+    remaining: f64,
+    /// Part of `remaining` assigned to sales not matched yet; FIFO leaves it alone
+    reserved: f64,
+}
+
+#[tauri::command]
+pub async fn tax_report(
+    state: State<'_, crate::AppState>,
+    req: TaxReportRequest,
+) -> Result<TaxReport, String> {
+    let report = build_tax_report(&state.db, &req).await?;
+
+    info!(
+        "phase=tax_report_done year={} income={} disposals={} warnings={}",
+        report.year,
+        report.income.len(),
+        report.disposals.len(),
+        report.warnings.len()
+    );
+    Ok(report)
+}
+
+#[tauri::command]
+pub async fn export_tax_report_csv(
+    state: State<'_, crate::AppState>,
+    req: TaxReportRequest,
+    layout: TaxCsvLayout,
+) -> Result<String, String> {
+    let report = build_tax_report(&state.db, &req).await?;
+    let rows = match layout {
+        TaxCsvLayout::Generic => generic_rows(&report),
+        TaxCsvLayout::Koinly => koinly_rows(&report),
+        TaxCsvLayout::CoinTracking => cointracking_rows(&report),
+    };
+
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    for row in rows {
+        writer
+            .write_record(&row)
+            .map_err(|e| format!("Failed to write CSV row: {}", e))?;
+    }
+    let bytes = writer
+        .into_inner()
+        .map_err(|e| format!("Failed to finish CSV: {}", e))?;
+
+    String::from_utf8(bytes).map_err(|e| format!("Invalid CSV output: {}", e))
+}
+
+pub(crate) async fn build_tax_report(db: &SqlitePool, req: &TaxReportRequest) -> Result<TaxReport, String> {
+    let (period_start, period_end) = fiscal_period(req.year, req.fiscal_year_start_month.unwrap_or(1))?;
+    let in_period = |at: &DateTime<Utc>| {
+        let day = at.date_naive();
+        day >= period_start && day <= period_end
+    };
+
+    let mut claims = sqlx::query_as::<_, AirdropClaim>("SELECT * FROM airdrop_claims ORDER BY received_at ASC, id ASC")
+        .fetch_all(db)
+        .await
+        .map_err(|e| format!("Failed to fetch claims: {}", e))?;
+    let mut disposals =
+        sqlx::query_as::<_, TokenDisposal>("SELECT * FROM token_disposals ORDER BY disposed_at ASC, id ASC")
+            .fetch_all(db)
+            .await
+            .map_err(|e| format!("Failed to fetch disposals: {}", e))?;
+    let assigned_lots = sqlx::query_as::<_, DisposalLot>("SELECT * FROM disposal_lots")
+        .fetch_all(db)
+        .await
+        .map_err(|e| format!("Failed to fetch disposal lots: {}", e))?;
+    let airdrop_names: HashMap<i64, String> = sqlx::query_as::<_, (i64, String)>("SELECT id, name FROM airdrops")
+        .fetch_all(db)
+        .await
+        .map_err(|e| format!("Failed to fetch airdrops: {}", e))?
+        .into_iter()
+        .collect();
+
+    let mut lots_by_disposal: HashMap<i64, Vec<&DisposalLot>> = HashMap::new();
+    for lot in &assigned_lots {
+        lots_by_disposal.entry(lot.disposal_id).or_default().push(lot);
+    }
+
+    // Values are converted at the rate of the day they were received or sold.
+    let target = match req.currency.as_deref().map(str::trim).filter(|c| !c.is_empty()) {
+        Some(currency) => currency.to_uppercase(),
+        None => base_currency(db).await?,
+    };
+    let prices = PriceBook::load(db).await?;
+    let mut warnings = Vec::new();
+    for claim in claims.iter_mut().filter(|c| c.currency != target) {
+        match prices.convert(claim.fair_value, &claim.currency, &target, claim.received_at.date_naive()) {
+            Some(value) => {
+                claim.fair_value = value;
+                claim.currency = target.clone();
+            }
+            None if in_period(&claim.received_at) => warnings.push(format!(
+                "No {}/{} price for claim #{}; left in {}",
+                claim.currency, target, claim.id, claim.currency
+            )),
+            None => {}
+        }
+    }
+    for disposal in disposals.iter_mut().filter(|d| d.currency != target) {
+        let day = disposal.disposed_at.date_naive();
+        match (
+            prices.convert(disposal.proceeds, &disposal.currency, &target, day),
+            prices.convert(disposal.fee, &disposal.currency, &target, day),
+        ) {
+            (Some(proceeds), Some(fee)) => {
+                disposal.proceeds = proceeds;
+                disposal.fee = fee;
+                disposal.currency = target.clone();
+            }
+            _ if in_period(&disposal.disposed_at) => warnings.push(format!(
+                "No {}/{} price for sale #{}; left in {}",
+                disposal.currency, target, disposal.id, disposal.currency
+            )),
+            _ => {}
+        }
+    }
+
+    let income: Vec<TaxIncomeRow> = claims
+        .iter()
+        .filter(|c| in_period(&c.received_at))
+        .map(|c| TaxIncomeRow {
+            claim_id: c.id,
+            airdrop_name: c.airdrop_id.and_then(|id| airdrop_names.get(&id).cloned()),
+            token: c.token.clone(),
+            amount: c.amount,
+            received_at: c.received_at,
+            fair_value: c.fair_value,
+            currency: c.currency.clone(),
+            tx_hash: c.tx_hash.clone(),
+        })
+        .collect();
+
+    // Lots are consumed across the whole history so earlier years' sales
+    // are accounted for; only rows inside the period are reported.
+    let mut lots: Vec<Lot> = claims
+        .iter()
+        .map(|claim| Lot {
+            claim,
+            remaining: claim.amount,
+            reserved: 0.0,
+        })
+        .collect();
+    if matches!(req.method, LotMethod::SpecificId) {
+        // Earlier sales falling back to FIFO must not take a lot a later sale chose
+        for assigned in &assigned_lots {
+            if let Some(lot) = lots.iter_mut().find(|l| l.claim.id == assigned.claim_id) {
+                lot.reserved += assigned.amount;
+            }
+        }
+    }
+    let mut rows = Vec::new();
+    for disposal in &disposals {
+        let report_it = in_period(&disposal.disposed_at);
+        let mut pieces: Vec<(Option<usize>, f64)> = Vec::new();
+        let mut left = disposal.amount;
+
+        if matches!(req.method, LotMethod::SpecificId) {
+            for assigned in lots_by_disposal.get(&disposal.id).into_iter().flatten() {
+                let Some(index) = lots.iter().position(|l| l.claim.id == assigned.claim_id) else {
+                    continue;
+                };
+                lots[index].reserved -= assigned.amount;
+                let take = assigned.amount.min(lots[index].remaining).min(left);
+                if take + AMOUNT_EPSILON < assigned.amount && report_it {
+                    warnings.push(format!(
+                        "Sale #{} assigns {} {} from claim #{}, but only {} was left; the rest was matched FIFO",
+                        disposal.id, assigned.amount, disposal.token, assigned.claim_id, take
+                    ));
+                }
+                if take > AMOUNT_EPSILON {
+                    lots[index].remaining -= take;
+                    left -= take;
+                    pieces.push((Some(index), take));
+                }
+            }
+        }
+
+        for (index, lot) in lots.iter_mut().enumerate() {
+            if left <= AMOUNT_EPSILON {
+                break;
+            }
+            let available = lot.remaining - lot.reserved.max(0.0);
+            if lot.claim.token != disposal.token
+                || lot.claim.received_at > disposal.disposed_at
+                || available <= AMOUNT_EPSILON
+            {
+                continue;
+            }
+            let take = available.min(left);
+            lot.remaining -= take;
+            left -= take;
+            pieces.push((Some(index), take));
+        }
+
+        if left > AMOUNT_EPSILON {
+            if report_it {
+                warnings.push(format!(
+                    "Sale #{} of {} {} exceeds recorded claims by {}; that part has a zero cost basis",
+                    disposal.id, disposal.amount, disposal.token, left
+                ));
+            }
+            pieces.push((None, left));
+        }
+
+        if !report_it {
+            continue;
+        }
+        for (index, amount) in pieces {
+            let share = amount / disposal.amount;
+            let fee = disposal.fee * share;
+            let proceeds = disposal.proceeds * share - fee;
+            let claim = index.map(|i| lots[i].claim);
+            let cost_basis = claim.map_or(0.0, |c| c.fair_value * amount / c.amount);
+            let holding_days = claim.map(|c| (disposal.disposed_at - c.received_at).num_days());
+            if let Some(claim) = claim.filter(|c| c.currency != disposal.currency) {
+                warnings.push(format!(
+                    "Sale #{} is in {} but claim #{} is valued in {}",
+                    disposal.id, disposal.currency, claim.id, claim.currency
+                ));
+            }
+
+            rows.push(TaxDisposalRow {
+                disposal_id: disposal.id,
+                claim_id: claim.map(|c| c.id),
+                token: disposal.token.clone(),
+                amount,
+                received_at: claim.map(|c| c.received_at),
+                disposed_at: disposal.disposed_at,
+                cost_basis,
+                proceeds,
+                fee,
+                gain_loss: proceeds - cost_basis,
+                holding_days,
+                long_term: holding_days.map(|d| d > LONG_TERM_DAYS),
+                currency: disposal.currency.clone(),
+                tx_hash: disposal.tx_hash.clone(),
+            });
+        }
+    }
+
+    let currencies: BTreeSet<&str> = income
+        .iter()
+        .map(|r| r.currency.as_str())
+        .chain(rows.iter().map(|r| r.currency.as_str()))
+        .collect();
+    let currency = match currencies.len() {
+        0 => None,
+        1 => currencies.iter().next().map(|c| c.to_string()),
+        _ => {
+            warnings.push(format!(
+                "Rows use several currencies ({}); totals mix them",
+                currencies.iter().copied().collect::<Vec<_>>().join(", ")
+            ));
+            None
+        }
+    };
+
+    Ok(TaxReport {
+        year: req.year,
+        method: req.method,
+        period_start,
+        period_end,
+        currency,
+        total_income: income.iter().map(|r| r.fair_value).sum(),
+        total_proceeds: rows.iter().map(|r| r.proceeds).sum(),
+        total_cost_basis: rows.iter().map(|r| r.cost_basis).sum(),
+        total_gain_loss: rows.iter().map(|r| r.gain_loss).sum(),
+        income,
+        disposals: rows,
+        warnings,
+    })
+}
+
+/// First and last day of fiscal year `year` starting in `start_month`.
+fn fiscal_period(year: i32, start_month: u32) -> Result<(NaiveDate, NaiveDate), String> {
+    let start = NaiveDate::from_ymd_opt(year, start_month, 1)
+        .ok_or_else(|| format!("Invalid fiscal year start: {}-{}", year, start_month))?;
+    let next = NaiveDate::from_ymd_opt(year + 1, start_month, 1)
+        .ok_or_else(|| format!("Invalid fiscal year start: {}-{}", year + 1, start_month))?;
+    Ok((start, next.pred_opt().unwrap_or(next)))
+}
+
+fn amount(value: f64) -> String {
+    let formatted = format!("{:.8}", value);
+    let trimmed = formatted.trim_end_matches('0').trim_end_matches('.');
+    if trimmed.is_empty() || trimmed == "-0" {
+        "0".to_string()
+    } else {
+        trimmed.to_string()
+    }
+}
+
+fn money(value: f64) -> String {
+    format!("{:.2}", value)
+}
+
+fn generic_rows(report: &TaxReport) -> Vec<Vec<String>> {
+    let mut rows = vec![[
+        "type",
+        "date_received",
+        "date_disposed",
+        "token",
+        "amount",
+        "fair_value_at_receipt",
+        "proceeds",
+        "fee",
+        "cost_basis",
+        "gain_loss",
+        "holding_days",
+        "term",
+        "currency",
+        "airdrop",
+        "tx_hash",
+    ]
+    .iter()
+    .map(|h| h.to_string())
+    .collect()];
+
+    for row in &report.income {
+        rows.push(vec![
+            "income".to_string(),
+            row.received_at.format("%Y-%m-%d").to_string(),
+            String::new(),
+            row.token.clone(),
+            amount(row.amount),
+            money(row.fair_value),
+            String::new(),
+            String::new(),
+            String::new(),
+            String::new(),
+            String::new(),
+            String::new(),
+            row.currency.clone(),
+            row.airdrop_name.clone().unwrap_or_default(),
+            row.tx_hash.clone().unwrap_or_default(),
+        ]);
+    }
+    for row in &report.disposals {
+        rows.push(vec![
+            "disposal".to_string(),
+            row.received_at.map(|d| d.format("%Y-%m-%d").to_string()).unwrap_or_default(),
+            row.disposed_at.format("%Y-%m-%d").to_string(),
+            row.token.clone(),
+            amount(row.amount),
+            String::new(),
+            money(row.proceeds),
+            money(row.fee),
+            money(row.cost_basis),
+            money(row.gain_loss),
+            row.holding_days.map(|d| d.to_string()).unwrap_or_default(),
+            match row.long_term {
+                Some(true) => "long".to_string(),
+                Some(false) => "short".to_string(),
+                None => String::new(),
+            },
+            row.currency.clone(),
+            String::new(),
+            row.tx_hash.clone().unwrap_or_default(),
+        ]);
+    }
+    rows
+}
+
+/// One entry per sale, re-assembled from its lot rows; the import targets
+/// do their own lot matching.
+struct Sale {
+    disposed_at: DateTime<Utc>,
+    token: String,
+    amount: f64,
+    net_proceeds: f64,
+    fee: f64,
+    currency: String,
+    tx_hash: Option<String>,
+}
+
+fn sales(report: &TaxReport) -> Vec<Sale> {
+    let mut sales: Vec<Sale> = Vec::new();
+    let mut index: HashMap<i64, usize> = HashMap::new();
+    for row in &report.disposals {
+        match index.get(&row.disposal_id) {
+            Some(&i) => {
+                sales[i].amount += row.amount;
+                sales[i].net_proceeds += row.proceeds;
+                sales[i].fee += row.fee;
+            }
+            None => {
+                index.insert(row.disposal_id, sales.len());
+                sales.push(Sale {
+                    disposed_at: row.disposed_at,
+                    token: row.token.clone(),
+                    amount: row.amount,
+                    net_proceeds: row.proceeds,
+                    fee: row.fee,
+                    currency: row.currency.clone(),
+                    tx_hash: row.tx_hash.clone(),
+                });
+            }
+        }
+    }
+    sales
+}
+
+/// Koinly's "universal" CSV template.
+fn koinly_rows(report: &TaxReport) -> Vec<Vec<String>> {
+    let mut rows = vec![[
+        "Date",
+        "Sent Amount",
+        "Sent Currency",
+        "Received Amount",
+        "Received Currency",
+        "Fee Amount",
+        "Fee Currency",
+        "Net Worth Amount",
+        "Net Worth Currency",
+        "Label",
+        "Description",
+        "TxHash",
+    ]
+    .iter()
+    .map(|h| h.to_string())
+    .collect()];
+
+    for row in &report.income {
+        rows.push(vec![
+            row.received_at.format("%Y-%m-%d %H:%M UTC").to_string(),
+            String::new(),
+            String::new(),
+            amount(row.amount),
+            row.token.clone(),
+            String::new(),
+            String::new(),
+            money(row.fair_value),
+            row.currency.clone(),
+            "airdrop".to_string(),
+            row.airdrop_name.clone().unwrap_or_default(),
+            row.tx_hash.clone().unwrap_or_default(),
+        ]);
+    }
+    for sale in sales(report) {
+        rows.push(vec![
+            sale.disposed_at.format("%Y-%m-%d %H:%M UTC").to_string(),
+            amount(sale.amount),
+            sale.token.clone(),
+            money(sale.net_proceeds + sale.fee),
+            sale.currency.clone(),
+            if sale.fee > 0.0 { money(sale.fee) } else { String::new() },
+            if sale.fee > 0.0 { sale.currency.clone() } else { String::new() },
+            String::new(),
+            String::new(),
+            String::new(),
+            String::new(),
+            sale.tx_hash.unwrap_or_default(),
+        ]);
+    }
+    rows
+}
+
+/// CoinTracking's generic CSV import.
+fn cointracking_rows(report: &TaxReport) -> Vec<Vec<String>> {
+    let mut rows = vec![[
+        "Type",
+        "Buy Amount",
+        "Buy Currency",
+        "Sell Amount",
+        "Sell Currency",
+        "Fee",
+        "Fee Currency",
+        "Exchange",
+        "Trade-Group",
+        "Comment",
+        "Date",
+        "Tx-ID",
+    ]
+    .iter()
+    .map(|h| h.to_string())
+    .collect()];
+
+    for row in &report.income {
+        rows.push(vec![
+            "Airdrop".to_string(),
+            amount(row.amount),
+            row.token.clone(),
+            String::new(),
+            String::new(),
+            String::new(),
+            String::new(),
+            String::new(),
+            String::new(),
+            format!(
+                "{} (value {} {})",
+                row.airdrop_name.as_deref().unwrap_or("Airdrop claim"),
+                money(row.fair_value),
+                row.currency
+            ),
+            row.received_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+            row.tx_hash.clone().unwrap_or_default(),
+        ]);
+    }
+    for sale in sales(report) {
+        rows.push(vec![
+            "Trade".to_string(),
+            money(sale.net_proceeds + sale.fee),
+            sale.currency.clone(),
+            amount(sale.amount),
+            sale.token.clone(),
+            if sale.fee > 0.0 { money(sale.fee) } else { String::new() },
+            if sale.fee > 0.0 { sale.currency.clone() } else { String::new() },
+            String::new(),
+            String::new(),
+            String::new(),
+            sale.disposed_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+            sale.tx_hash.unwrap_or_default(),
+        ]);
+    }
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::memory_db;
+
+    async fn claim(db: &SqlitePool, amount: f64, fair_value: f64, received_at: &str) -> i64 {
+        sqlx::query("INSERT INTO airdrop_claims (token, amount, fair_value, currency, received_at, created_at) VALUES ('ARB', ?, ?, 'USD', ?, ?)")
+            .bind(amount)
+            .bind(fair_value)
+            .bind(received_at)
+            .bind(received_at)
+            .execute(db)
+            .await
+            .unwrap()
+            .last_insert_rowid()
+    }
+
+    async fn sale(db: &SqlitePool, amount: f64, proceeds: f64, fee: f64, disposed_at: &str) -> i64 {
+        sqlx::query("INSERT INTO token_disposals (token, amount, proceeds, fee, currency, disposed_at, tx_hash, created_at) VALUES ('ARB', ?, ?, ?, 'USD', ?, '0xabc', ?)")
+            .bind(amount)
+            .bind(proceeds)
+            .bind(fee)
+            .bind(disposed_at)
+            .bind(disposed_at)
+            .execute(db)
+            .await
+            .unwrap()
+            .last_insert_rowid()
+    }
+
+    async fn assign(db: &SqlitePool, disposal_id: i64, claim_id: i64, amount: f64) {
+        sqlx::query("INSERT INTO disposal_lots (disposal_id, claim_id, amount) VALUES (?, ?, ?)")
+            .bind(disposal_id)
+            .bind(claim_id)
+            .bind(amount)
+            .execute(db)
+            .await
+            .unwrap();
+    }
+
+    fn request(method: LotMethod) -> TaxReportRequest {
+        TaxReportRequest { year: 2024, method, fiscal_year_start_month: None, currency: None }
+    }
+
+    fn matched(report: &TaxReport) -> Vec<(i64, Option<i64>, f64, f64)> {
+        report
+            .disposals
+            .iter()
+            .map(|r| (r.disposal_id, r.claim_id, r.amount, r.cost_basis))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn fifo_matches_oldest_claims_first() {
+        let db = memory_db().await;
+        let first = claim(&db, 10.0, 100.0, "2024-01-01T00:00:00Z").await;
+        let second = claim(&db, 10.0, 200.0, "2024-02-01T00:00:00Z").await;
+        let sold = sale(&db, 15.0, 600.0, 0.0, "2024-03-01T00:00:00Z").await;
+
+        let report = build_tax_report(&db, &request(LotMethod::Fifo)).await.unwrap();
+
+        assert_eq!(matched(&report), vec![(sold, Some(first), 10.0, 100.0), (sold, Some(second), 5.0, 100.0)]);
+        assert_eq!(report.total_income, 300.0);
+        assert_eq!(report.total_proceeds, 600.0);
+        assert_eq!(report.total_gain_loss, 400.0);
+        assert!(report.warnings.is_empty(), "{:?}", report.warnings);
+    }
+
+    #[tokio::test]
+    async fn fifo_reports_uncovered_amounts_at_zero_basis() {
+        let db = memory_db().await;
+        claim(&db, 1.0, 10.0, "2024-01-01T00:00:00Z").await;
+        let sold = sale(&db, 3.0, 30.0, 3.0, "2024-03-01T00:00:00Z").await;
+
+        let report = build_tax_report(&db, &request(LotMethod::Fifo)).await.unwrap();
+
+        let uncovered = &report.disposals[1];
+        assert_eq!((uncovered.disposal_id, uncovered.claim_id, uncovered.amount), (sold, None, 2.0));
+        assert_eq!(uncovered.fee, 2.0);
+        assert_eq!(uncovered.proceeds, 18.0);
+        assert_eq!(report.warnings.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn specific_id_keeps_lots_reserved_for_later_sales() {
+        let db = memory_db().await;
+        let chosen = claim(&db, 10.0, 100.0, "2024-01-01T00:00:00Z").await;
+        let other = claim(&db, 10.0, 300.0, "2024-02-01T00:00:00Z").await;
+        // The earlier sale has no assignment and falls back to FIFO
+        let early = sale(&db, 5.0, 250.0, 0.0, "2024-03-01T00:00:00Z").await;
+        let late = sale(&db, 10.0, 500.0, 0.0, "2024-04-01T00:00:00Z").await;
+        assign(&db, late, chosen, 10.0).await;
+
+        let report = build_tax_report(&db, &request(LotMethod::SpecificId)).await.unwrap();
+
+        assert_eq!(matched(&report), vec![(early, Some(other), 5.0, 150.0), (late, Some(chosen), 10.0, 100.0)]);
+        assert!(report.warnings.is_empty(), "{:?}", report.warnings);
+
+        // FIFO ignores the assignment
+        let fifo = build_tax_report(&db, &request(LotMethod::Fifo)).await.unwrap();
+        assert_eq!(
+            matched(&fifo),
+            vec![(early, Some(chosen), 5.0, 50.0), (late, Some(chosen), 5.0, 50.0), (late, Some(other), 5.0, 150.0)]
+        );
+    }
+
+    #[tokio::test]
+    async fn specific_id_falls_back_to_fifo_for_unassigned_rest() {
+        let db = memory_db().await;
+        let first = claim(&db, 10.0, 100.0, "2024-01-01T00:00:00Z").await;
+        let second = claim(&db, 10.0, 200.0, "2024-02-01T00:00:00Z").await;
+        let sold = sale(&db, 12.0, 600.0, 0.0, "2024-03-01T00:00:00Z").await;
+        assign(&db, sold, second, 8.0).await;
+
+        let report = build_tax_report(&db, &request(LotMethod::SpecificId)).await.unwrap();
+
+        assert_eq!(matched(&report), vec![(sold, Some(second), 8.0, 160.0), (sold, Some(first), 4.0, 40.0)]);
+    }
+
+    #[tokio::test]
+    async fn only_rows_inside_the_fiscal_year_are_reported() {
+        let db = memory_db().await;
+        claim(&db, 10.0, 100.0, "2023-06-01T00:00:00Z").await;
+        claim(&db, 10.0, 200.0, "2024-06-01T00:00:00Z").await;
+        sale(&db, 10.0, 150.0, 0.0, "2023-12-01T00:00:00Z").await;
+        let sold = sale(&db, 5.0, 150.0, 0.0, "2024-07-01T00:00:00Z").await;
+
+        let report = build_tax_report(&db, &request(LotMethod::Fifo)).await.unwrap();
+
+        assert_eq!(report.income.len(), 1);
+        // The 2023 sale used up the 2023 claim
+        assert_eq!(report.disposals.len(), 1);
+        assert_eq!((report.disposals[0].disposal_id, report.disposals[0].cost_basis), (sold, 100.0));
+    }
+
+    async fn layout_report() -> TaxReport {
+        let db = memory_db().await;
+        claim(&db, 10.0, 100.0, "2024-01-01T08:30:00Z").await;
+        claim(&db, 10.0, 200.0, "2024-02-01T08:30:00Z").await;
+        sale(&db, 15.0, 600.0, 6.0, "2024-03-01T12:00:00Z").await;
+        build_tax_report(&db, &request(LotMethod::Fifo)).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn koinly_layout_has_one_row_per_sale() {
+        let rows = koinly_rows(&layout_report().await);
+
+        assert_eq!(rows[0][0], "Date");
+        assert_eq!(rows[0][9], "Label");
+        assert_eq!(rows.len(), 4);
+        assert_eq!(rows[1], vec!["2024-01-01 08:30 UTC", "", "", "10", "ARB", "", "", "100.00", "USD", "airdrop", "", ""]);
+        assert_eq!(
+            rows[3],
+            vec!["2024-03-01 12:00 UTC", "15", "ARB", "600.00", "USD", "6.00", "USD", "", "", "", "", "0xabc"]
+        );
+    }
+
+    #[tokio::test]
+    async fn cointracking_layout_has_one_row_per_sale() {
+        let rows = cointracking_rows(&layout_report().await);
+
+        assert_eq!(rows[0][0], "Type");
+        assert_eq!(rows.len(), 4);
+        assert_eq!(rows[1][0], "Airdrop");
+        assert_eq!(rows[1][9], "Airdrop claim (value 100.00 USD)");
+        assert_eq!(rows[1][10], "2024-01-01 08:30:00");
+        assert_eq!(
+            rows[3],
+            vec!["Trade", "600.00", "USD", "15", "ARB", "6.00", "USD", "", "", "", "2024-03-01 12:00:00", "0xabc"]
+        );
+    }
+
+    #[tokio::test]
+    async fn generic_layout_has_one_row_per_lot() {
+        let rows = generic_rows(&layout_report().await);
+
+        assert_eq!(rows.len(), 5);
+        assert_eq!(rows[3][0], "disposal");
+        assert_eq!(&rows[3][4..12], &["10", "", "396.00", "4.00", "100.00", "296.00", "60", "short"]);
+    }
+}
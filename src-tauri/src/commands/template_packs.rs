@@ -0,0 +1,514 @@
+use log::info;
+use tauri::State;
+use sqlx::SqlitePool;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+use crate::commands::airdrops::{default_task_entries, default_task_titles, validate_reset_policy};
+use crate::models::{Airdrop, AirdropDailyTask, AirdropType};
+
+/// Identifies pack files; bump `PACK_FORMAT_VERSION` on breaking changes.
+const PACK_FORMAT: &str = "praetor-desk/airdrop-types";
+const PACK_FORMAT_VERSION: u32 = 1;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TemplatePack {
+    pub format: String,
+    pub format_version: u32,
+    pub exported_at: DateTime<Utc>,
+    pub types: Vec<TemplatePackType>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TemplatePackType {
+    pub name: String,
+    #[serde(default = "default_template_version")]
+    pub version: i64,
+    #[serde(default = "default_reset_policy")]
+    pub reset_policy: String,
+    #[serde(default)]
+    pub recommended_chains: Vec<String>,
+    #[serde(default)]
+    pub default_tasks: serde_json::Value,
+}
+
+fn default_template_version() -> i64 {
+    1
+}
+
+fn default_reset_policy() -> String {
+    "daily".to_string()
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TemplateConflictPolicy {
+    /// Import as a new type under a free name
+    Rename,
+    /// Add the pack's missing tasks and chains to the existing type
+    Merge,
+    /// Leave the existing type alone
+    Skip,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ImportTemplatePackRequest {
+    pub content: String,
+    /// Applies to every type whose name already exists
+    pub on_conflict: TemplateConflictPolicy,
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TemplateImportAction {
+    Created,
+    Renamed,
+    Merged,
+    Unchanged,
+    Skipped,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TemplateImportResult {
+    pub name: String,
+    pub action: TemplateImportAction,
+    pub airdrop_type_id: Option<i64>,
+    /// Final name when the type was renamed
+    pub imported_as: Option<String>,
+    pub added_tasks: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TemplateSyncResult {
+    pub airdrop_id: i64,
+    pub airdrop_name: String,
+    pub from_version: Option<i64>,
+    pub to_version: i64,
+    pub added_tasks: Vec<String>,
+}
+
+/// Serializes the given types (or all types) as a portable JSON pack.
+#[tauri::command]
+pub async fn export_template_pack(
+    state: State<'_, crate::AppState>,
+    type_ids: Option<Vec<i64>>,
+) -> Result<String, String> {
+    let mut types = sqlx::query_as::<_, AirdropType>("SELECT * FROM airdrop_types ORDER BY name ASC")
+        .fetch_all(&state.db)
+        .await
+        .map_err(|e| format!("Failed to fetch airdrop types: {}", e))?;
+    if let Some(ids) = &type_ids {
+        types.retain(|t| ids.contains(&t.id));
+    }
+
+    let pack = TemplatePack {
+        format: PACK_FORMAT.to_string(),
+        format_version: PACK_FORMAT_VERSION,
+        exported_at: Utc::now(),
+        types: types
+            .into_iter()
+            .map(|t| TemplatePackType {
+                name: t.name,
+                version: t.version,
+                reset_policy: t.reset_policy,
+                recommended_chains: t.recommended_chains,
+                default_tasks: t.default_tasks,
+            })
+            .collect(),
+    };
+
+    info!("phase=template_pack_export_ok types={}", pack.types.len());
+    serde_json::to_string_pretty(&pack).map_err(|e| format!("Failed to serialize pack: {}", e))
+}
+
+#[tauri::command]
+pub async fn import_template_pack(
+    state: State<'_, crate::AppState>,
+    req: ImportTemplatePackRequest,
+) -> Result<Vec<TemplateImportResult>, String> {
+    import_pack(&state.db, &req).await
+}
+
+pub(crate) async fn import_pack(
+    db: &SqlitePool,
+    req: &ImportTemplatePackRequest,
+) -> Result<Vec<TemplateImportResult>, String> {
+    let pack: TemplatePack =
+        serde_json::from_str(&req.content).map_err(|e| format!("Invalid template pack: {}", e))?;
+    if pack.format != PACK_FORMAT {
+        return Err(format!("Not an airdrop type pack: {}", pack.format));
+    }
+    if pack.format_version > PACK_FORMAT_VERSION {
+        return Err(format!(
+            "Pack format version {} is newer than this app supports",
+            pack.format_version
+        ));
+    }
+
+    let mut tx = db
+        .begin()
+        .await
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+
+    // Kept up to date as types merge, so a type listed twice merges twice
+    let mut existing = sqlx::query_as::<_, AirdropType>("SELECT * FROM airdrop_types")
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to fetch airdrop types: {}", e))?;
+    let mut taken: HashSet<String> = existing.iter().map(|t| t.name.to_lowercase()).collect();
+
+    let now = Utc::now().to_rfc3339();
+    let mut results = Vec::with_capacity(pack.types.len());
+    for incoming in pack.types {
+        let reset_policy = validate_reset_policy(&incoming.reset_policy)?;
+        let entries = default_task_entries(&incoming.default_tasks);
+        let current = existing
+            .iter_mut()
+            .find(|t| t.name.eq_ignore_ascii_case(incoming.name.trim()));
+
+        let (name, renamed) = match (current, req.on_conflict) {
+            (Some(current), TemplateConflictPolicy::Skip) => {
+                results.push(TemplateImportResult {
+                    name: incoming.name,
+                    action: TemplateImportAction::Skipped,
+                    airdrop_type_id: Some(current.id),
+                    imported_as: None,
+                    added_tasks: Vec::new(),
+                });
+                continue;
+            }
+            (Some(current), TemplateConflictPolicy::Merge) => {
+                // Whole entries are kept so task fields other than the title survive
+                let mut merged_tasks: Vec<serde_json::Value> =
+                    current.default_tasks.as_array().cloned().unwrap_or_default();
+                let mut seen: HashSet<String> = default_task_titles(&current.default_tasks)
+                    .iter()
+                    .map(|t| t.to_lowercase())
+                    .collect();
+                let mut added = Vec::new();
+                for (title, entry) in entries {
+                    if seen.insert(title.to_lowercase()) {
+                        merged_tasks.push(entry.clone());
+                        added.push(title);
+                    }
+                }
+
+                let mut chains = current.recommended_chains.clone();
+                for chain in incoming.recommended_chains {
+                    if !chains.iter().any(|c| c.eq_ignore_ascii_case(&chain)) {
+                        chains.push(chain);
+                    }
+                }
+
+                // Local edits may have moved past the pack's version already.
+                let version = if added.is_empty() {
+                    current.version
+                } else {
+                    (current.version + 1).max(incoming.version)
+                };
+                let merged_tasks = serde_json::Value::Array(merged_tasks);
+                sqlx::query(
+                    "UPDATE airdrop_types SET default_tasks = ?, recommended_chains = ?, version = ?, updated_at = ? WHERE id = ?",
+                )
+                .bind(merged_tasks.to_string())
+                .bind(serde_json::to_string(&chains).unwrap())
+                .bind(version)
+                .bind(&now)
+                .bind(current.id)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| format!("Failed to merge {}: {}", current.name, e))?;
+                current.default_tasks = merged_tasks;
+                current.recommended_chains = chains;
+                current.version = version;
+
+                results.push(TemplateImportResult {
+                    name: incoming.name,
+                    action: if added.is_empty() {
+                        TemplateImportAction::Unchanged
+                    } else {
+                        TemplateImportAction::Merged
+                    },
+                    airdrop_type_id: Some(current.id),
+                    imported_as: None,
+                    added_tasks: added,
+                });
+                continue;
+            }
+            (Some(_), TemplateConflictPolicy::Rename) => {
+                let base = incoming.name.trim().to_string();
+                let name = (2..)
+                    .map(|n| format!("{} ({})", base, n))
+                    .find(|candidate| !taken.contains(&candidate.to_lowercase()))
+                    .unwrap();
+                (name, true)
+            }
+            // The same name twice within one pack: keep the first.
+            (None, _) if taken.contains(&incoming.name.trim().to_lowercase()) => {
+                results.push(TemplateImportResult {
+                    name: incoming.name,
+                    action: TemplateImportAction::Skipped,
+                    airdrop_type_id: None,
+                    imported_as: None,
+                    added_tasks: Vec::new(),
+                });
+                continue;
+            }
+            (None, _) => (incoming.name.trim().to_string(), false),
+        };
+
+        let tasks: Vec<&serde_json::Value> = entries.iter().map(|(_, entry)| *entry).collect();
+        let id = sqlx::query(
+            r#"
+            INSERT INTO airdrop_types (name, default_tasks, reset_policy, recommended_chains, version, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(&name)
+        .bind(serde_json::to_string(&tasks).unwrap())
+        .bind(reset_policy)
+        .bind(serde_json::to_string(&incoming.recommended_chains).unwrap())
+        .bind(incoming.version.max(1))
+        .bind(&now)
+        .bind(&now)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to import {}: {}", name, e))?
+        .last_insert_rowid();
+        taken.insert(name.to_lowercase());
+
+        results.push(TemplateImportResult {
+            name: incoming.name,
+            action: if renamed {
+                TemplateImportAction::Renamed
+            } else {
+                TemplateImportAction::Created
+            },
+            airdrop_type_id: (!req.dry_run).then_some(id),
+            imported_as: renamed.then_some(name),
+            added_tasks: entries.into_iter().map(|(title, _)| title).collect(),
+        });
+    }
+
+    if req.dry_run {
+        tx.rollback()
+            .await
+            .map_err(|e| format!("Failed to roll back dry run: {}", e))?;
+    } else {
+        tx.commit()
+            .await
+            .map_err(|e| format!("Failed to import template pack: {}", e))?;
+    }
+
+    info!(
+        "phase=template_pack_import_done types={} dry_run={}",
+        results.len(),
+        req.dry_run
+    );
+    Ok(results)
+}
+
+/// Adds template tasks that airdrops behind their type's version are
+/// missing. Tasks are matched by title, so nothing is duplicated.
+#[tauri::command]
+pub async fn sync_template_tasks(
+    state: State<'_, crate::AppState>,
+    airdrop_id: Option<i64>,
+    dry_run: bool,
+) -> Result<Vec<TemplateSyncResult>, String> {
+    let mut airdrops = sqlx::query_as::<_, Airdrop>(
+        r#"
+        SELECT a.* FROM airdrops a
+        JOIN airdrop_types t ON t.id = a.airdrop_type_id
+        WHERE a.template_version IS NULL OR a.template_version < t.version
+        ORDER BY a.position ASC
+        "#,
+    )
+    .fetch_all(&state.db)
+    .await
+    .map_err(|e| format!("Failed to fetch airdrops: {}", e))?;
+    if let Some(id) = airdrop_id {
+        airdrops.retain(|a| a.id == id);
+    }
+
+    let types = sqlx::query_as::<_, AirdropType>("SELECT * FROM airdrop_types")
+        .fetch_all(&state.db)
+        .await
+        .map_err(|e| format!("Failed to fetch airdrop types: {}", e))?;
+
+    let mut tx = state
+        .db
+        .begin()
+        .await
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+
+    let now = Utc::now().to_rfc3339();
+    let mut results = Vec::with_capacity(airdrops.len());
+    for airdrop in airdrops {
+        let Some(airdrop_type) = types.iter().find(|t| Some(t.id) == airdrop.airdrop_type_id) else {
+            continue;
+        };
+
+        let tasks = sqlx::query_as::<_, AirdropDailyTask>(
+            "SELECT * FROM airdrop_daily_tasks WHERE airdrop_id = ?",
+        )
+        .bind(airdrop.id)
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to fetch daily tasks: {}", e))?;
+        let mut seen: HashSet<String> = tasks.iter().map(|t| t.title.trim().to_lowercase()).collect();
+        let next_order = tasks.iter().map(|t| t.order).max().map_or(0, |o| o + 1);
+
+        let added: Vec<String> = default_task_titles(&airdrop_type.default_tasks)
+            .into_iter()
+            .filter(|title| seen.insert(title.to_lowercase()))
+            .collect();
+        for (offset, title) in added.iter().enumerate() {
+            sqlx::query(
+                r#"
+                INSERT INTO airdrop_daily_tasks (airdrop_id, title, "order", done_dates, created_at, updated_at)
+                VALUES (?, ?, ?, '[]', ?, ?)
+                "#,
+            )
+            .bind(airdrop.id)
+            .bind(title)
+            .bind(next_order + offset as i64)
+            .bind(&now)
+            .bind(&now)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| format!("Failed to add template task: {}", e))?;
+        }
+
+        sqlx::query("UPDATE airdrops SET template_version = ? WHERE id = ?")
+            .bind(airdrop_type.version)
+            .bind(airdrop.id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| format!("Failed to update template version: {}", e))?;
+
+        results.push(TemplateSyncResult {
+            airdrop_id: airdrop.id,
+            airdrop_name: airdrop.name,
+            from_version: airdrop.template_version,
+            to_version: airdrop_type.version,
+            added_tasks: added,
+        });
+    }
+
+    if dry_run {
+        tx.rollback()
+            .await
+            .map_err(|e| format!("Failed to roll back dry run: {}", e))?;
+    } else {
+        tx.commit()
+            .await
+            .map_err(|e| format!("Failed to sync template tasks: {}", e))?;
+    }
+
+    info!(
+        "phase=template_sync_done airdrops={} dry_run={}",
+        results.len(),
+        dry_run
+    );
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::memory_db;
+    use serde_json::json;
+
+    async fn seed_type(db: &SqlitePool, name: &str, default_tasks: serde_json::Value) -> i64 {
+        let now = Utc::now().to_rfc3339();
+        sqlx::query("INSERT INTO airdrop_types (name, default_tasks, version, created_at, updated_at) VALUES (?, ?, 1, ?, ?)")
+            .bind(name)
+            .bind(default_tasks.to_string())
+            .bind(&now)
+            .bind(&now)
+            .execute(db)
+            .await
+            .unwrap()
+            .last_insert_rowid()
+    }
+
+    async fn fetch_type(db: &SqlitePool, id: i64) -> AirdropType {
+        sqlx::query_as::<_, AirdropType>("SELECT * FROM airdrop_types WHERE id = ?")
+            .bind(id)
+            .fetch_one(db)
+            .await
+            .unwrap()
+    }
+
+    fn import(types: serde_json::Value, on_conflict: TemplateConflictPolicy, dry_run: bool) -> ImportTemplatePackRequest {
+        let pack = json!({
+            "format": PACK_FORMAT,
+            "format_version": PACK_FORMAT_VERSION,
+            "exported_at": "2024-05-01T00:00:00Z",
+            "types": types,
+        });
+        ImportTemplatePackRequest { content: pack.to_string(), on_conflict, dry_run }
+    }
+
+    #[tokio::test]
+    async fn merging_keeps_task_objects() {
+        let db = memory_db().await;
+        let id = seed_type(&db, "L2", json!([{ "title": "Bridge", "url": "https://bridge.example" }])).await;
+        let req = import(
+            json!([{ "name": "l2", "default_tasks": ["bridge", { "title": "Swap", "minutes": 5 }] }]),
+            TemplateConflictPolicy::Merge,
+            false,
+        );
+
+        let results = import_pack(&db, &req).await.unwrap();
+
+        assert_eq!(results[0].added_tasks, vec!["Swap"]);
+        let merged = fetch_type(&db, id).await;
+        assert_eq!(
+            merged.default_tasks,
+            json!([{ "title": "Bridge", "url": "https://bridge.example" }, { "title": "Swap", "minutes": 5 }])
+        );
+        assert_eq!(merged.version, 2);
+    }
+
+    #[tokio::test]
+    async fn a_type_listed_twice_merges_both_times() {
+        let db = memory_db().await;
+        let id = seed_type(&db, "L2", json!(["Bridge"])).await;
+        let req = import(
+            json!([
+                { "name": "L2", "recommended_chains": ["base"], "default_tasks": ["Swap"] },
+                { "name": "L2", "recommended_chains": ["zksync"], "default_tasks": ["Swap", "Lend"] },
+            ]),
+            TemplateConflictPolicy::Merge,
+            false,
+        );
+
+        let results = import_pack(&db, &req).await.unwrap();
+
+        assert_eq!(results[0].added_tasks, vec!["Swap"]);
+        assert_eq!(results[1].added_tasks, vec!["Lend"]);
+        let merged = fetch_type(&db, id).await;
+        assert_eq!(merged.default_tasks, json!(["Bridge", "Swap", "Lend"]));
+        assert_eq!(merged.recommended_chains, vec!["base", "zksync"]);
+        assert_eq!(merged.version, 3);
+    }
+
+    #[tokio::test]
+    async fn new_types_keep_task_objects_and_dry_runs_write_nothing() {
+        let db = memory_db().await;
+        let types = json!([{ "name": "Testnet", "default_tasks": [{ "title": "Faucet", "url": "https://faucet.example" }, { "note": "untitled" }] }]);
+
+        let preview = import_pack(&db, &import(types.clone(), TemplateConflictPolicy::Skip, true)).await.unwrap();
+        assert_eq!(preview[0].airdrop_type_id, None);
+        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM airdrop_types").fetch_one(&db).await.unwrap();
+        assert_eq!(count, 0);
+
+        let results = import_pack(&db, &import(types, TemplateConflictPolicy::Skip, false)).await.unwrap();
+        let created = fetch_type(&db, results[0].airdrop_type_id.unwrap()).await;
+        assert_eq!(created.default_tasks, json!([{ "title": "Faucet", "url": "https://faucet.example" }]));
+    }
+}
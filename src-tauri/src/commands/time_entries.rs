@@ -0,0 +1,580 @@
+use log::{info, warn};
+use tauri::{AppHandle, Emitter, State};
+use sqlx::{Sqlite, SqliteExecutor, SqlitePool, Transaction};
+use chrono::{DateTime, Datelike, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use crate::commands::claims::parse_date_or_datetime;
+use crate::models::TimeEntry;
+
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Deserialize)]
+pub struct StartTimeEntryRequest {
+    /// Derived from the task when omitted
+    pub project_id: Option<i64>,
+    pub task_id: Option<i64>,
+    pub notes: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateTimeEntryRequest {
+    pub project_id: Option<i64>,
+    pub task_id: Option<i64>,
+    pub started_at: String,
+    pub ended_at: String,
+    pub notes: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateTimeEntryRequest {
+    pub id: i64,
+    pub started_at: Option<String>,
+    /// Only for stopped entries
+    pub ended_at: Option<String>,
+    pub notes: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct TimeEntryFilter {
+    pub project_id: Option<i64>,
+    pub task_id: Option<i64>,
+    pub from: Option<String>,
+    pub to: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TimeReportGroup {
+    Project,
+    Task,
+    Day,
+    Week,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TimeReportRequest {
+    pub group_by: TimeReportGroup,
+    #[serde(flatten)]
+    pub filter: TimeEntryFilter,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TimeReportRow {
+    /// Project or task id, `YYYY-MM-DD` or ISO week `YYYY-Www`
+    pub key: String,
+    pub label: String,
+    pub seconds: i64,
+    pub entries: i64,
+}
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct EstimateComparison {
+    pub task_id: i64,
+    pub task_title: String,
+    pub project_id: i64,
+    pub project_name: String,
+    pub done: bool,
+    pub estimated_minutes: Option<i64>,
+    pub actual_minutes: f64,
+}
+
+/// Starts a timer for a project or one of its tasks. A timer that is already
+/// running is stopped first, so at most one runs at a time.
+#[tauri::command]
+pub async fn start_time_entry(
+    state: State<'_, crate::AppState>,
+    req: StartTimeEntryRequest,
+) -> Result<TimeEntry, String> {
+    start_entry(&state.db, &req).await
+}
+
+pub(crate) async fn start_entry(db: &SqlitePool, req: &StartTimeEntryRequest) -> Result<TimeEntry, String> {
+    let project_id = resolve_project(db, req.project_id, req.task_id).await?;
+
+    // Stop and start together; the one-running-timer index rejects a racing start
+    let mut tx = db
+        .begin()
+        .await
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+    close_running(&mut tx, "manual").await?;
+
+    let now = Utc::now();
+    let id = sqlx::query(
+        r#"
+        INSERT INTO time_entries (project_id, task_id, started_at, notes, source, heartbeat_at, created_at, updated_at)
+        VALUES (?, ?, ?, ?, 'timer', ?, ?, ?)
+        "#,
+    )
+    .bind(project_id)
+    .bind(req.task_id)
+    .bind(now.to_rfc3339())
+    .bind(&req.notes)
+    .bind(now.to_rfc3339())
+    .bind(now.to_rfc3339())
+    .bind(now.to_rfc3339())
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| format!("Failed to start timer: {}", e))?
+    .last_insert_rowid();
+
+    tx.commit()
+        .await
+        .map_err(|e| format!("Failed to commit transaction: {}", e))?;
+
+    info!("phase=timer_start_ok id={} project={} task={:?}", id, project_id, req.task_id);
+    fetch_entry(db, id).await
+}
+
+/// Stops the running timer, if any, and returns it.
+#[tauri::command]
+pub async fn stop_time_entry(state: State<'_, crate::AppState>) -> Result<Option<TimeEntry>, String> {
+    stop_running_entry(&state.db, "manual").await
+}
+
+#[tauri::command]
+pub async fn get_running_time_entry(state: State<'_, crate::AppState>) -> Result<Option<TimeEntry>, String> {
+    sqlx::query_as::<_, TimeEntry>("SELECT * FROM time_entries WHERE ended_at IS NULL ORDER BY started_at DESC LIMIT 1")
+        .fetch_optional(&state.db)
+        .await
+        .map_err(|e| format!("Failed to fetch running timer: {}", e))
+}
+
+#[tauri::command]
+pub async fn list_time_entries(
+    state: State<'_, crate::AppState>,
+    filter: Option<TimeEntryFilter>,
+) -> Result<Vec<TimeEntry>, String> {
+    fetch_entries(&state.db, &filter.unwrap_or_default()).await
+}
+
+/// Adds a finished entry by hand, e.g. for work done away from the app.
+#[tauri::command]
+pub async fn create_time_entry(
+    state: State<'_, crate::AppState>,
+    req: CreateTimeEntryRequest,
+) -> Result<i64, String> {
+    let project_id = resolve_project(&state.db, req.project_id, req.task_id).await?;
+    let started_at = parse_date_or_datetime(&req.started_at)?;
+    let ended_at = parse_date_or_datetime(&req.ended_at)?;
+    let duration = duration_seconds(started_at, ended_at)?;
+
+    let now = Utc::now();
+    let id = sqlx::query(
+        r#"
+        INSERT INTO time_entries (
+            project_id, task_id, started_at, ended_at, duration_seconds, notes, source, created_at, updated_at
+        )
+        VALUES (?, ?, ?, ?, ?, ?, 'manual', ?, ?)
+        "#,
+    )
+    .bind(project_id)
+    .bind(req.task_id)
+    .bind(started_at.to_rfc3339())
+    .bind(ended_at.to_rfc3339())
+    .bind(duration)
+    .bind(&req.notes)
+    .bind(now.to_rfc3339())
+    .bind(now.to_rfc3339())
+    .execute(&state.db)
+    .await
+    .map_err(|e| format!("Failed to create time entry: {}", e))?
+    .last_insert_rowid();
+
+    Ok(id)
+}
+
+#[tauri::command]
+pub async fn update_time_entry(
+    state: State<'_, crate::AppState>,
+    req: UpdateTimeEntryRequest,
+) -> Result<(), String> {
+    let entry = fetch_entry(&state.db, req.id).await?;
+    let started_at = match &req.started_at {
+        Some(raw) => parse_date_or_datetime(raw)?,
+        None => entry.started_at,
+    };
+    let ended_at = match &req.ended_at {
+        Some(_) if entry.ended_at.is_none() => {
+            return Err("Stop the running timer before editing its end time".to_string())
+        }
+        Some(raw) => Some(parse_date_or_datetime(raw)?),
+        None => entry.ended_at,
+    };
+    let duration = ended_at
+        .map(|ended_at| duration_seconds(started_at, ended_at))
+        .transpose()?;
+
+    sqlx::query(
+        r#"
+        UPDATE time_entries
+        SET started_at = ?, ended_at = ?, duration_seconds = ?, notes = COALESCE(?, notes), updated_at = ?
+        WHERE id = ?
+        "#,
+    )
+    .bind(started_at.to_rfc3339())
+    .bind(ended_at.map(|e| e.to_rfc3339()))
+    .bind(duration)
+    .bind(&req.notes)
+    .bind(Utc::now().to_rfc3339())
+    .bind(req.id)
+    .execute(&state.db)
+    .await
+    .map_err(|e| format!("Failed to update time entry: {}", e))?;
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn delete_time_entry(state: State<'_, crate::AppState>, id: i64) -> Result<(), String> {
+    sqlx::query("DELETE FROM time_entries WHERE id = ?")
+        .bind(id)
+        .execute(&state.db)
+        .await
+        .map_err(|e| format!("Failed to delete time entry: {}", e))?;
+
+    Ok(())
+}
+
+/// Tracked time of stopped entries grouped by project, task, day or ISO week.
+/// Entries count toward the day they started on.
+#[tauri::command]
+pub async fn time_report(
+    state: State<'_, crate::AppState>,
+    req: TimeReportRequest,
+) -> Result<Vec<TimeReportRow>, String> {
+    let entries = fetch_entries(&state.db, &req.filter).await?;
+
+    let projects: BTreeMap<i64, String> = sqlx::query_as::<_, (i64, String)>("SELECT id, name FROM projects")
+        .fetch_all(&state.db)
+        .await
+        .map_err(|e| format!("Failed to fetch projects: {}", e))?
+        .into_iter()
+        .collect();
+    let tasks: BTreeMap<i64, String> = sqlx::query_as::<_, (i64, String)>("SELECT id, title FROM project_tasks")
+        .fetch_all(&state.db)
+        .await
+        .map_err(|e| format!("Failed to fetch project tasks: {}", e))?
+        .into_iter()
+        .collect();
+
+    let mut groups: BTreeMap<String, TimeReportRow> = BTreeMap::new();
+    for entry in entries {
+        let Some(seconds) = entry.duration_seconds else {
+            continue;
+        };
+        let (key, label) = match req.group_by {
+            TimeReportGroup::Project => (
+                entry.project_id.to_string(),
+                projects.get(&entry.project_id).cloned().unwrap_or_default(),
+            ),
+            TimeReportGroup::Task => match entry.task_id {
+                Some(task_id) => (
+                    task_id.to_string(),
+                    tasks.get(&task_id).cloned().unwrap_or_default(),
+                ),
+                None => (
+                    format!("project-{}", entry.project_id),
+                    format!(
+                        "{} (no task)",
+                        projects.get(&entry.project_id).cloned().unwrap_or_default()
+                    ),
+                ),
+            },
+            TimeReportGroup::Day => {
+                let day = entry.started_at.format("%Y-%m-%d").to_string();
+                (day.clone(), day)
+            }
+            TimeReportGroup::Week => {
+                let week = entry.started_at.iso_week();
+                let key = format!("{}-W{:02}", week.year(), week.week());
+                (key.clone(), key)
+            }
+        };
+        let row = groups.entry(key.clone()).or_insert(TimeReportRow {
+            key,
+            label,
+            seconds: 0,
+            entries: 0,
+        });
+        row.seconds += seconds;
+        row.entries += 1;
+    }
+
+    Ok(groups.into_values().collect())
+}
+
+/// Estimated against tracked minutes for tasks that have either.
+#[tauri::command]
+pub async fn estimate_vs_actual(
+    state: State<'_, crate::AppState>,
+    project_id: Option<i64>,
+) -> Result<Vec<EstimateComparison>, String> {
+    sqlx::query_as::<_, EstimateComparison>(
+        r#"
+        SELECT t.id AS task_id, t.title AS task_title, p.id AS project_id, p.name AS project_name,
+               t.done AS done, t.estimated_minutes AS estimated_minutes,
+               COALESCE(SUM(e.duration_seconds), 0) / 60.0 AS actual_minutes
+        FROM project_tasks t
+        JOIN projects p ON p.id = t.project_id
+        LEFT JOIN time_entries e ON e.task_id = t.id AND e.duration_seconds IS NOT NULL
+        WHERE (? IS NULL OR t.project_id = ?)
+        GROUP BY t.id
+        HAVING t.estimated_minutes IS NOT NULL OR COUNT(e.id) > 0
+        ORDER BY p.name ASC, t."order" ASC
+        "#,
+    )
+    .bind(project_id)
+    .bind(project_id)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|e| format!("Failed to compare estimates: {}", e))
+}
+
+/// Closes the running timer at the current time.
+pub(crate) async fn stop_running_entry(db: &SqlitePool, reason: &str) -> Result<Option<TimeEntry>, String> {
+    let mut tx = db
+        .begin()
+        .await
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+    let stopped = close_running(&mut tx, reason).await?;
+    tx.commit()
+        .await
+        .map_err(|e| format!("Failed to commit transaction: {}", e))?;
+
+    match stopped {
+        Some(id) => Ok(Some(fetch_entry(db, id).await?)),
+        None => Ok(None),
+    }
+}
+
+/// Closes the running timer at the current time and returns its id.
+async fn close_running(tx: &mut Transaction<'_, Sqlite>, reason: &str) -> Result<Option<i64>, String> {
+    let running = sqlx::query_as::<_, TimeEntry>("SELECT * FROM time_entries WHERE ended_at IS NULL")
+        .fetch_optional(&mut **tx)
+        .await
+        .map_err(|e| format!("Failed to fetch running timer: {}", e))?;
+    let Some(entry) = running else {
+        return Ok(None);
+    };
+
+    close_entry(&mut **tx, entry.id, entry.started_at, Utc::now(), reason).await?;
+    info!("phase=timer_stop_ok id={} reason={}", entry.id, reason);
+    Ok(Some(entry.id))
+}
+
+/// Announces timers closed by `recover_entries` with `time-entry-recovered`
+/// so the UI can offer to resume, then keeps the heartbeat of the running
+/// timer fresh.
+pub(crate) fn spawn_time_tracker(app: AppHandle, db: SqlitePool, recovered: Vec<TimeEntry>) {
+    tauri::async_runtime::spawn(async move {
+        for entry in recovered {
+            let _ = app.emit("time-entry-recovered", entry);
+        }
+
+        let mut interval = tokio::time::interval(HEARTBEAT_INTERVAL);
+        loop {
+            interval.tick().await;
+            if let Err(e) = sqlx::query("UPDATE time_entries SET heartbeat_at = ? WHERE ended_at IS NULL")
+                .bind(Utc::now().to_rfc3339())
+                .execute(&db)
+                .await
+            {
+                warn!("phase=timer_heartbeat_failed error={}", e);
+            }
+        }
+    });
+}
+
+/// Closes timers left running by a crash at their last heartbeat. Runs at
+/// startup before any command can start a timer.
+pub(crate) async fn recover_entries(db: &SqlitePool) -> Result<Vec<TimeEntry>, String> {
+    let running = sqlx::query_as::<_, TimeEntry>("SELECT * FROM time_entries WHERE ended_at IS NULL")
+        .fetch_all(db)
+        .await
+        .map_err(|e| format!("Failed to fetch running timers: {}", e))?;
+
+    let mut recovered = Vec::new();
+    for entry in running {
+        let ended_at = entry.heartbeat_at.unwrap_or(entry.started_at).max(entry.started_at);
+        close_entry(db, entry.id, entry.started_at, ended_at, "recovered").await?;
+        info!("phase=timer_recover_ok id={}", entry.id);
+        recovered.push(fetch_entry(db, entry.id).await?);
+    }
+
+    Ok(recovered)
+}
+
+async fn close_entry(
+    db: impl SqliteExecutor<'_>,
+    id: i64,
+    started_at: DateTime<Utc>,
+    ended_at: DateTime<Utc>,
+    reason: &str,
+) -> Result<(), String> {
+    sqlx::query(
+        r#"
+        UPDATE time_entries
+        SET ended_at = ?, duration_seconds = ?, stop_reason = ?, updated_at = ?
+        WHERE id = ?
+        "#,
+    )
+    .bind(ended_at.to_rfc3339())
+    .bind((ended_at - started_at).num_seconds().max(0))
+    .bind(reason)
+    .bind(Utc::now().to_rfc3339())
+    .bind(id)
+    .execute(db)
+    .await
+    .map_err(|e| format!("Failed to stop timer: {}", e))?;
+
+    Ok(())
+}
+
+async fn fetch_entry(db: &SqlitePool, id: i64) -> Result<TimeEntry, String> {
+    sqlx::query_as::<_, TimeEntry>("SELECT * FROM time_entries WHERE id = ?")
+        .bind(id)
+        .fetch_optional(db)
+        .await
+        .map_err(|e| format!("Failed to fetch time entry: {}", e))?
+        .ok_or_else(|| format!("Time entry {} not found", id))
+}
+
+async fn fetch_entries(db: &SqlitePool, filter: &TimeEntryFilter) -> Result<Vec<TimeEntry>, String> {
+    let from = filter.from.as_deref().map(parse_date_or_datetime).transpose()?.map(|d| d.to_rfc3339());
+    let to = filter.to.as_deref().map(parse_date_or_datetime).transpose()?.map(|d| d.to_rfc3339());
+
+    sqlx::query_as::<_, TimeEntry>(
+        r#"
+        SELECT * FROM time_entries
+        WHERE (? IS NULL OR project_id = ?)
+          AND (? IS NULL OR task_id = ?)
+          AND (? IS NULL OR started_at >= ?)
+          AND (? IS NULL OR started_at < ?)
+        ORDER BY started_at DESC
+        "#,
+    )
+    .bind(filter.project_id)
+    .bind(filter.project_id)
+    .bind(filter.task_id)
+    .bind(filter.task_id)
+    .bind(&from)
+    .bind(&from)
+    .bind(&to)
+    .bind(&to)
+    .fetch_all(db)
+    .await
+    .map_err(|e| format!("Failed to fetch time entries: {}", e))
+}
+
+/// The project an entry belongs to: the task's project when a task is given.
+async fn resolve_project(db: &SqlitePool, project_id: Option<i64>, task_id: Option<i64>) -> Result<i64, String> {
+    let Some(task_id) = task_id else {
+        return project_id.ok_or_else(|| "A project or task is required".to_string());
+    };
+    let task_project = sqlx::query_scalar::<_, i64>("SELECT project_id FROM project_tasks WHERE id = ?")
+        .bind(task_id)
+        .fetch_optional(db)
+        .await
+        .map_err(|e| format!("Failed to fetch project task: {}", e))?
+        .ok_or_else(|| format!("Project task {} not found", task_id))?;
+    if project_id.is_some_and(|p| p != task_project) {
+        return Err("Task belongs to another project".to_string());
+    }
+
+    Ok(task_project)
+}
+
+fn duration_seconds(started_at: DateTime<Utc>, ended_at: DateTime<Utc>) -> Result<i64, String> {
+    let seconds = (ended_at - started_at).num_seconds();
+    if seconds <= 0 {
+        return Err("End time must be after start time".to_string());
+    }
+    Ok(seconds)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::memory_db;
+
+    async fn seed_project(db: &SqlitePool) -> i64 {
+        let now = Utc::now().to_rfc3339();
+        sqlx::query("INSERT INTO projects (name, status, created_at, updated_at) VALUES ('Timers', 'active', ?, ?)")
+            .bind(&now)
+            .bind(&now)
+            .execute(db)
+            .await
+            .unwrap()
+            .last_insert_rowid()
+    }
+
+    fn start_request(project_id: i64) -> StartTimeEntryRequest {
+        StartTimeEntryRequest { project_id: Some(project_id), task_id: None, notes: None }
+    }
+
+    async fn running_ids(db: &SqlitePool) -> Vec<i64> {
+        sqlx::query_scalar("SELECT id FROM time_entries WHERE ended_at IS NULL")
+            .fetch_all(db)
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn starting_a_timer_stops_the_running_one() {
+        let db = memory_db().await;
+        let project_id = seed_project(&db).await;
+
+        let first = start_entry(&db, &start_request(project_id)).await.unwrap();
+        let second = start_entry(&db, &start_request(project_id)).await.unwrap();
+
+        assert_eq!(running_ids(&db).await, vec![second.id]);
+        let first = fetch_entry(&db, first.id).await.unwrap();
+        assert_eq!(first.stop_reason.as_deref(), Some("manual"));
+    }
+
+    #[tokio::test]
+    async fn only_one_timer_can_run() {
+        let db = memory_db().await;
+        let project_id = seed_project(&db).await;
+        start_entry(&db, &start_request(project_id)).await.unwrap();
+
+        let now = Utc::now().to_rfc3339();
+        let second = sqlx::query("INSERT INTO time_entries (project_id, started_at, created_at, updated_at) VALUES (?, ?, ?, ?)")
+            .bind(project_id)
+            .bind(&now)
+            .bind(&now)
+            .bind(&now)
+            .execute(&db)
+            .await;
+
+        assert!(second.is_err());
+    }
+
+    #[tokio::test]
+    async fn recovery_closes_timers_at_their_last_heartbeat() {
+        let db = memory_db().await;
+        let project_id = seed_project(&db).await;
+        sqlx::query(
+            "INSERT INTO time_entries (project_id, started_at, heartbeat_at, created_at, updated_at) VALUES (?, ?, ?, ?, ?)",
+        )
+        .bind(project_id)
+        .bind("2024-05-01T09:00:00+00:00")
+        .bind("2024-05-01T09:45:00+00:00")
+        .bind("2024-05-01T09:00:00+00:00")
+        .bind("2024-05-01T09:00:00+00:00")
+        .execute(&db)
+        .await
+        .unwrap();
+
+        let recovered = recover_entries(&db).await.unwrap();
+
+        assert_eq!(recovered.len(), 1);
+        assert_eq!(recovered[0].duration_seconds, Some(45 * 60));
+        assert_eq!(recovered[0].stop_reason.as_deref(), Some("recovered"));
+        assert!(running_ids(&db).await.is_empty());
+        assert!(stop_running_entry(&db, "manual").await.unwrap().is_none());
+    }
+}
@@ -0,0 +1,867 @@
+use log::info;
+use tauri::State;
+use sqlx::SqlitePool;
+use chrono::{DateTime, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::commands::url_health::{base_domain, host_of};
+use crate::models::{Airdrop, AirdropContract, DomainContract, WalletTransaction};
+
+/// Decimals of the native gas token on every EVM chain we import from.
+pub(crate) const NATIVE_DECIMALS: u32 = 18;
+/// Largest `decimals` accepted for a token; real ERC-20s stay well below it
+pub(crate) const MAX_TOKEN_DECIMALS: u32 = 36;
+
+#[derive(Debug, Deserialize)]
+pub struct ImportWalletTransactionsRequest {
+    pub wallet_id: i64,
+    pub chain: String,
+    /// `csv` for explorer exports (Etherscan/Arbiscan layout) or `json` for
+    /// JSON-RPC / explorer API transaction lists
+    pub format: String,
+    pub content: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TransactionImportError {
+    pub row: usize,
+    pub message: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ImportWalletTransactionsReport {
+    pub total_rows: usize,
+    pub imported: usize,
+    pub duplicates: usize,
+    pub matched: usize,
+    pub errors: Vec<TransactionImportError>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct WalletTransactionFilter {
+    pub wallet_id: Option<i64>,
+    pub airdrop_id: Option<i64>,
+    pub chain: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateContractMappingRequest {
+    /// Set for a per-airdrop contract; leave empty and set `domain` for a domain mapping.
+    pub airdrop_id: Option<i64>,
+    pub domain: Option<String>,
+    pub chain: Option<String>,
+    pub address: String,
+    pub label: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ContractMappings {
+    pub airdrop_contracts: Vec<AirdropContract>,
+    pub domain_contracts: Vec<DomainContract>,
+}
+
+#[derive(Debug, Default)]
+struct ParsedTransaction {
+    tx_hash: String,
+    block_number: Option<i64>,
+    timestamp: Option<DateTime<Utc>>,
+    from_address: Option<String>,
+    to_address: Option<String>,
+    value: Option<String>,
+    fee: Option<String>,
+    method: Option<String>,
+    status: Option<String>,
+}
+
+#[tauri::command]
+pub async fn import_wallet_transactions(
+    state: State<'_, crate::AppState>,
+    req: ImportWalletTransactionsRequest,
+) -> Result<ImportWalletTransactionsReport, String> {
+    let chain = req.chain.trim().to_string();
+    if chain.is_empty() {
+        return Err("Chain is required".to_string());
+    }
+
+    let rows = match req.format.to_lowercase().as_str() {
+        "csv" => parse_explorer_csv(&req.content)?,
+        "json" => parse_transaction_json(&req.content)?,
+        other => return Err(format!("Unsupported import format: {}", other)),
+    };
+
+    let mut report = ImportWalletTransactionsReport {
+        total_rows: rows.len(),
+        imported: 0,
+        duplicates: 0,
+        matched: 0,
+        errors: Vec::new(),
+    };
+
+    let mut tx = state
+        .db
+        .begin()
+        .await
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+    let now = Utc::now().to_rfc3339();
+
+    for (idx, row) in rows.into_iter().enumerate() {
+        let parsed = match row {
+            Ok(parsed) => parsed,
+            Err(message) => {
+                report.errors.push(TransactionImportError { row: idx + 1, message });
+                continue;
+            }
+        };
+
+        let result = sqlx::query(
+            r#"
+            INSERT OR IGNORE INTO wallet_transactions
+                (wallet_id, chain, tx_hash, block_number, timestamp, from_address, to_address, value, fee, method, status, created_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(req.wallet_id)
+        .bind(&chain)
+        .bind(&parsed.tx_hash)
+        .bind(parsed.block_number)
+        .bind(parsed.timestamp.map(|t| t.to_rfc3339()))
+        .bind(&parsed.from_address)
+        .bind(&parsed.to_address)
+        .bind(&parsed.value)
+        .bind(&parsed.fee)
+        .bind(&parsed.method)
+        .bind(&parsed.status)
+        .bind(&now)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to import transaction on row {}: {}", idx + 1, e))?;
+
+        if result.rows_affected() == 0 {
+            report.duplicates += 1;
+        } else {
+            report.imported += 1;
+        }
+    }
+
+    tx.commit()
+        .await
+        .map_err(|e| format!("Failed to commit transaction import: {}", e))?;
+
+    report.matched = match_transactions(&state.db, Some(req.wallet_id)).await?;
+
+    info!(
+        "phase=tx_import_done wallet_id={} chain={} imported={} duplicates={} matched={} errors={}",
+        req.wallet_id,
+        chain,
+        report.imported,
+        report.duplicates,
+        report.matched,
+        report.errors.len()
+    );
+    Ok(report)
+}
+
+#[tauri::command]
+pub async fn list_wallet_transactions(
+    state: State<'_, crate::AppState>,
+    filter: Option<WalletTransactionFilter>,
+) -> Result<Vec<WalletTransaction>, String> {
+    let filter = filter.unwrap_or_default();
+    let mut query = "SELECT * FROM wallet_transactions WHERE 1 = 1".to_string();
+    if filter.wallet_id.is_some() {
+        query.push_str(" AND wallet_id = ?");
+    }
+    if filter.airdrop_id.is_some() {
+        query.push_str(" AND airdrop_id = ?");
+    }
+    if filter.chain.is_some() {
+        query.push_str(" AND LOWER(chain) = LOWER(?)");
+    }
+    query.push_str(" ORDER BY COALESCE(timestamp, created_at) DESC");
+
+    let mut q = sqlx::query_as::<_, WalletTransaction>(&query);
+    if let Some(wallet_id) = filter.wallet_id {
+        q = q.bind(wallet_id);
+    }
+    if let Some(airdrop_id) = filter.airdrop_id {
+        q = q.bind(airdrop_id);
+    }
+    if let Some(chain) = &filter.chain {
+        q = q.bind(chain);
+    }
+
+    let transactions = q
+        .fetch_all(&state.db)
+        .await
+        .map_err(|e| format!("Failed to fetch transactions: {}", e))?;
+
+    Ok(transactions)
+}
+
+#[tauri::command]
+pub async fn delete_wallet_transaction(
+    state: State<'_, crate::AppState>,
+    id: i64,
+) -> Result<(), String> {
+    sqlx::query("DELETE FROM wallet_transactions WHERE id = ?")
+        .bind(id)
+        .execute(&state.db)
+        .await
+        .map_err(|e| format!("Failed to delete transaction: {}", e))?;
+
+    Ok(())
+}
+
+/// Re-runs airdrop matching for every transaction, e.g. after editing contract mappings.
+#[tauri::command]
+pub async fn rematch_wallet_transactions(state: State<'_, crate::AppState>) -> Result<usize, String> {
+    match_transactions(&state.db, None).await
+}
+
+#[tauri::command]
+pub async fn list_contract_mappings(
+    state: State<'_, crate::AppState>,
+) -> Result<ContractMappings, String> {
+    let airdrop_contracts = sqlx::query_as::<_, AirdropContract>(
+        "SELECT * FROM airdrop_contracts ORDER BY airdrop_id ASC, created_at ASC",
+    )
+    .fetch_all(&state.db)
+    .await
+    .map_err(|e| format!("Failed to fetch airdrop contracts: {}", e))?;
+
+    let domain_contracts = sqlx::query_as::<_, DomainContract>(
+        "SELECT * FROM domain_contracts ORDER BY domain ASC, created_at ASC",
+    )
+    .fetch_all(&state.db)
+    .await
+    .map_err(|e| format!("Failed to fetch domain contracts: {}", e))?;
+
+    Ok(ContractMappings {
+        airdrop_contracts,
+        domain_contracts,
+    })
+}
+
+#[tauri::command]
+pub async fn create_contract_mapping(
+    state: State<'_, crate::AppState>,
+    req: CreateContractMappingRequest,
+) -> Result<i64, String> {
+    let address = req.address.trim().to_lowercase();
+    if !is_evm_address(&address) {
+        return Err(format!("Invalid contract address: {}", req.address));
+    }
+    let now = Utc::now().to_rfc3339();
+
+    let result = match (req.airdrop_id, &req.domain) {
+        (Some(airdrop_id), _) => {
+            sqlx::query(
+                r#"
+                INSERT INTO airdrop_contracts (airdrop_id, chain, address, label, created_at)
+                VALUES (?, ?, ?, ?, ?)
+                "#,
+            )
+            .bind(airdrop_id)
+            .bind(&req.chain)
+            .bind(&address)
+            .bind(&req.label)
+            .bind(&now)
+            .execute(&state.db)
+            .await
+        }
+        (None, Some(domain)) => {
+            let domain = host_of(domain)
+                .or_else(|| host_of(&format!("https://{}", domain.trim())))
+                .map(|host| base_domain(&host))
+                .ok_or_else(|| format!("Invalid domain: {}", domain))?;
+            sqlx::query(
+                r#"
+                INSERT INTO domain_contracts (domain, chain, address, label, created_at)
+                VALUES (?, ?, ?, ?, ?)
+                "#,
+            )
+            .bind(&domain)
+            .bind(&req.chain)
+            .bind(&address)
+            .bind(&req.label)
+            .bind(&now)
+            .execute(&state.db)
+            .await
+        }
+        (None, None) => return Err("Either airdrop_id or domain is required".to_string()),
+    };
+
+    let id = result
+        .map_err(|e| format!("Failed to create contract mapping: {}", e))?
+        .last_insert_rowid();
+
+    Ok(id)
+}
+
+#[tauri::command]
+pub async fn delete_airdrop_contract(state: State<'_, crate::AppState>, id: i64) -> Result<(), String> {
+    sqlx::query("DELETE FROM airdrop_contracts WHERE id = ?")
+        .bind(id)
+        .execute(&state.db)
+        .await
+        .map_err(|e| format!("Failed to delete airdrop contract: {}", e))?;
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn delete_domain_contract(state: State<'_, crate::AppState>, id: i64) -> Result<(), String> {
+    sqlx::query("DELETE FROM domain_contracts WHERE id = ?")
+        .bind(id)
+        .execute(&state.db)
+        .await
+        .map_err(|e| format!("Failed to delete domain contract: {}", e))?;
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn link_completion_transaction(
+    state: State<'_, crate::AppState>,
+    completion_id: i64,
+    transaction_id: i64,
+) -> Result<(), String> {
+    sqlx::query(
+        "INSERT OR IGNORE INTO task_completion_transactions (completion_id, transaction_id) VALUES (?, ?)",
+    )
+    .bind(completion_id)
+    .bind(transaction_id)
+    .execute(&state.db)
+    .await
+    .map_err(|e| format!("Failed to link transaction: {}", e))?;
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn unlink_completion_transaction(
+    state: State<'_, crate::AppState>,
+    completion_id: i64,
+    transaction_id: i64,
+) -> Result<(), String> {
+    sqlx::query(
+        "DELETE FROM task_completion_transactions WHERE completion_id = ? AND transaction_id = ?",
+    )
+    .bind(completion_id)
+    .bind(transaction_id)
+    .execute(&state.db)
+    .await
+    .map_err(|e| format!("Failed to unlink transaction: {}", e))?;
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn list_completion_transactions(
+    state: State<'_, crate::AppState>,
+    completion_id: i64,
+) -> Result<Vec<WalletTransaction>, String> {
+    let transactions = sqlx::query_as::<_, WalletTransaction>(
+        r#"
+        SELECT wt.* FROM wallet_transactions wt
+        JOIN task_completion_transactions tct ON tct.transaction_id = wt.id
+        WHERE tct.completion_id = ?
+        ORDER BY COALESCE(wt.timestamp, wt.created_at) ASC
+        "#,
+    )
+    .bind(completion_id)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|e| format!("Failed to fetch completion transactions: {}", e))?;
+
+    Ok(transactions)
+}
+
+/// Assigns `airdrop_id` to transactions whose counterparty is a contract mapped
+/// to an airdrop directly or through the airdrop's URL domain. Returns how many
+/// transactions are matched.
+pub(crate) async fn match_transactions(
+    db: &SqlitePool,
+    wallet_id: Option<i64>,
+) -> Result<usize, String> {
+    let airdrops = sqlx::query_as::<_, Airdrop>("SELECT * FROM airdrops")
+        .fetch_all(db)
+        .await
+        .map_err(|e| format!("Failed to fetch airdrops: {}", e))?;
+    let airdrop_contracts = sqlx::query_as::<_, AirdropContract>("SELECT * FROM airdrop_contracts")
+        .fetch_all(db)
+        .await
+        .map_err(|e| format!("Failed to fetch airdrop contracts: {}", e))?;
+    let domain_contracts = sqlx::query_as::<_, DomainContract>("SELECT * FROM domain_contracts")
+        .fetch_all(db)
+        .await
+        .map_err(|e| format!("Failed to fetch domain contracts: {}", e))?;
+
+    // (airdrop_id, chain, address) for every known campaign contract
+    let mut contracts: Vec<(i64, Option<String>, String)> = airdrop_contracts
+        .into_iter()
+        .map(|c| (c.airdrop_id, c.chain, c.address.to_lowercase()))
+        .collect();
+    for airdrop in &airdrops {
+        let Some(domain) = host_of(&airdrop.url).map(|h| base_domain(&h)) else {
+            continue;
+        };
+        contracts.extend(
+            domain_contracts
+                .iter()
+                .filter(|c| c.domain == domain)
+                .map(|c| (airdrop.id, c.chain.clone(), c.address.to_lowercase())),
+        );
+    }
+
+    let transactions = match wallet_id {
+        Some(wallet_id) => {
+            sqlx::query_as::<_, WalletTransaction>("SELECT * FROM wallet_transactions WHERE wallet_id = ?")
+                .bind(wallet_id)
+                .fetch_all(db)
+                .await
+        }
+        None => {
+            sqlx::query_as::<_, WalletTransaction>("SELECT * FROM wallet_transactions")
+                .fetch_all(db)
+                .await
+        }
+    }
+    .map_err(|e| format!("Failed to fetch transactions: {}", e))?;
+
+    let mut matched = 0;
+    for transaction in &transactions {
+        let counterparty = transaction.to_address.as_deref().map(|a| a.to_lowercase());
+        let airdrop_id = contracts
+            .iter()
+            .find(|(_, chain, address)| {
+                Some(address) == counterparty.as_ref()
+                    && chain
+                        .as_deref()
+                        .is_none_or(|c| c.eq_ignore_ascii_case(&transaction.chain))
+            })
+            .map(|(airdrop_id, _, _)| *airdrop_id);
+
+        if airdrop_id.is_some() {
+            matched += 1;
+        }
+        if airdrop_id != transaction.airdrop_id {
+            sqlx::query("UPDATE wallet_transactions SET airdrop_id = ? WHERE id = ?")
+                .bind(airdrop_id)
+                .bind(transaction.id)
+                .execute(db)
+                .await
+                .map_err(|e| format!("Failed to match transaction: {}", e))?;
+        }
+    }
+
+    Ok(matched)
+}
+
+fn parse_explorer_csv(content: &str) -> Result<Vec<Result<ParsedTransaction, String>>, String> {
+    let mut reader = csv::ReaderBuilder::new()
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_reader(content.as_bytes());
+
+    let headers: Vec<String> = reader
+        .headers()
+        .map_err(|e| format!("Failed to read CSV header: {}", e))?
+        .iter()
+        .map(|h| h.to_lowercase())
+        .collect();
+    let column = |pred: &dyn Fn(&str) -> bool| headers.iter().position(|h| pred(h));
+
+    let hash_col = column(&|h| matches!(h, "txhash" | "transaction hash" | "hash"))
+        .ok_or("CSV has no transaction hash column")?;
+    let block_col = column(&|h| matches!(h, "blockno" | "block" | "blocknumber"));
+    let unix_col = column(&|h| h == "unixtimestamp" || h == "timestamp");
+    let datetime_col = column(&|h| h.starts_with("datetime"));
+    let from_col = column(&|h| h == "from");
+    let to_col = column(&|h| h == "to");
+    let contract_col = column(&|h| h == "contractaddress");
+    let value_in_col = column(&|h| h.starts_with("value_in"));
+    let value_out_col = column(&|h| h.starts_with("value_out"));
+    let fee_col = column(&|h| h.starts_with("txnfee") && !h.contains("usd"));
+    let status_col = column(&|h| h == "status");
+    let method_col = column(&|h| h == "method");
+
+    let rows = reader
+        .records()
+        .map(|record| {
+            let record = record.map_err(|e| format!("Malformed CSV row: {}", e))?;
+            let get = |col: Option<usize>| {
+                col.and_then(|c| record.get(c))
+                    .map(|v| v.trim().to_string())
+                    .filter(|v| !v.is_empty())
+            };
+
+            let tx_hash = get(Some(hash_col)).ok_or("Missing transaction hash")?.to_lowercase();
+            if !is_tx_hash(&tx_hash) {
+                return Err(format!("Invalid transaction hash: {}", tx_hash));
+            }
+
+            let timestamp = get(unix_col)
+                .and_then(|t| t.parse::<i64>().ok())
+                .and_then(|t| DateTime::from_timestamp(t, 0))
+                .or_else(|| {
+                    get(datetime_col)
+                        .and_then(|t| NaiveDateTime::parse_from_str(&t, "%Y-%m-%d %H:%M:%S").ok())
+                        .map(|t| t.and_utc())
+                });
+
+            // Explorer exports split value into IN/OUT columns; keep whichever moved.
+            let value = [get(value_out_col), get(value_in_col)]
+                .into_iter()
+                .flatten()
+                .find(|v| v.parse::<f64>().is_ok_and(|n| n != 0.0))
+                .or_else(|| get(value_out_col).or_else(|| get(value_in_col)));
+
+            Ok(ParsedTransaction {
+                tx_hash,
+                block_number: get(block_col).and_then(|b| b.parse().ok()),
+                timestamp,
+                from_address: get(from_col).map(|a| a.to_lowercase()),
+                to_address: get(to_col)
+                    .or_else(|| get(contract_col))
+                    .map(|a| a.to_lowercase()),
+                value,
+                fee: get(fee_col),
+                method: get(method_col),
+                status: get(status_col),
+            })
+        })
+        .collect();
+
+    Ok(rows)
+}
+
+/// Accepts a bare array, a JSON-RPC `{ "result": [...] }` envelope or a
+/// single transaction object. Quantities may be hex (JSON-RPC) or decimal
+/// strings (explorer APIs).
+fn parse_transaction_json(content: &str) -> Result<Vec<Result<ParsedTransaction, String>>, String> {
+    let value: serde_json::Value =
+        serde_json::from_str(content).map_err(|e| format!("Invalid JSON: {}", e))?;
+
+    let items = match value {
+        serde_json::Value::Array(items) => items,
+        serde_json::Value::Object(ref map) => match map.get("result") {
+            Some(serde_json::Value::Array(items)) => items.clone(),
+            Some(item @ serde_json::Value::Object(_)) => vec![item.clone()],
+            _ => vec![value.clone()],
+        },
+        _ => return Err("JSON import must be an array of transactions".to_string()),
+    };
+
+    Ok(items.iter().map(parse_json_transaction).collect())
+}
+
+fn parse_json_transaction(item: &serde_json::Value) -> Result<ParsedTransaction, String> {
+    let text = |keys: &[&str]| {
+        keys.iter().find_map(|key| match item.get(*key) {
+            Some(serde_json::Value::String(s)) if !s.is_empty() => Some(s.clone()),
+            Some(serde_json::Value::Number(n)) => Some(n.to_string()),
+            _ => None,
+        })
+    };
+
+    let tx_hash = text(&["hash", "transactionHash", "txHash"])
+        .ok_or("Missing transaction hash")?
+        .to_lowercase();
+    if !is_tx_hash(&tx_hash) {
+        return Err(format!("Invalid transaction hash: {}", tx_hash));
+    }
+
+    let value = text(&["value"])
+        .and_then(|v| parse_quantity(&v))
+        .and_then(|wei| format_units(wei, NATIVE_DECIMALS).ok());
+    let gas_used = text(&["gasUsed"]).and_then(|g| parse_quantity(&g));
+    let gas_price = text(&["effectiveGasPrice", "gasPrice"]).and_then(|g| parse_quantity(&g));
+    let fee = match (gas_used, gas_price) {
+        (Some(used), Some(price)) => used
+            .checked_mul(price)
+            .and_then(|wei| format_units(wei, NATIVE_DECIMALS).ok()),
+        _ => None,
+    };
+
+    let status = match (text(&["isError"]), text(&["status"])) {
+        (Some(e), _) => Some(if e == "1" { "Error" } else { "Success" }.to_string()),
+        (None, Some(s)) => Some(match parse_quantity(&s) {
+            Some(1) => "Success".to_string(),
+            Some(0) => "Error".to_string(),
+            _ => s,
+        }),
+        _ => None,
+    };
+
+    Ok(ParsedTransaction {
+        tx_hash,
+        block_number: text(&["blockNumber"])
+            .and_then(|b| parse_quantity(&b))
+            .and_then(|b| i64::try_from(b).ok()),
+        timestamp: text(&["timeStamp", "timestamp"])
+            .and_then(|t| parse_quantity(&t))
+            .and_then(|t| i64::try_from(t).ok())
+            .and_then(|t| DateTime::from_timestamp(t, 0)),
+        from_address: text(&["from"]).map(|a| a.to_lowercase()),
+        to_address: text(&["to"])
+            .or_else(|| text(&["contractAddress"]))
+            .map(|a| a.to_lowercase()),
+        value,
+        fee,
+        method: text(&["functionName", "methodId"])
+            .or_else(|| text(&["input"]).filter(|i| i.len() >= 10).map(|i| i[..10].to_string())),
+        status,
+    })
+}
+
+/// Parses a `0x`-prefixed hex or plain decimal quantity.
+pub(crate) fn parse_quantity(raw: &str) -> Option<u128> {
+    let raw = raw.trim();
+    match raw.strip_prefix("0x").or_else(|| raw.strip_prefix("0X")) {
+        Some("") => Some(0),
+        Some(hex) => u128::from_str_radix(hex, 16).ok(),
+        None => raw.parse().ok(),
+    }
+}
+
+/// Formats an integer amount of base units as a decimal string.
+pub(crate) fn format_units(amount: u128, decimals: u32) -> Result<String, String> {
+    let scale = 10u128
+        .checked_pow(decimals)
+        .ok_or_else(|| format!("Unsupported token decimals: {}", decimals))?;
+    let whole = amount / scale;
+    let fraction = amount % scale;
+    if fraction == 0 {
+        return Ok(whole.to_string());
+    }
+
+    let fraction = format!("{:0width$}", fraction, width = decimals as usize);
+    Ok(format!("{}.{}", whole, fraction.trim_end_matches('0')))
+}
+
+pub(crate) fn is_evm_address(address: &str) -> bool {
+    address.len() == 42
+        && address.starts_with("0x")
+        && address[2..].chars().all(|c| c.is_ascii_hexdigit())
+}
+
+pub(crate) fn is_tx_hash(hash: &str) -> bool {
+    hash.len() == 66 && hash.starts_with("0x") && hash[2..].chars().all(|c| c.is_ascii_hexdigit())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_quantity_reads_hex_and_decimal() {
+        assert_eq!(parse_quantity("0x1bc16d674ec80000"), Some(2_000_000_000_000_000_000));
+        assert_eq!(parse_quantity("0X0a"), Some(10));
+        assert_eq!(parse_quantity("0x"), Some(0));
+        assert_eq!(parse_quantity(" 42 "), Some(42));
+        assert_eq!(parse_quantity("0xzz"), None);
+        assert_eq!(parse_quantity("-1"), None);
+    }
+
+    #[test]
+    fn format_units_trims_trailing_zeros() {
+        assert_eq!(format_units(1_500_000_000_000_000_000, 18).unwrap(), "1.5");
+        assert_eq!(format_units(2_000_000, 6).unwrap(), "2");
+        assert_eq!(format_units(1, 18).unwrap(), "0.000000000000000001");
+        assert_eq!(format_units(123, 0).unwrap(), "123");
+    }
+
+    #[test]
+    fn format_units_rejects_overflowing_decimals() {
+        assert!(format_units(u128::MAX, 38).is_ok());
+        assert!(format_units(1, 39).is_err());
+        assert!(format_units(1, u32::MAX).is_err());
+    }
+
+    fn hash(n: u8) -> String {
+        format!("0x{}", format!("{:02x}", n).repeat(32))
+    }
+
+    fn address(n: u8) -> String {
+        format!("0x{}", format!("{:02x}", n).repeat(20))
+    }
+
+    #[test]
+    fn parses_etherscan_csv_exports() {
+        let csv = format!(
+            "\"Transaction Hash\",\"Blockno\",\"UnixTimestamp\",\"DateTime (UTC)\",\"From\",\"To\",\"ContractAddress\",\"Value_IN(ETH)\",\"Value_OUT(ETH)\",\"CurrentValue @ $3000/Eth\",\"TxnFee(ETH)\",\"TxnFee(USD)\",\"Historical $Price/Eth\",\"Status\",\"ErrCode\",\"Method\"\n\
+             \"{}\",\"19000000\",\"1704067200\",\"2024-01-01 00:00:00\",\"{}\",\"{}\",\"\",\"0\",\"0.25\",\"750\",\"0.0012\",\"3.6\",\"3000\",\"\",\"\",\"Bridge\"\n\
+             \"{}\",\"19000001\",\"\",\"2024-01-02 08:30:00\",\"{}\",\"\",\"{}\",\"0\",\"0\",\"0\",\"0.02\",\"60\",\"3000\",\"Error(0)\",\"out of gas\",\"0x60806040\"\n\
+             \"not-a-hash\",\"1\",\"\",\"\",\"\",\"\",\"\",\"\",\"\",\"\",\"\",\"\",\"\",\"\",\"\",\"\"\n",
+            hash(0xAB).to_uppercase().replace("0X", "0x"),
+            address(0xA1).to_uppercase().replace("0X", "0x"),
+            address(0xB2),
+            hash(0xCD),
+            address(0xA1),
+            address(0xC3),
+        );
+
+        let rows = parse_explorer_csv(&csv).unwrap();
+        assert_eq!(rows.len(), 3);
+
+        let transfer = rows[0].as_ref().unwrap();
+        assert_eq!(transfer.tx_hash, hash(0xab));
+        assert_eq!(transfer.block_number, Some(19_000_000));
+        assert_eq!(transfer.timestamp.unwrap().to_rfc3339(), "2024-01-01T00:00:00+00:00");
+        assert_eq!(transfer.from_address.as_deref(), Some(address(0xa1).as_str()));
+        assert_eq!(transfer.to_address.as_deref(), Some(address(0xb2).as_str()));
+        assert_eq!(transfer.value.as_deref(), Some("0.25"));
+        // The USD fee column is not mistaken for the native fee
+        assert_eq!(transfer.fee.as_deref(), Some("0.0012"));
+        assert_eq!(transfer.method.as_deref(), Some("Bridge"));
+
+        // Contract creations have no `To`; the created contract stands in
+        let creation = rows[1].as_ref().unwrap();
+        assert_eq!(creation.to_address.as_deref(), Some(address(0xc3).as_str()));
+        assert_eq!(creation.timestamp.unwrap().to_rfc3339(), "2024-01-02T08:30:00+00:00");
+        assert_eq!(creation.value.as_deref(), Some("0"));
+        assert_eq!(creation.status.as_deref(), Some("Error(0)"));
+
+        assert!(rows[2].as_ref().unwrap_err().contains("Invalid transaction hash"));
+    }
+
+    #[test]
+    fn csv_without_a_hash_column_is_rejected() {
+        assert!(parse_explorer_csv("Blockno,From,To\n1,0x1,0x2\n").is_err());
+    }
+
+    #[test]
+    fn parses_json_rpc_result_envelopes() {
+        let json = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "result": [
+                {
+                    "hash": hash(0x11),
+                    "blockNumber": "0x121eac0",
+                    "timestamp": "0x65920080",
+                    "from": address(0xA1),
+                    "to": address(0xB2),
+                    "value": "0x3782dace9d900000",
+                    "gasUsed": "0x5208",
+                    "effectiveGasPrice": "0x3b9aca00",
+                    "input": "0xa9059cbb0000",
+                    "status": "0x1"
+                },
+                {
+                    "hash": hash(0x22),
+                    "to": null,
+                    "contractAddress": address(0xC3),
+                    "status": "0x0"
+                },
+                { "blockNumber": "0x1" }
+            ]
+        })
+        .to_string();
+
+        let rows = parse_transaction_json(&json).unwrap();
+        assert_eq!(rows.len(), 3);
+
+        let call = rows[0].as_ref().unwrap();
+        assert_eq!(call.block_number, Some(19_000_000));
+        assert_eq!(call.timestamp.unwrap().to_rfc3339(), "2024-01-01T00:00:00+00:00");
+        assert_eq!(call.from_address.as_deref(), Some(address(0xa1).as_str()));
+        assert_eq!(call.value.as_deref(), Some("4"));
+        // 21000 gas at 1 gwei
+        assert_eq!(call.fee.as_deref(), Some("0.000021"));
+        assert_eq!(call.method.as_deref(), Some("0xa9059cbb"));
+        assert_eq!(call.status.as_deref(), Some("Success"));
+
+        let creation = rows[1].as_ref().unwrap();
+        assert_eq!(creation.to_address.as_deref(), Some(address(0xc3).as_str()));
+        assert_eq!(creation.status.as_deref(), Some("Error"));
+
+        assert!(rows[2].is_err());
+
+        // A single transaction as the result is accepted too
+        let single = serde_json::json!({ "result": { "hash": hash(0x33) } }).to_string();
+        assert_eq!(parse_transaction_json(&single).unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn matching_respects_contract_chains_and_airdrop_domains() {
+        let db = crate::test_support::memory_db().await;
+        let now = Utc::now().to_rfc3339();
+        let wallet_id = sqlx::query("INSERT INTO wallets (label, address, created_at, updated_at) VALUES ('Main', ?, ?, ?)")
+            .bind(address(0xA1))
+            .bind(&now)
+            .bind(&now)
+            .execute(&db)
+            .await
+            .unwrap()
+            .last_insert_rowid();
+        let mut airdrop_ids = Vec::new();
+        for (name, url) in [("Bridge", "https://bridge.example.xyz"), ("Quest", "https://app.quest.xyz/season-2")] {
+            let id = sqlx::query("INSERT INTO airdrops (name, url, created_at, updated_at) VALUES (?, ?, ?, ?)")
+                .bind(name)
+                .bind(url)
+                .bind(&now)
+                .bind(&now)
+                .execute(&db)
+                .await
+                .unwrap()
+                .last_insert_rowid();
+            airdrop_ids.push(id);
+        }
+        let (bridge, quest) = (airdrop_ids[0], airdrop_ids[1]);
+
+        // Bridge contract only counts on Base; the quest's domain maps a contract on any chain
+        sqlx::query("INSERT INTO airdrop_contracts (airdrop_id, chain, address, created_at) VALUES (?, 'base', ?, ?)")
+            .bind(bridge)
+            .bind(address(0xB2).to_uppercase().replace("0X", "0x"))
+            .bind(&now)
+            .execute(&db)
+            .await
+            .unwrap();
+        sqlx::query("INSERT INTO domain_contracts (domain, chain, address, created_at) VALUES ('quest.xyz', NULL, ?, ?)")
+            .bind(address(0xC3))
+            .bind(&now)
+            .execute(&db)
+            .await
+            .unwrap();
+
+        let transactions = [
+            (hash(1), "base", address(0xB2)),
+            (hash(2), "Base", address(0xB2)),
+            (hash(3), "arbitrum", address(0xB2)),
+            (hash(4), "arbitrum", address(0xC3)),
+            (hash(5), "base", address(0xD4)),
+        ];
+        for (tx_hash, chain, to) in &transactions {
+            sqlx::query(
+                "INSERT INTO wallet_transactions (wallet_id, chain, tx_hash, to_address, created_at) VALUES (?, ?, ?, ?, ?)",
+            )
+            .bind(wallet_id)
+            .bind(chain)
+            .bind(tx_hash)
+            .bind(to)
+            .bind(&now)
+            .execute(&db)
+            .await
+            .unwrap();
+        }
+        // A stale match is cleared when nothing maps the counterparty any more
+        sqlx::query("UPDATE wallet_transactions SET airdrop_id = ? WHERE tx_hash = ?")
+            .bind(quest)
+            .bind(hash(5))
+            .execute(&db)
+            .await
+            .unwrap();
+
+        assert_eq!(match_transactions(&db, Some(wallet_id)).await.unwrap(), 3);
+
+        let matched: Vec<Option<i64>> =
+            sqlx::query_scalar("SELECT airdrop_id FROM wallet_transactions ORDER BY tx_hash")
+                .fetch_all(&db)
+                .await
+                .unwrap();
+        assert_eq!(matched, vec![Some(bridge), Some(bridge), None, Some(quest), None]);
+    }
+}
+
@@ -0,0 +1,439 @@
+use log::{info, warn};
+use tauri::{AppHandle, Emitter, State};
+use sqlx::SqlitePool;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::time::Duration;
+
+use crate::commands::airdrops::normalize_url;
+use crate::models::{Airdrop, AirdropUrlHealth};
+
+const DEFAULT_TIMEOUT_SECS: u64 = 15;
+const MAX_REDIRECTS: usize = 10;
+const MAX_BODY_BYTES: usize = 512 * 1024;
+
+#[derive(Debug, Default, Deserialize)]
+pub struct CheckAirdropUrlsRequest {
+    /// Limits the check to these airdrops; all airdrops are checked when omitted.
+    pub airdrop_ids: Option<Vec<i64>>,
+    pub timeout_secs: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct UrlCheckProgress {
+    pub checked: usize,
+    pub total: usize,
+    pub result: AirdropUrlHealth,
+}
+
+#[tauri::command]
+pub async fn check_airdrop_urls(
+    state: State<'_, crate::AppState>,
+    req: CheckAirdropUrlsRequest,
+) -> Result<Vec<AirdropUrlHealth>, String> {
+    run_url_checks(&state.db, &req, |_| {}).await
+}
+
+/// Runs the URL check on a background task. Emits `airdrop-url-check-progress`
+/// after each airdrop and `airdrop-url-check-done` with all results at the end.
+#[tauri::command]
+pub async fn start_airdrop_url_check(
+    app: AppHandle,
+    state: State<'_, crate::AppState>,
+    req: CheckAirdropUrlsRequest,
+) -> Result<(), String> {
+    let db = state.db.clone();
+
+    tauri::async_runtime::spawn(async move {
+        let progress_app = app.clone();
+        let results = run_url_checks(&db, &req, move |progress| {
+            let _ = progress_app.emit("airdrop-url-check-progress", progress);
+        })
+        .await;
+
+        match results {
+            Ok(results) => {
+                let _ = app.emit("airdrop-url-check-done", results);
+            }
+            Err(e) => {
+                warn!("phase=url_check_failed error={}", e);
+                let _ = app.emit("airdrop-url-check-failed", e);
+            }
+        }
+    });
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn list_airdrop_url_health(
+    state: State<'_, crate::AppState>,
+) -> Result<Vec<AirdropUrlHealth>, String> {
+    let results = sqlx::query_as::<_, AirdropUrlHealth>(
+        "SELECT * FROM airdrop_url_health ORDER BY checked_at DESC",
+    )
+    .fetch_all(&state.db)
+    .await
+    .map_err(|e| format!("Failed to fetch URL health: {}", e))?;
+
+    Ok(results)
+}
+
+#[tauri::command]
+pub async fn list_trusted_redirect_domains(
+    state: State<'_, crate::AppState>,
+) -> Result<Vec<String>, String> {
+    let domains = sqlx::query_scalar::<_, String>(
+        "SELECT domain FROM trusted_redirect_domains ORDER BY domain ASC",
+    )
+    .fetch_all(&state.db)
+    .await
+    .map_err(|e| format!("Failed to fetch trusted domains: {}", e))?;
+
+    Ok(domains)
+}
+
+#[tauri::command]
+pub async fn trust_redirect_domain(
+    state: State<'_, crate::AppState>,
+    domain: String,
+) -> Result<(), String> {
+    let domain = base_domain(&domain.trim().to_lowercase());
+
+    sqlx::query(
+        "INSERT OR IGNORE INTO trusted_redirect_domains (domain, created_at) VALUES (?, ?)",
+    )
+    .bind(&domain)
+    .bind(Utc::now().to_rfc3339())
+    .execute(&state.db)
+    .await
+    .map_err(|e| format!("Failed to trust domain: {}", e))?;
+
+    // Clear warnings that pointed at the newly trusted domain.
+    let flagged = sqlx::query_as::<_, AirdropUrlHealth>(
+        "SELECT * FROM airdrop_url_health WHERE phishing_warning = 1",
+    )
+    .fetch_all(&state.db)
+    .await
+    .map_err(|e| format!("Failed to fetch URL health: {}", e))?;
+
+    for health in flagged {
+        let final_domain = health.final_url.as_deref().and_then(host_of).map(|h| base_domain(&h));
+        if final_domain.as_deref() == Some(domain.as_str()) {
+            sqlx::query("UPDATE airdrop_url_health SET phishing_warning = 0 WHERE airdrop_id = ?")
+                .bind(health.airdrop_id)
+                .execute(&state.db)
+                .await
+                .map_err(|e| format!("Failed to update URL health: {}", e))?;
+        }
+    }
+
+    info!("phase=redirect_domain_trusted domain={}", domain);
+    Ok(())
+}
+
+/// Checks every selected airdrop URL and stores the latest result per airdrop.
+pub(crate) async fn run_url_checks<F>(
+    db: &SqlitePool,
+    req: &CheckAirdropUrlsRequest,
+    mut on_progress: F,
+) -> Result<Vec<AirdropUrlHealth>, String>
+where
+    F: FnMut(UrlCheckProgress),
+{
+    let mut airdrops = sqlx::query_as::<_, Airdrop>(
+        "SELECT * FROM airdrops ORDER BY position ASC, created_at ASC",
+    )
+    .fetch_all(db)
+    .await
+    .map_err(|e| format!("Failed to fetch airdrops: {}", e))?;
+
+    if let Some(ids) = &req.airdrop_ids {
+        airdrops.retain(|a| ids.contains(&a.id));
+    }
+
+    let client = reqwest::Client::builder()
+        .redirect(reqwest::redirect::Policy::limited(MAX_REDIRECTS))
+        .timeout(Duration::from_secs(req.timeout_secs.unwrap_or(DEFAULT_TIMEOUT_SECS)))
+        .user_agent(concat!("PraetorDesk/", env!("CARGO_PKG_VERSION")))
+        .build()
+        .map_err(|e| format!("Failed to build HTTP client: {}", e))?;
+
+    let trusted: HashSet<String> = sqlx::query_scalar::<_, String>(
+        "SELECT domain FROM trusted_redirect_domains",
+    )
+    .fetch_all(db)
+    .await
+    .map_err(|e| format!("Failed to fetch trusted domains: {}", e))?
+    .into_iter()
+    .collect();
+
+    info!("phase=url_check_start count={}", airdrops.len());
+
+    let total = airdrops.len();
+    let mut results = Vec::with_capacity(total);
+    for (idx, airdrop) in airdrops.iter().enumerate() {
+        // A redirect target only counts as known once a check accepted it.
+        let previous_final = sqlx::query_scalar::<_, Option<String>>(
+            "SELECT final_url FROM airdrop_url_health WHERE airdrop_id = ? AND phishing_warning = 0",
+        )
+        .bind(airdrop.id)
+        .fetch_optional(db)
+        .await
+        .map_err(|e| format!("Failed to fetch URL health: {}", e))?
+        .flatten();
+
+        let health = check_url(&client, airdrop, previous_final.as_deref(), &trusted).await;
+
+        sqlx::query(
+            r#"
+            INSERT INTO airdrop_url_health (airdrop_id, url, status_code, final_url, page_title, error, is_dead, is_redirected, phishing_warning, checked_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            ON CONFLICT(airdrop_id) DO UPDATE SET
+                url = excluded.url,
+                status_code = excluded.status_code,
+                final_url = excluded.final_url,
+                page_title = excluded.page_title,
+                error = excluded.error,
+                is_dead = excluded.is_dead,
+                is_redirected = excluded.is_redirected,
+                phishing_warning = excluded.phishing_warning,
+                checked_at = excluded.checked_at
+            "#,
+        )
+        .bind(health.airdrop_id)
+        .bind(&health.url)
+        .bind(health.status_code)
+        .bind(&health.final_url)
+        .bind(&health.page_title)
+        .bind(&health.error)
+        .bind(if health.is_dead { 1 } else { 0 })
+        .bind(if health.is_redirected { 1 } else { 0 })
+        .bind(if health.phishing_warning { 1 } else { 0 })
+        .bind(health.checked_at.to_rfc3339())
+        .execute(db)
+        .await
+        .map_err(|e| format!("Failed to save URL health: {}", e))?;
+
+        if health.phishing_warning {
+            warn!(
+                "phase=url_check_phishing_warning airdrop_id={} url={} final_url={}",
+                airdrop.id,
+                airdrop.url,
+                health.final_url.as_deref().unwrap_or_default()
+            );
+        }
+
+        on_progress(UrlCheckProgress {
+            checked: idx + 1,
+            total,
+            result: health.clone(),
+        });
+        results.push(health);
+    }
+
+    let dead = results.iter().filter(|r| r.is_dead).count();
+    let flagged = results.iter().filter(|r| r.phishing_warning).count();
+    info!(
+        "phase=url_check_done count={} dead={} phishing_warnings={}",
+        results.len(),
+        dead,
+        flagged
+    );
+    Ok(results)
+}
+
+async fn check_url(
+    client: &reqwest::Client,
+    airdrop: &Airdrop,
+    previous_final: Option<&str>,
+    trusted: &HashSet<String>,
+) -> AirdropUrlHealth {
+    let mut health = AirdropUrlHealth {
+        airdrop_id: airdrop.id,
+        url: airdrop.url.clone(),
+        status_code: None,
+        final_url: None,
+        page_title: None,
+        error: None,
+        is_dead: false,
+        is_redirected: false,
+        phishing_warning: false,
+        checked_at: Utc::now(),
+    };
+
+    let response = match client.get(&airdrop.url).send().await {
+        Ok(response) => response,
+        Err(e) => {
+            health.error = Some(e.to_string());
+            health.is_dead = true;
+            return health;
+        }
+    };
+
+    let status = response.status();
+    let final_url = response.url().to_string();
+    health.status_code = Some(status.as_u16() as i64);
+    // 401/403/429 usually mean bot protection rather than a dead campaign.
+    health.is_dead = status.is_server_error()
+        || (status.is_client_error() && !matches!(status.as_u16(), 401 | 403 | 429));
+    health.is_redirected = normalize_url(&final_url) != normalize_url(&airdrop.url);
+
+    if health.is_redirected {
+        let origin = host_of(&airdrop.url).map(|h| base_domain(&h));
+        let target = host_of(&final_url).map(|h| base_domain(&h));
+        let previous = previous_final.and_then(host_of).map(|h| base_domain(&h));
+        if let Some(target) = target {
+            let known = origin.as_deref() == Some(target.as_str())
+                || previous.as_deref() == Some(target.as_str())
+                || trusted.contains(&target);
+            health.phishing_warning = !known;
+        }
+    }
+    health.final_url = Some(final_url);
+
+    match read_capped(response, MAX_BODY_BYTES).await {
+        Ok(body) => health.page_title = extract_title(&body),
+        Err(e) => health.error = Some(format!("Failed to read body: {}", e)),
+    }
+
+    health
+}
+
+pub(crate) fn host_of(raw: &str) -> Option<String> {
+    url::Url::parse(raw)
+        .ok()
+        .and_then(|u| u.host_str().map(|h| h.to_lowercase()))
+}
+
+/// The registrable domain per the public suffix list, so `app.example.xyz`
+/// and `example.xyz` count as the same site but `a.vercel.app` and
+/// `b.vercel.app` do not. IPs and hosts without a known suffix stay as is.
+pub(crate) fn base_domain(host: &str) -> String {
+    let host = host.trim_end_matches('.');
+    if host.parse::<std::net::IpAddr>().is_ok() {
+        return host.to_string();
+    }
+    psl::domain_str(host).unwrap_or(host).to_string()
+}
+
+/// Reads at most `limit` bytes of the body; the title sits near the top.
+async fn read_capped(mut response: reqwest::Response, limit: usize) -> Result<String, reqwest::Error> {
+    let mut body = Vec::new();
+    while let Some(chunk) = response.chunk().await? {
+        let room = limit - body.len();
+        body.extend_from_slice(&chunk[..chunk.len().min(room)]);
+        if body.len() >= limit {
+            break;
+        }
+    }
+    Ok(String::from_utf8_lossy(&body).into_owned())
+}
+
+fn extract_title(html: &str) -> Option<String> {
+    let lower = html.to_ascii_lowercase();
+    let open = lower.find("<title")?;
+    let start = open + lower[open..].find('>')? + 1;
+    let end = start + lower[start..].find("</title")?;
+    let title = html.get(start..end)?.split_whitespace().collect::<Vec<_>>().join(" ");
+
+    if title.is_empty() {
+        None
+    } else {
+        Some(title)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{http_stand_in, memory_db, StandInReply};
+
+    #[test]
+    fn base_domain_uses_the_public_suffix_list() {
+        assert_eq!(base_domain("app.example.xyz"), "example.xyz");
+        assert_eq!(base_domain("www.example.com"), "example.com");
+        assert_eq!(base_domain("claim.project.co.uk"), "project.co.uk");
+        assert_ne!(base_domain("evil.co.uk"), base_domain("project.co.uk"));
+        assert_eq!(base_domain("project.vercel.app"), "project.vercel.app");
+        assert_ne!(base_domain("evil.vercel.app"), base_domain("project.vercel.app"));
+        assert_eq!(base_domain("127.0.0.1"), "127.0.0.1");
+        assert_eq!(base_domain("localhost"), "localhost");
+    }
+
+    #[test]
+    fn extract_title_collapses_whitespace() {
+        assert_eq!(
+            extract_title("<html><TITLE>\n  Season 2 \n Quests </title></html>").as_deref(),
+            Some("Season 2 Quests")
+        );
+        assert_eq!(extract_title("<title></title>"), None);
+        assert_eq!(extract_title("<p>no title</p>"), None);
+    }
+
+    async fn add_airdrop(db: &SqlitePool, name: &str, url: &str) -> i64 {
+        let now = Utc::now().to_rfc3339();
+        sqlx::query("INSERT INTO airdrops (name, url, created_at, updated_at) VALUES (?, ?, ?, ?)")
+            .bind(name)
+            .bind(url)
+            .bind(&now)
+            .bind(&now)
+            .execute(db)
+            .await
+            .unwrap()
+            .last_insert_rowid()
+    }
+
+    #[tokio::test]
+    async fn classifies_dead_redirected_and_suspicious_urls() {
+        // `localhost` and `127.0.0.1` are different sites, like two hosting subdomains
+        let elsewhere = http_stand_in(|_| StandInReply::ok("<title>Claim now</title>")).await;
+        let elsewhere = elsewhere.replace("127.0.0.1", "localhost");
+        let phish_target = format!("{}/claim", elsewhere);
+        let site = http_stand_in(move |request| match request.path.as_str() {
+            "/live" => StandInReply::ok("<title>Live campaign</title>"),
+            "/moved" => StandInReply::status(301).header("Location", "/live"),
+            "/phish" => StandInReply::status(302).header("Location", phish_target.clone()),
+            "/big" => StandInReply::ok(format!("<title>Big</title>{}", "x".repeat(2 * MAX_BODY_BYTES))),
+            _ => StandInReply::status(404),
+        })
+        .await;
+
+        let db = memory_db().await;
+        let live = add_airdrop(&db, "live", &format!("{}/live", site)).await;
+        let moved = add_airdrop(&db, "moved", &format!("{}/moved", site)).await;
+        let phish = add_airdrop(&db, "phish", &format!("{}/phish", site)).await;
+        let gone = add_airdrop(&db, "gone", &format!("{}/gone", site)).await;
+        let big = add_airdrop(&db, "big", &format!("{}/big", site)).await;
+
+        let results = run_url_checks(&db, &CheckAirdropUrlsRequest::default(), |_| {}).await.unwrap();
+        let result = |id: i64| results.iter().find(|r| r.airdrop_id == id).unwrap();
+
+        assert_eq!(result(live).status_code, Some(200));
+        assert_eq!(result(live).page_title.as_deref(), Some("Live campaign"));
+        assert!(!result(live).is_redirected && !result(live).is_dead);
+
+        assert!(result(moved).is_redirected);
+        assert!(!result(moved).phishing_warning);
+
+        assert!(result(phish).is_redirected);
+        assert!(result(phish).phishing_warning);
+        assert_eq!(result(phish).page_title.as_deref(), Some("Claim now"));
+
+        assert!(result(gone).is_dead);
+        assert_eq!(result(gone).status_code, Some(404));
+
+        assert_eq!(result(big).page_title.as_deref(), Some("Big"));
+        assert!(result(big).error.is_none());
+
+        // Trusting the target domain clears the warning on the next run
+        sqlx::query("INSERT INTO trusted_redirect_domains (domain, created_at) VALUES ('localhost', ?)")
+            .bind(Utc::now().to_rfc3339())
+            .execute(&db)
+            .await
+            .unwrap();
+        let results = run_url_checks(&db, &CheckAirdropUrlsRequest::default(), |_| {}).await.unwrap();
+        assert!(!results.iter().any(|r| r.phishing_warning));
+    }
+}
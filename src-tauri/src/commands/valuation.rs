@@ -0,0 +1,137 @@
+use tauri::State;
+use chrono::{NaiveDate, Utc};
+use serde::Serialize;
+use std::collections::HashMap;
+
+use crate::commands::prices::{base_currency, PriceBook};
+use crate::models::{Airdrop, AirdropClaim, AirdropExpense, Project};
+
+#[derive(Debug, Serialize)]
+pub struct AirdropValuation {
+    pub airdrop_id: i64,
+    pub airdrop_name: String,
+    pub expected_value: Option<f64>,
+    pub cost_to_date: f64,
+    /// Fair value of everything claimed so far
+    pub claimed_value: f64,
+    /// Claimed value minus cost
+    pub realized_net: f64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ProjectValuation {
+    pub project_id: i64,
+    pub project_name: String,
+    pub budget: Option<f64>,
+    pub budget_currency: Option<String>,
+    /// `budget` converted into the base currency
+    pub budget_in_base: Option<f64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ValuationSummary {
+    pub base_currency: String,
+    pub airdrops: Vec<AirdropValuation>,
+    pub projects: Vec<ProjectValuation>,
+    /// Amounts that had no price and were left out
+    pub warnings: Vec<String>,
+}
+
+/// Airdrop costs and claims and project budgets, all in the base currency.
+#[tauri::command]
+pub async fn valuation_summary(state: State<'_, crate::AppState>) -> Result<ValuationSummary, String> {
+    let db = &state.db;
+    let base = base_currency(db).await?;
+    let prices = PriceBook::load(db).await?;
+    let today = Utc::now().date_naive();
+    let mut warnings = Vec::new();
+
+    let expenses = sqlx::query_as::<_, AirdropExpense>("SELECT * FROM airdrop_expenses")
+        .fetch_all(db)
+        .await
+        .map_err(|e| format!("Failed to fetch expenses: {}", e))?;
+    let mut costs: HashMap<i64, f64> = HashMap::new();
+    for expense in &expenses {
+        let day = NaiveDate::parse_from_str(&expense.spent_on, "%Y-%m-%d").unwrap_or(today);
+        match prices.convert(expense.amount, &expense.currency, &base, day) {
+            Some(amount) => *costs.entry(expense.airdrop_id).or_default() += amount,
+            None => warnings.push(format!(
+                "Expense #{} ({} {}) has no {} price",
+                expense.id, expense.amount, expense.currency, base
+            )),
+        }
+    }
+
+    let claims = sqlx::query_as::<_, AirdropClaim>("SELECT * FROM airdrop_claims WHERE airdrop_id IS NOT NULL")
+        .fetch_all(db)
+        .await
+        .map_err(|e| format!("Failed to fetch claims: {}", e))?;
+    let mut claimed: HashMap<i64, f64> = HashMap::new();
+    for claim in &claims {
+        let Some(airdrop_id) = claim.airdrop_id else {
+            continue;
+        };
+        match prices.convert(claim.fair_value, &claim.currency, &base, claim.received_at.date_naive()) {
+            Some(value) => *claimed.entry(airdrop_id).or_default() += value,
+            None => warnings.push(format!(
+                "Claim #{} ({} {}) has no {} price",
+                claim.id, claim.fair_value, claim.currency, base
+            )),
+        }
+    }
+
+    let airdrops = sqlx::query_as::<_, Airdrop>("SELECT * FROM airdrops ORDER BY position ASC")
+        .fetch_all(db)
+        .await
+        .map_err(|e| format!("Failed to fetch airdrops: {}", e))?;
+    let airdrops = airdrops
+        .into_iter()
+        .map(|airdrop| {
+            let cost_to_date = costs.get(&airdrop.id).copied().unwrap_or(0.0);
+            let claimed_value = claimed.get(&airdrop.id).copied().unwrap_or(0.0);
+            AirdropValuation {
+                airdrop_id: airdrop.id,
+                airdrop_name: airdrop.name,
+                expected_value: airdrop.expected_value,
+                cost_to_date,
+                claimed_value,
+                realized_net: claimed_value - cost_to_date,
+            }
+        })
+        .collect();
+
+    let projects = sqlx::query_as::<_, Project>("SELECT * FROM projects ORDER BY created_at DESC")
+        .fetch_all(db)
+        .await
+        .map_err(|e| format!("Failed to fetch projects: {}", e))?;
+    let projects = projects
+        .into_iter()
+        .map(|project| {
+            let currency = project.budget_currency.clone().unwrap_or_else(|| base.clone());
+            let budget_in_base = project.budget.and_then(|budget| {
+                let converted = prices.convert(budget, &currency, &base, today);
+                if converted.is_none() {
+                    warnings.push(format!(
+                        "Budget of {} ({} {}) has no {} price",
+                        project.name, budget, currency, base
+                    ));
+                }
+                converted
+            });
+            ProjectValuation {
+                project_id: project.id,
+                project_name: project.name,
+                budget: project.budget,
+                budget_currency: project.budget_currency,
+                budget_in_base,
+            }
+        })
+        .collect();
+
+    Ok(ValuationSummary {
+        base_currency: base,
+        airdrops,
+        projects,
+        warnings,
+    })
+}
@@ -0,0 +1,142 @@
+use log::info;
+use tauri::State;
+use chrono::Utc;
+use serde::Deserialize;
+
+use crate::models::Wallet;
+
+#[derive(Debug, Deserialize)]
+pub struct CreateWalletRequest {
+    pub label: String,
+    pub address: String,
+    pub chains: Option<Vec<String>>,
+    pub group_name: Option<String>,
+    pub funding_source: Option<String>,
+    pub notes: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateWalletRequest {
+    pub id: i64,
+    pub label: Option<String>,
+    pub address: Option<String>,
+    pub chains: Option<Vec<String>>,
+    pub group_name: Option<String>,
+    pub funding_source: Option<String>,
+    pub notes: Option<String>,
+}
+
+#[tauri::command]
+pub async fn list_wallets(state: State<'_, crate::AppState>) -> Result<Vec<Wallet>, String> {
+    let wallets = sqlx::query_as::<_, Wallet>("SELECT * FROM wallets ORDER BY label ASC")
+        .fetch_all(&state.db)
+        .await
+        .map_err(|e| format!("Failed to fetch wallets: {}", e))?;
+
+    Ok(wallets)
+}
+
+#[tauri::command]
+pub async fn create_wallet(
+    state: State<'_, crate::AppState>,
+    req: CreateWalletRequest,
+) -> Result<i64, String> {
+    let now = Utc::now();
+    let chains = req.chains.clone().unwrap_or_default();
+
+    let id = sqlx::query(
+        r#"
+        INSERT INTO wallets (label, address, chains, group_name, funding_source, notes, created_at, updated_at)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(&req.label)
+    .bind(req.address.trim())
+    .bind(serde_json::to_string(&chains).unwrap())
+    .bind(req.group_name.as_deref().and_then(normalize_group))
+    .bind(&req.funding_source)
+    .bind(&req.notes)
+    .bind(now.to_rfc3339())
+    .bind(now.to_rfc3339())
+    .execute(&state.db)
+    .await
+    .map_err(|e| format!("Failed to create wallet: {}", e))?
+    .last_insert_rowid();
+
+    info!("phase=wallet_create_ok id={}", id);
+    Ok(id)
+}
+
+#[tauri::command]
+pub async fn update_wallet(
+    state: State<'_, crate::AppState>,
+    req: UpdateWalletRequest,
+) -> Result<(), String> {
+    let mut updates = Vec::new();
+    if req.label.is_some() {
+        updates.push("label = ?");
+    }
+    if req.address.is_some() {
+        updates.push("address = ?");
+    }
+    if req.chains.is_some() {
+        updates.push("chains = ?");
+    }
+    if req.group_name.is_some() {
+        updates.push("group_name = ?");
+    }
+    if req.funding_source.is_some() {
+        updates.push("funding_source = ?");
+    }
+    if req.notes.is_some() {
+        updates.push("notes = ?");
+    }
+    updates.push("updated_at = ?");
+
+    let query = format!("UPDATE wallets SET {} WHERE id = ?", updates.join(", "));
+
+    let mut q = sqlx::query(&query);
+    if let Some(label) = &req.label {
+        q = q.bind(label);
+    }
+    if let Some(address) = &req.address {
+        q = q.bind(address.trim());
+    }
+    if let Some(chains) = &req.chains {
+        q = q.bind(serde_json::to_string(chains).unwrap());
+    }
+    if let Some(group_name) = &req.group_name {
+        // An empty group clears it, putting the wallet back on its own.
+        q = q.bind(normalize_group(group_name));
+    }
+    if let Some(funding_source) = &req.funding_source {
+        q = q.bind(funding_source);
+    }
+    if let Some(notes) = &req.notes {
+        q = q.bind(notes);
+    }
+    q = q.bind(Utc::now().to_rfc3339());
+    q = q.bind(req.id);
+
+    q.execute(&state.db)
+        .await
+        .map_err(|e| format!("Failed to update wallet: {}", e))?;
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn delete_wallet(state: State<'_, crate::AppState>, id: i64) -> Result<(), String> {
+    sqlx::query("DELETE FROM wallets WHERE id = ?")
+        .bind(id)
+        .execute(&state.db)
+        .await
+        .map_err(|e| format!("Failed to delete wallet: {}", e))?;
+
+    Ok(())
+}
+
+/// Trimmed group name, or `None` for a blank one so the wallet stays ungrouped.
+fn normalize_group(group_name: &str) -> Option<&str> {
+    Some(group_name.trim()).filter(|g| !g.is_empty())
+}
@@ -105,6 +105,615 @@ pub async fn migrate(pool: &SqlitePool) -> Result<(), sqlx::Error> {
     .execute(pool)
     .await?;
 
+    // Airdrop URL Health
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS airdrop_url_health (
+            airdrop_id INTEGER PRIMARY KEY,
+            url TEXT NOT NULL,
+            status_code INTEGER,
+            final_url TEXT,
+            page_title TEXT,
+            error TEXT,
+            is_dead INTEGER NOT NULL DEFAULT 0,
+            is_redirected INTEGER NOT NULL DEFAULT 0,
+            phishing_warning INTEGER NOT NULL DEFAULT 0,
+            checked_at TEXT NOT NULL,
+            FOREIGN KEY (airdrop_id) REFERENCES airdrops(id) ON DELETE CASCADE
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Trusted Redirect Domains
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS trusted_redirect_domains (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            domain TEXT NOT NULL UNIQUE,
+            created_at TEXT NOT NULL
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Airdrop Feeds
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS airdrop_feeds (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            airdrop_id INTEGER NOT NULL,
+            url TEXT NOT NULL,
+            title TEXT,
+            last_polled_at TEXT,
+            last_error TEXT,
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL,
+            FOREIGN KEY (airdrop_id) REFERENCES airdrops(id) ON DELETE CASCADE
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Feed Items
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS feed_items (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            feed_id INTEGER NOT NULL,
+            airdrop_id INTEGER NOT NULL,
+            guid TEXT NOT NULL,
+            title TEXT NOT NULL,
+            link TEXT,
+            summary TEXT,
+            published_at TEXT,
+            read INTEGER NOT NULL DEFAULT 0,
+            created_at TEXT NOT NULL,
+            UNIQUE (feed_id, guid),
+            FOREIGN KEY (feed_id) REFERENCES airdrop_feeds(id) ON DELETE CASCADE,
+            FOREIGN KEY (airdrop_id) REFERENCES airdrops(id) ON DELETE CASCADE
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Wallets
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS wallets (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            label TEXT NOT NULL,
+            address TEXT NOT NULL,
+            chains TEXT NOT NULL DEFAULT '[]',
+            group_name TEXT,
+            notes TEXT,
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Social Identities
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS social_identities (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            platform TEXT NOT NULL,
+            handle TEXT NOT NULL,
+            notes TEXT,
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL,
+            UNIQUE (platform, handle)
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Airdrop Identities
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS airdrop_identities (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            airdrop_id INTEGER NOT NULL,
+            identity_id INTEGER NOT NULL,
+            wallet_id INTEGER,
+            created_at TEXT NOT NULL,
+            UNIQUE (airdrop_id, identity_id, wallet_id),
+            FOREIGN KEY (airdrop_id) REFERENCES airdrops(id) ON DELETE CASCADE,
+            FOREIGN KEY (identity_id) REFERENCES social_identities(id) ON DELETE CASCADE,
+            FOREIGN KEY (wallet_id) REFERENCES wallets(id) ON DELETE SET NULL
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Airdrop Referrals
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS airdrop_referrals (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            airdrop_id INTEGER NOT NULL,
+            wallet_id INTEGER,
+            code TEXT,
+            link TEXT,
+            notes TEXT,
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL,
+            FOREIGN KEY (airdrop_id) REFERENCES airdrops(id) ON DELETE CASCADE,
+            FOREIGN KEY (wallet_id) REFERENCES wallets(id) ON DELETE SET NULL
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Migration: Add funding_source to wallets if it doesn't exist
+    sqlx::query(
+        r#"
+        ALTER TABLE wallets ADD COLUMN funding_source TEXT
+        "#,
+    )
+    .execute(pool)
+    .await
+    .ok(); // Ignore error if column already exists
+
+    // Task Completions
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS task_completions (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            task_id INTEGER NOT NULL,
+            airdrop_id INTEGER NOT NULL,
+            wallet_id INTEGER,
+            completed_on TEXT NOT NULL,
+            completed_at TEXT NOT NULL,
+            FOREIGN KEY (task_id) REFERENCES airdrop_daily_tasks(id) ON DELETE CASCADE,
+            FOREIGN KEY (airdrop_id) REFERENCES airdrops(id) ON DELETE CASCADE,
+            FOREIGN KEY (wallet_id) REFERENCES wallets(id) ON DELETE SET NULL
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Wallet Transactions
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS wallet_transactions (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            wallet_id INTEGER NOT NULL,
+            chain TEXT NOT NULL,
+            tx_hash TEXT NOT NULL,
+            block_number INTEGER,
+            timestamp TEXT,
+            from_address TEXT,
+            to_address TEXT,
+            value TEXT,
+            fee TEXT,
+            method TEXT,
+            status TEXT,
+            airdrop_id INTEGER,
+            created_at TEXT NOT NULL,
+            UNIQUE (wallet_id, chain, tx_hash),
+            FOREIGN KEY (wallet_id) REFERENCES wallets(id) ON DELETE CASCADE,
+            FOREIGN KEY (airdrop_id) REFERENCES airdrops(id) ON DELETE SET NULL
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Airdrop Contracts
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS airdrop_contracts (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            airdrop_id INTEGER NOT NULL,
+            chain TEXT,
+            address TEXT NOT NULL,
+            label TEXT,
+            created_at TEXT NOT NULL,
+            FOREIGN KEY (airdrop_id) REFERENCES airdrops(id) ON DELETE CASCADE
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Domain Contracts
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS domain_contracts (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            domain TEXT NOT NULL,
+            chain TEXT,
+            address TEXT NOT NULL,
+            label TEXT,
+            created_at TEXT NOT NULL
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Task Completion Transactions
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS task_completion_transactions (
+            completion_id INTEGER NOT NULL,
+            transaction_id INTEGER NOT NULL,
+            PRIMARY KEY (completion_id, transaction_id),
+            FOREIGN KEY (completion_id) REFERENCES task_completions(id) ON DELETE CASCADE,
+            FOREIGN KEY (transaction_id) REFERENCES wallet_transactions(id) ON DELETE CASCADE
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Completion Proofs
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS completion_proofs (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            completion_id INTEGER NOT NULL,
+            kind TEXT NOT NULL,
+            value TEXT NOT NULL,
+            chain TEXT,
+            note TEXT,
+            created_at TEXT NOT NULL,
+            FOREIGN KEY (completion_id) REFERENCES task_completions(id) ON DELETE CASCADE
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Chains
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS chains (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            name TEXT NOT NULL UNIQUE,
+            chain_id INTEGER,
+            rpc_url TEXT NOT NULL,
+            native_symbol TEXT NOT NULL DEFAULT 'ETH',
+            gas_threshold_gwei REAL,
+            gas_alerts_enabled INTEGER NOT NULL DEFAULT 1,
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Gas Prices
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS gas_prices (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            chain_id INTEGER NOT NULL,
+            gas_price_wei TEXT NOT NULL,
+            base_fee_wei TEXT,
+            priority_fee_wei TEXT,
+            gas_price_gwei REAL NOT NULL,
+            recorded_at TEXT NOT NULL,
+            FOREIGN KEY (chain_id) REFERENCES chains(id) ON DELETE CASCADE
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Migration: Add min_native_balance to chains if it doesn't exist
+    sqlx::query(
+        r#"
+        ALTER TABLE chains ADD COLUMN min_native_balance REAL
+        "#,
+    )
+    .execute(pool)
+    .await
+    .ok(); // Ignore error if column already exists
+
+    // Chain Tokens
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS chain_tokens (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            chain_id INTEGER NOT NULL,
+            symbol TEXT NOT NULL,
+            address TEXT NOT NULL,
+            decimals INTEGER NOT NULL,
+            created_at TEXT NOT NULL,
+            UNIQUE (chain_id, address),
+            FOREIGN KEY (chain_id) REFERENCES chains(id) ON DELETE CASCADE
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Balance Snapshots
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS balance_snapshots (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            wallet_id INTEGER NOT NULL,
+            chain_id INTEGER NOT NULL,
+            token_address TEXT,
+            symbol TEXT NOT NULL,
+            raw_balance TEXT NOT NULL,
+            balance REAL NOT NULL,
+            snapshot_date TEXT NOT NULL,
+            recorded_at TEXT NOT NULL,
+            FOREIGN KEY (wallet_id) REFERENCES wallets(id) ON DELETE CASCADE,
+            FOREIGN KEY (chain_id) REFERENCES chains(id) ON DELETE CASCADE
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Migration: Add scoring inputs to airdrops if they don't exist
+    sqlx::query(
+        r#"
+        ALTER TABLE airdrops ADD COLUMN expected_value REAL
+        "#,
+    )
+    .execute(pool)
+    .await
+    .ok(); // Ignore error if column already exists
+
+    sqlx::query(
+        r#"
+        ALTER TABLE airdrops ADD COLUMN confidence REAL
+        "#,
+    )
+    .execute(pool)
+    .await
+    .ok(); // Ignore error if column already exists
+
+    sqlx::query(
+        r#"
+        ALTER TABLE airdrops ADD COLUMN snapshot_date TEXT
+        "#,
+    )
+    .execute(pool)
+    .await
+    .ok(); // Ignore error if column already exists
+
+    // Airdrop Expenses
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS airdrop_expenses (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            airdrop_id INTEGER NOT NULL,
+            amount REAL NOT NULL,
+            currency TEXT NOT NULL DEFAULT 'USD',
+            description TEXT,
+            spent_on TEXT NOT NULL,
+            created_at TEXT NOT NULL,
+            FOREIGN KEY (airdrop_id) REFERENCES airdrops(id) ON DELETE CASCADE
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Settings
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS settings (
+            key TEXT PRIMARY KEY,
+            value TEXT NOT NULL,
+            updated_at TEXT NOT NULL
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Candidate Sources
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS candidate_sources (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            name TEXT NOT NULL,
+            location TEXT NOT NULL UNIQUE,
+            last_fetched_at TEXT,
+            last_error TEXT,
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Airdrop Candidates
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS airdrop_candidates (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            source_id INTEGER,
+            name TEXT NOT NULL,
+            url TEXT NOT NULL,
+            normalized_url TEXT NOT NULL UNIQUE,
+            chain TEXT,
+            description TEXT,
+            tasks TEXT NOT NULL DEFAULT '[]',
+            status TEXT NOT NULL DEFAULT 'new',
+            airdrop_id INTEGER,
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL,
+            FOREIGN KEY (source_id) REFERENCES candidate_sources(id) ON DELETE SET NULL,
+            FOREIGN KEY (airdrop_id) REFERENCES airdrops(id) ON DELETE SET NULL
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Migration: Add template pack fields to airdrop_types if they don't exist
+    sqlx::query(
+        r#"
+        ALTER TABLE airdrop_types ADD COLUMN reset_policy TEXT NOT NULL DEFAULT 'daily'
+        "#,
+    )
+    .execute(pool)
+    .await
+    .ok(); // Ignore error if column already exists
+
+    sqlx::query(
+        r#"
+        ALTER TABLE airdrop_types ADD COLUMN recommended_chains TEXT NOT NULL DEFAULT '[]'
+        "#,
+    )
+    .execute(pool)
+    .await
+    .ok(); // Ignore error if column already exists
+
+    sqlx::query(
+        r#"
+        ALTER TABLE airdrop_types ADD COLUMN version INTEGER NOT NULL DEFAULT 1
+        "#,
+    )
+    .execute(pool)
+    .await
+    .ok(); // Ignore error if column already exists
+
+    // Migration: Add template_version to airdrops if it doesn't exist
+    sqlx::query(
+        r#"
+        ALTER TABLE airdrops ADD COLUMN template_version INTEGER
+        "#,
+    )
+    .execute(pool)
+    .await
+    .ok(); // Ignore error if column already exists
+
+    // Browser Profiles
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS browser_profiles (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            name TEXT NOT NULL,
+            kind TEXT NOT NULL,
+            executable TEXT NOT NULL,
+            profile TEXT NOT NULL,
+            args_template TEXT NOT NULL DEFAULT '[]',
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Migration: Add browser_profile_id to airdrops and wallets if they don't exist
+    sqlx::query(
+        r#"
+        ALTER TABLE airdrops ADD COLUMN browser_profile_id INTEGER REFERENCES browser_profiles(id) ON DELETE SET NULL
+        "#,
+    )
+    .execute(pool)
+    .await
+    .ok(); // Ignore error if column already exists
+
+    sqlx::query(
+        r#"
+        ALTER TABLE wallets ADD COLUMN browser_profile_id INTEGER REFERENCES browser_profiles(id) ON DELETE SET NULL
+        "#,
+    )
+    .execute(pool)
+    .await
+    .ok(); // Ignore error if column already exists
+
+    // Airdrop Claims
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS airdrop_claims (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            airdrop_id INTEGER,
+            wallet_id INTEGER,
+            token TEXT NOT NULL,
+            amount REAL NOT NULL,
+            fair_value REAL NOT NULL,
+            currency TEXT NOT NULL DEFAULT 'USD',
+            received_at TEXT NOT NULL,
+            tx_hash TEXT,
+            notes TEXT,
+            created_at TEXT NOT NULL,
+            FOREIGN KEY (airdrop_id) REFERENCES airdrops(id) ON DELETE SET NULL,
+            FOREIGN KEY (wallet_id) REFERENCES wallets(id) ON DELETE SET NULL
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Token Disposals
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS token_disposals (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            token TEXT NOT NULL,
+            amount REAL NOT NULL,
+            proceeds REAL NOT NULL,
+            fee REAL NOT NULL DEFAULT 0,
+            currency TEXT NOT NULL DEFAULT 'USD',
+            disposed_at TEXT NOT NULL,
+            tx_hash TEXT,
+            notes TEXT,
+            created_at TEXT NOT NULL
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Disposal Lots (specific-ID matching)
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS disposal_lots (
+            disposal_id INTEGER NOT NULL,
+            claim_id INTEGER NOT NULL,
+            amount REAL NOT NULL,
+            PRIMARY KEY (disposal_id, claim_id),
+            FOREIGN KEY (disposal_id) REFERENCES token_disposals(id) ON DELETE CASCADE,
+            FOREIGN KEY (claim_id) REFERENCES airdrop_claims(id) ON DELETE CASCADE
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Prices
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS prices (
+            asset TEXT NOT NULL,
+            quote TEXT NOT NULL,
+            date TEXT NOT NULL,
+            price REAL NOT NULL,
+            source TEXT,
+            updated_at TEXT NOT NULL,
+            PRIMARY KEY (asset, quote, date)
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
     // Projects
     sqlx::query(
         r#"
@@ -139,6 +748,289 @@ pub async fn migrate(pool: &SqlitePool) -> Result<(), sqlx::Error> {
     .execute(pool)
     .await?;
 
+    // Migration: Add budget columns to projects if they don't exist
+    sqlx::query(
+        r#"
+        ALTER TABLE projects ADD COLUMN budget REAL
+        "#,
+    )
+    .execute(pool)
+    .await
+    .ok(); // Ignore error if column already exists
+
+    sqlx::query(
+        r#"
+        ALTER TABLE projects ADD COLUMN budget_currency TEXT
+        "#,
+    )
+    .execute(pool)
+    .await
+    .ok(); // Ignore error if column already exists
+
+    // Migration: Add parent_task_id to project_tasks if it doesn't exist
+    sqlx::query(
+        r#"
+        ALTER TABLE project_tasks ADD COLUMN parent_task_id INTEGER REFERENCES project_tasks(id) ON DELETE CASCADE
+        "#,
+    )
+    .execute(pool)
+    .await
+    .ok(); // Ignore error if column already exists
+
+    // Migration: Add scheduling columns to project_tasks if they don't exist
+    sqlx::query(
+        r#"
+        ALTER TABLE project_tasks ADD COLUMN due_date TEXT
+        "#,
+    )
+    .execute(pool)
+    .await
+    .ok(); // Ignore error if column already exists
+
+    sqlx::query(
+        r#"
+        ALTER TABLE project_tasks ADD COLUMN start_date TEXT
+        "#,
+    )
+    .execute(pool)
+    .await
+    .ok(); // Ignore error if column already exists
+
+    sqlx::query(
+        r#"
+        ALTER TABLE project_tasks ADD COLUMN priority INTEGER
+        "#,
+    )
+    .execute(pool)
+    .await
+    .ok(); // Ignore error if column already exists
+
+    sqlx::query(
+        r#"
+        ALTER TABLE project_tasks ADD COLUMN estimated_minutes INTEGER
+        "#,
+    )
+    .execute(pool)
+    .await
+    .ok(); // Ignore error if column already exists
+
+    sqlx::query(
+        r#"
+        ALTER TABLE project_tasks ADD COLUMN completed_at TEXT
+        "#,
+    )
+    .execute(pool)
+    .await
+    .ok(); // Ignore error if column already exists
+
+    // Migration: Add recurrence columns to project_tasks if they don't exist
+    sqlx::query(
+        r#"
+        ALTER TABLE project_tasks ADD COLUMN recurrence TEXT
+        "#,
+    )
+    .execute(pool)
+    .await
+    .ok(); // Ignore error if column already exists
+
+    sqlx::query(
+        r#"
+        ALTER TABLE project_tasks ADD COLUMN next_occurrence_id INTEGER REFERENCES project_tasks(id) ON DELETE SET NULL
+        "#,
+    )
+    .execute(pool)
+    .await
+    .ok(); // Ignore error if column already exists
+
+    // Project Columns (Kanban workflow states)
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS project_columns (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            project_id INTEGER NOT NULL,
+            name TEXT NOT NULL,
+            position INTEGER NOT NULL DEFAULT 0,
+            wip_limit INTEGER,
+            is_done INTEGER NOT NULL DEFAULT 0,
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL,
+            UNIQUE (project_id, name),
+            FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Migration: Add column_id to project_tasks if it doesn't exist
+    sqlx::query(
+        r#"
+        ALTER TABLE project_tasks ADD COLUMN column_id INTEGER REFERENCES project_columns(id) ON DELETE SET NULL
+        "#,
+    )
+    .execute(pool)
+    .await
+    .ok(); // Ignore error if column already exists
+
+    // Migration: Add column_order (position within the board column) to project_tasks
+    sqlx::query(
+        r#"
+        ALTER TABLE project_tasks ADD COLUMN column_order INTEGER NOT NULL DEFAULT 0
+        "#,
+    )
+    .execute(pool)
+    .await
+    .ok(); // Ignore error if column already exists
+
+    // Project Milestones
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS project_milestones (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            project_id INTEGER NOT NULL,
+            name TEXT NOT NULL,
+            description TEXT,
+            target_date TEXT,
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL,
+            FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Migration: Add milestone_id to project_tasks if it doesn't exist
+    sqlx::query(
+        r#"
+        ALTER TABLE project_tasks ADD COLUMN milestone_id INTEGER REFERENCES project_milestones(id) ON DELETE SET NULL
+        "#,
+    )
+    .execute(pool)
+    .await
+    .ok(); // Ignore error if column already exists
+
+    // Project Task Dependencies
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS project_task_dependencies (
+            task_id INTEGER NOT NULL,
+            blocked_by_task_id INTEGER NOT NULL,
+            created_at TEXT NOT NULL,
+            PRIMARY KEY (task_id, blocked_by_task_id),
+            CHECK (task_id != blocked_by_task_id),
+            FOREIGN KEY (task_id) REFERENCES project_tasks(id) ON DELETE CASCADE,
+            FOREIGN KEY (blocked_by_task_id) REFERENCES project_tasks(id) ON DELETE CASCADE
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Time Entries
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS time_entries (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            project_id INTEGER NOT NULL,
+            task_id INTEGER,
+            started_at TEXT NOT NULL,
+            ended_at TEXT,
+            duration_seconds INTEGER,
+            notes TEXT,
+            source TEXT NOT NULL DEFAULT 'timer',
+            stop_reason TEXT,
+            heartbeat_at TEXT,
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL,
+            FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE,
+            FOREIGN KEY (task_id) REFERENCES project_tasks(id) ON DELETE SET NULL
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // At most one timer runs at a time
+    sqlx::query("CREATE UNIQUE INDEX IF NOT EXISTS time_entries_one_running ON time_entries((1)) WHERE ended_at IS NULL")
+        .execute(pool)
+        .await?;
+
+    // Focus Sessions (one row per focus interval)
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS focus_sessions (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            task_id INTEGER,
+            airdrop_id INTEGER,
+            planned_seconds INTEGER NOT NULL,
+            focused_seconds INTEGER NOT NULL DEFAULT 0,
+            completed INTEGER NOT NULL DEFAULT 0,
+            started_at TEXT NOT NULL,
+            ended_at TEXT,
+            FOREIGN KEY (task_id) REFERENCES project_tasks(id) ON DELETE SET NULL,
+            FOREIGN KEY (airdrop_id) REFERENCES airdrops(id) ON DELETE SET NULL
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Focus Interruptions
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS focus_interruptions (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            session_id INTEGER NOT NULL,
+            note TEXT NOT NULL,
+            noted_at TEXT NOT NULL,
+            FOREIGN KEY (session_id) REFERENCES focus_sessions(id) ON DELETE CASCADE
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Project Status History
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS project_status_history (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            project_id INTEGER NOT NULL,
+            from_status TEXT,
+            to_status TEXT NOT NULL,
+            reason TEXT,
+            changed_at TEXT NOT NULL,
+            FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Statuses used to be free text; anything unknown would fail to load
+    sqlx::query(
+        "UPDATE projects SET status = 'active' WHERE status NOT IN ('active', 'paused', 'completed', 'archived')",
+    )
+    .execute(pool)
+    .await?;
+
+    // Project Templates
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS project_templates (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            name TEXT NOT NULL UNIQUE,
+            description TEXT,
+            content TEXT NOT NULL,
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
     // Ideas
     sqlx::query(
         r#"
@@ -1,16 +1,24 @@
 use log::{info, warn};
 use tauri::{Manager, State};
 use sqlx::sqlite::{SqliteConnectOptions, SqlitePool, SqlitePoolOptions};
+use std::path::PathBuf;
 use std::str::FromStr;
 
 mod models;
 mod commands;
 mod database;
 mod oauth;
+mod rpc;
+#[cfg(test)]
+mod test_support;
 
 #[derive(Clone)]
 pub struct AppState {
     pub db: SqlitePool,
+    /// App data directory; files the app manages (e.g. proof screenshots) live under it.
+    pub data_dir: PathBuf,
+    /// Running focus timer, kept here so it outlives webview reloads.
+    pub focus: commands::focus::FocusEngine,
 }
 
 pub fn run() {
@@ -18,6 +26,8 @@ pub fn run() {
     info!("phase=boot");
 
     tauri::Builder::default()
+        .plugin(tauri_plugin_shell::init())
+        .plugin(tauri_plugin_notification::init())
         .setup(|app| {
             let app_handle = app.handle().clone();
             tauri::async_runtime::block_on(async {
@@ -47,10 +57,22 @@ pub fn run() {
 
                 // Run migrations
                 database::migrate(&pool).await?;
+                commands::focus::close_stale_sessions(&pool).await?;
+                let recovered_entries = commands::time_entries::recover_entries(&pool).await?;
                 info!("phase=db_init_ok");
 
+                // Background pollers
+                commands::feeds::spawn_feed_poller(app_handle.clone(), pool.clone());
+                commands::gas::spawn_gas_watcher(app_handle.clone(), pool.clone());
+                commands::candidates::spawn_candidate_poller(app_handle.clone(), pool.clone());
+                commands::time_entries::spawn_time_tracker(app_handle.clone(), pool.clone(), recovered_entries);
+
                 // Store database pool in app state
-                app_handle.manage(AppState { db: pool });
+                app_handle.manage(AppState {
+                    db: pool,
+                    data_dir: app_data_dir,
+                    focus: commands::focus::FocusEngine::default(),
+                });
 
                 Ok::<(), Box<dyn std::error::Error>>(())
             })?;
@@ -65,19 +87,200 @@ pub fn run() {
             commands::airdrops::delete_airdrop,
             commands::airdrops::list_airdrop_types,
             commands::airdrops::create_airdrop_type,
+            commands::airdrops::update_airdrop_type,
             commands::airdrops::reorder_airdrops,
             // Airdrop Daily Tasks
             commands::airdrops::list_airdrop_daily_tasks,
             commands::airdrops::create_airdrop_daily_task,
             commands::airdrops::delete_airdrop_daily_task,
             commands::airdrops::mark_task_done_today,
+            commands::airdrops::list_task_completions,
+            commands::airdrops::delete_task_completion,
+            // Completion Proofs
+            commands::proofs::attach_completion_proof,
+            commands::proofs::list_completion_proofs,
+            commands::proofs::delete_completion_proof,
+            // Airdrop Import / Export
+            commands::airdrop_io::import_airdrops,
+            commands::airdrop_io::export_airdrops_csv,
+            // Airdrop Type Template Packs
+            commands::template_packs::export_template_pack,
+            commands::template_packs::import_template_pack,
+            commands::template_packs::sync_template_tasks,
+            // Airdrop URL Health
+            commands::url_health::check_airdrop_urls,
+            commands::url_health::start_airdrop_url_check,
+            commands::url_health::list_airdrop_url_health,
+            commands::url_health::list_trusted_redirect_domains,
+            commands::url_health::trust_redirect_domain,
+            // Airdrop Feeds
+            commands::feeds::list_airdrop_feeds,
+            commands::feeds::create_airdrop_feed,
+            commands::feeds::delete_airdrop_feed,
+            commands::feeds::poll_airdrop_feeds,
+            commands::feeds::list_feed_items,
+            commands::feeds::feed_unread_counts,
+            commands::feeds::mark_feed_items_read,
+            commands::feeds::get_feed_settings,
+            commands::feeds::update_feed_settings,
+            // Airdrop Candidates
+            commands::candidates::list_candidate_sources,
+            commands::candidates::create_candidate_source,
+            commands::candidates::delete_candidate_source,
+            commands::candidates::refresh_candidate_sources,
+            commands::candidates::list_airdrop_candidates,
+            commands::candidates::accept_airdrop_candidate,
+            commands::candidates::dismiss_airdrop_candidate,
+            // Wallets
+            commands::wallets::list_wallets,
+            commands::wallets::create_wallet,
+            commands::wallets::update_wallet,
+            commands::wallets::delete_wallet,
+            // Browser Profiles
+            commands::browser_profiles::list_browser_profiles,
+            commands::browser_profiles::create_browser_profile,
+            commands::browser_profiles::update_browser_profile,
+            commands::browser_profiles::delete_browser_profile,
+            commands::browser_profiles::set_airdrop_browser_profile,
+            commands::browser_profiles::set_wallet_browser_profile,
+            commands::browser_profiles::open_airdrop,
+            // Identities / Referrals
+            commands::identities::list_social_identities,
+            commands::identities::create_social_identity,
+            commands::identities::update_social_identity,
+            commands::identities::delete_social_identity,
+            commands::identities::link_airdrop_identity,
+            commands::identities::unlink_airdrop_identity,
+            commands::identities::list_identity_usage,
+            commands::identities::list_identity_reuse_warnings,
+            commands::identities::list_airdrop_referrals,
+            commands::identities::create_airdrop_referral,
+            commands::identities::update_airdrop_referral,
+            commands::identities::delete_airdrop_referral,
+            // Sybil Audit
+            commands::sybil::sybil_audit_report,
+            // Wallet Transactions
+            commands::transactions::import_wallet_transactions,
+            commands::transactions::list_wallet_transactions,
+            commands::transactions::delete_wallet_transaction,
+            commands::transactions::rematch_wallet_transactions,
+            commands::transactions::list_contract_mappings,
+            commands::transactions::create_contract_mapping,
+            commands::transactions::delete_airdrop_contract,
+            commands::transactions::delete_domain_contract,
+            commands::transactions::link_completion_transaction,
+            commands::transactions::unlink_completion_transaction,
+            commands::transactions::list_completion_transactions,
+            // Chains / Gas
+            commands::chains::list_chains,
+            commands::chains::create_chain,
+            commands::chains::update_chain,
+            commands::chains::delete_chain,
+            commands::chains::list_chain_tokens,
+            commands::chains::create_chain_token,
+            commands::chains::delete_chain_token,
+            commands::gas::poll_gas_prices,
+            commands::gas::list_gas_price_history,
+            commands::gas::latest_gas_prices,
+            // Wallet Balances
+            commands::balances::snapshot_wallet_balances,
+            commands::balances::list_balance_snapshots,
+            commands::balances::list_funding_needs,
+            // Airdrop Expenses / Scoring
+            commands::expenses::list_airdrop_expenses,
+            commands::expenses::create_airdrop_expense,
+            commands::expenses::delete_airdrop_expense,
+            commands::scoring::get_scoring_weights,
+            commands::scoring::update_scoring_weights,
+            commands::scoring::rank_airdrops,
+            // Claims / Disposals / Tax
+            commands::claims::list_airdrop_claims,
+            commands::claims::create_airdrop_claim,
+            commands::claims::delete_airdrop_claim,
+            commands::claims::list_token_disposals,
+            commands::claims::create_token_disposal,
+            commands::claims::delete_token_disposal,
+            commands::claims::list_disposal_lots,
+            commands::tax::tax_report,
+            commands::tax::export_tax_report_csv,
+            // Prices / Valuation
+            commands::prices::get_currency_settings,
+            commands::prices::update_currency_settings,
+            commands::prices::list_prices,
+            commands::prices::upsert_price,
+            commands::prices::delete_price,
+            commands::prices::import_prices_csv,
+            commands::prices::fetch_prices,
+            commands::prices::convert_amount,
+            commands::valuation::valuation_summary,
+            // Settings
+            commands::settings::get_setting,
+            commands::settings::set_setting,
             // Projects
             commands::projects::list_projects,
             commands::projects::create_project,
             commands::projects::update_project,
             commands::projects::delete_project,
+            commands::projects::unarchive_project,
+            commands::projects::list_project_status_history,
+            // Project Templates
+            commands::project_templates::list_project_templates,
+            commands::project_templates::save_project_as_template,
+            commands::project_templates::delete_project_template,
+            commands::project_templates::instantiate_project_template,
+            commands::project_templates::clone_project,
             // Project Tasks
             commands::projects::list_project_tasks,
+            commands::projects::list_project_task_tree,
+            commands::projects::move_project_task,
+            commands::projects::list_overdue_project_tasks,
+            commands::projects::list_project_tasks_due_this_week,
+            commands::projects::list_project_tasks_by_priority,
+            // Project Columns
+            commands::project_columns::list_project_columns,
+            commands::project_columns::get_project_board,
+            commands::project_columns::create_project_column,
+            commands::project_columns::update_project_column,
+            commands::project_columns::delete_project_column,
+            commands::project_columns::reorder_project_columns,
+            commands::project_columns::move_task_to_column,
+            // Time Tracking
+            commands::time_entries::start_time_entry,
+            commands::time_entries::stop_time_entry,
+            commands::time_entries::get_running_time_entry,
+            commands::time_entries::list_time_entries,
+            commands::time_entries::create_time_entry,
+            commands::time_entries::update_time_entry,
+            commands::time_entries::delete_time_entry,
+            commands::time_entries::time_report,
+            commands::time_entries::estimate_vs_actual,
+            // Focus Timer
+            commands::focus::get_focus_settings,
+            commands::focus::update_focus_settings,
+            commands::focus::get_focus_state,
+            commands::focus::start_focus,
+            commands::focus::pause_focus,
+            commands::focus::resume_focus,
+            commands::focus::skip_focus_phase,
+            commands::focus::stop_focus,
+            commands::focus::add_focus_interruption,
+            commands::focus::list_focus_sessions,
+            // Project Milestones
+            commands::milestones::get_planning_settings,
+            commands::milestones::update_planning_settings,
+            commands::milestones::list_project_milestones,
+            commands::milestones::create_project_milestone,
+            commands::milestones::update_project_milestone,
+            commands::milestones::delete_project_milestone,
+            commands::milestones::set_task_milestone,
+            commands::milestones::list_project_progress,
+            // Recurring Tasks
+            commands::recurrence::set_task_recurrence,
+            // Project Task Dependencies
+            commands::task_dependencies::list_task_dependencies,
+            commands::task_dependencies::add_task_dependency,
+            commands::task_dependencies::remove_task_dependency,
+            commands::task_dependencies::list_ready_project_tasks,
             commands::projects::create_project_task,
             commands::projects::update_project_task,
             commands::projects::delete_project_task,
@@ -98,7 +301,26 @@ pub fn run() {
             // Research
             commands::research::research_context7_exa,
         ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|app_handle, event| {
+            if let tauri::RunEvent::Exit = event {
+                // Don't leave a timer running while the app is closed
+                if let Some(state) = app_handle.try_state::<AppState>() {
+                    let stopped = tauri::async_runtime::block_on(
+                        commands::time_entries::stop_running_entry(&state.db, "app_exit"),
+                    );
+                    if let Err(e) = stopped {
+                        warn!("phase=timer_exit_stop_failed error={}", e);
+                    }
+                    let focus_stopped = tauri::async_runtime::block_on(
+                        commands::focus::stop_running_focus(&state.db, &state.focus),
+                    );
+                    if let Err(e) = focus_stopped {
+                        warn!("phase=focus_exit_stop_failed error={}", e);
+                    }
+                }
+            }
+        });
 }
 
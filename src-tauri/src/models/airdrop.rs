@@ -8,6 +8,14 @@ pub struct AirdropType {
     pub name: String,
     #[sqlx(json)]
     pub default_tasks: serde_json::Value,
+    /// `daily`, `weekly` or `once`
+    #[sqlx(default)]
+    pub reset_policy: String,
+    #[sqlx(json)]
+    pub recommended_chains: Vec<String>,
+    /// Bumped whenever `default_tasks` changes
+    #[sqlx(default)]
+    pub version: i64,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -25,6 +33,20 @@ pub struct Airdrop {
     pub position: i64,
     pub notes: Option<String>,
     pub active: bool,
+    /// Estimated payout, in the base currency
+    #[sqlx(default)]
+    pub expected_value: Option<f64>,
+    /// 0.0..=1.0 likelihood the payout happens as estimated
+    #[sqlx(default)]
+    pub confidence: Option<f64>,
+    /// `YYYY-MM-DD` of the expected eligibility snapshot
+    #[sqlx(default)]
+    pub snapshot_date: Option<String>,
+    /// Type version whose tasks were last synced into this airdrop
+    #[sqlx(default)]
+    pub template_version: Option<i64>,
+    #[sqlx(default)]
+    pub browser_profile_id: Option<i64>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -42,3 +64,51 @@ pub struct AirdropDailyTask {
 }
 
 
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct AirdropUrlHealth {
+    pub airdrop_id: i64,
+    pub url: String,
+    pub status_code: Option<i64>,
+    pub final_url: Option<String>,
+    pub page_title: Option<String>,
+    pub error: Option<String>,
+    pub is_dead: bool,
+    pub is_redirected: bool,
+    pub phishing_warning: bool,
+    pub checked_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct TaskCompletion {
+    pub id: i64,
+    pub task_id: i64,
+    pub airdrop_id: i64,
+    pub wallet_id: Option<i64>,
+    pub completed_on: String,
+    pub completed_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct CompletionProof {
+    pub id: i64,
+    pub completion_id: i64,
+    /// `tx_hash`, `url` or `screenshot`
+    pub kind: String,
+    /// The hash, the URL, or the screenshot path relative to the app data dir
+    pub value: String,
+    pub chain: Option<String>,
+    pub note: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct AirdropExpense {
+    pub id: i64,
+    pub airdrop_id: i64,
+    pub amount: f64,
+    pub currency: String,
+    pub description: Option<String>,
+    pub spent_on: String,
+    pub created_at: DateTime<Utc>,
+}
@@ -0,0 +1,19 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct BrowserProfile {
+    pub id: i64,
+    pub name: String,
+    /// `chromium`, `firefox` or `custom`
+    pub kind: String,
+    pub executable: String,
+    /// Chromium profile directory or Firefox profile name
+    pub profile: String,
+    /// One entry per argument; `{url}` and `{profile}` are substituted
+    #[sqlx(json)]
+    pub args_template: Vec<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
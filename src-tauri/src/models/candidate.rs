@@ -0,0 +1,33 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct CandidateSource {
+    pub id: i64,
+    pub name: String,
+    /// HTTP(S) URL, `file://` URL or local path of a JSON list
+    pub location: String,
+    pub last_fetched_at: Option<DateTime<Utc>>,
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct AirdropCandidate {
+    pub id: i64,
+    pub source_id: Option<i64>,
+    pub name: String,
+    pub url: String,
+    pub normalized_url: String,
+    pub chain: Option<String>,
+    pub description: Option<String>,
+    #[sqlx(json)]
+    pub tasks: Vec<String>,
+    /// `new`, `accepted` or `dismissed`
+    pub status: String,
+    pub airdrop_id: Option<i64>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
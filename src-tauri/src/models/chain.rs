@@ -0,0 +1,57 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Chain {
+    pub id: i64,
+    /// Matches the free-form chain names used on airdrops and wallets
+    pub name: String,
+    pub chain_id: Option<i64>,
+    pub rpc_url: String,
+    pub native_symbol: String,
+    pub gas_threshold_gwei: Option<f64>,
+    pub gas_alerts_enabled: bool,
+    /// Native balance below which a wallet on this chain needs topping up
+    #[sqlx(default)]
+    pub min_native_balance: Option<f64>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct GasPrice {
+    pub id: i64,
+    pub chain_id: i64,
+    /// Wei amounts are stored as decimal strings; they overflow i64.
+    pub gas_price_wei: String,
+    pub base_fee_wei: Option<String>,
+    pub priority_fee_wei: Option<String>,
+    pub gas_price_gwei: f64,
+    pub recorded_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ChainToken {
+    pub id: i64,
+    pub chain_id: i64,
+    pub symbol: String,
+    pub address: String,
+    pub decimals: i64,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct BalanceSnapshot {
+    pub id: i64,
+    pub wallet_id: i64,
+    pub chain_id: i64,
+    /// `None` for the chain's native asset
+    pub token_address: Option<String>,
+    pub symbol: String,
+    pub raw_balance: String,
+    pub balance: f64,
+    /// `YYYY-MM-DD`, local date of the snapshot run
+    pub snapshot_date: String,
+    pub recorded_at: DateTime<Utc>,
+}
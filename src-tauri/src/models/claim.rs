@@ -0,0 +1,42 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct AirdropClaim {
+    pub id: i64,
+    pub airdrop_id: Option<i64>,
+    pub wallet_id: Option<i64>,
+    pub token: String,
+    pub amount: f64,
+    /// Total fair value of `amount` when received; the lot's cost basis
+    pub fair_value: f64,
+    pub currency: String,
+    pub received_at: DateTime<Utc>,
+    pub tx_hash: Option<String>,
+    pub notes: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct TokenDisposal {
+    pub id: i64,
+    pub token: String,
+    pub amount: f64,
+    /// Gross proceeds of the sale
+    pub proceeds: f64,
+    pub fee: f64,
+    pub currency: String,
+    pub disposed_at: DateTime<Utc>,
+    pub tx_hash: Option<String>,
+    pub notes: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Claim lot chosen for a disposal under specific-ID matching.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct DisposalLot {
+    pub disposal_id: i64,
+    pub claim_id: i64,
+    pub amount: f64,
+}
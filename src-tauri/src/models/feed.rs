@@ -0,0 +1,29 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct AirdropFeed {
+    pub id: i64,
+    pub airdrop_id: i64,
+    pub url: String,
+    pub title: Option<String>,
+    pub last_polled_at: Option<DateTime<Utc>>,
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct FeedItem {
+    pub id: i64,
+    pub feed_id: i64,
+    pub airdrop_id: i64,
+    pub guid: String,
+    pub title: String,
+    pub link: Option<String>,
+    pub summary: Option<String>,
+    pub published_at: Option<DateTime<Utc>>,
+    pub read: bool,
+    pub created_at: DateTime<Utc>,
+}
@@ -0,0 +1,24 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct FocusSession {
+    pub id: i64,
+    pub task_id: Option<i64>,
+    pub airdrop_id: Option<i64>,
+    pub planned_seconds: i64,
+    pub focused_seconds: i64,
+    /// Ran to the end rather than being skipped or stopped
+    pub completed: bool,
+    pub started_at: DateTime<Utc>,
+    pub ended_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct FocusInterruption {
+    pub id: i64,
+    pub session_id: i64,
+    pub note: String,
+    pub noted_at: DateTime<Utc>,
+}
@@ -0,0 +1,35 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct SocialIdentity {
+    pub id: i64,
+    /// `x`, `discord`, `telegram`, `email` or any other platform name
+    pub platform: String,
+    pub handle: String,
+    pub notes: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct AirdropIdentity {
+    pub id: i64,
+    pub airdrop_id: i64,
+    pub identity_id: i64,
+    pub wallet_id: Option<i64>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct AirdropReferral {
+    pub id: i64,
+    pub airdrop_id: i64,
+    pub wallet_id: Option<i64>,
+    pub code: Option<String>,
+    pub link: Option<String>,
+    pub notes: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
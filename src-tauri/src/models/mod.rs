@@ -3,11 +3,33 @@ pub mod project;
 pub mod idea;
 pub mod house;
 pub mod oauth_token;
+pub mod feed;
+pub mod wallet;
+pub mod identity;
+pub mod transaction;
+pub mod chain;
+pub mod candidate;
+pub mod browser;
+pub mod claim;
+pub mod price;
+pub mod time_entry;
+pub mod focus;
 
 pub use airdrop::*;
 pub use project::*;
 pub use idea::*;
 pub use house::*;
 pub use oauth_token::*;
+pub use feed::*;
+pub use wallet::*;
+pub use identity::*;
+pub use transaction::*;
+pub use chain::*;
+pub use candidate::*;
+pub use browser::*;
+pub use claim::*;
+pub use price::*;
+pub use time_entry::*;
+pub use focus::*;
 
 
@@ -0,0 +1,17 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Price {
+    /// Token symbol or currency code, uppercase
+    pub asset: String,
+    pub quote: String,
+    /// `YYYY-MM-DD`
+    pub date: String,
+    /// Units of `quote` per one `asset`
+    pub price: f64,
+    /// `csv`, `http` or `manual`
+    pub source: Option<String>,
+    pub updated_at: DateTime<Utc>,
+}
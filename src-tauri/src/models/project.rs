@@ -3,8 +3,9 @@ use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use std::str::FromStr;
 
-#[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
 #[sqlx(type_name = "project_status", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
 pub enum ProjectStatus {
     Active,
     Paused,
@@ -12,6 +13,30 @@ pub enum ProjectStatus {
     Archived,
 }
 
+impl ProjectStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ProjectStatus::Active => "active",
+            ProjectStatus::Paused => "paused",
+            ProjectStatus::Completed => "completed",
+            ProjectStatus::Archived => "archived",
+        }
+    }
+
+    /// Whether a regular status update may move a project from `self` to
+    /// `next`. Archived projects only come back through an explicit unarchive.
+    pub fn can_transition_to(&self, next: ProjectStatus) -> bool {
+        use ProjectStatus::*;
+        match (self, next) {
+            (from, to) if *from == to => true,
+            (Active, Paused | Completed | Archived) => true,
+            (Paused, Active | Completed | Archived) => true,
+            (Completed, Active | Archived) => true,
+            _ => false,
+        }
+    }
+}
+
 impl FromStr for ProjectStatus {
     type Err = String;
     
@@ -32,6 +57,11 @@ pub struct Project {
     pub name: String,
     pub description: Option<String>,
     pub status: ProjectStatus,
+    #[sqlx(default)]
+    pub budget: Option<f64>,
+    /// Defaults to the base currency when unset
+    #[sqlx(default)]
+    pub budget_currency: Option<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -43,7 +73,135 @@ pub struct ProjectTask {
     pub title: String,
     pub done: bool,
     pub order: i64,
+    #[sqlx(default)]
+    pub parent_task_id: Option<i64>,
+    /// YYYY-MM-DD
+    #[sqlx(default)]
+    pub due_date: Option<String>,
+    /// YYYY-MM-DD
+    #[sqlx(default)]
+    pub start_date: Option<String>,
+    /// 0 (P0, most urgent) to 3 (P3)
+    #[sqlx(default)]
+    pub priority: Option<i64>,
+    #[sqlx(default)]
+    pub estimated_minutes: Option<i64>,
+    /// Set when `done` flips to true, cleared when it flips back
+    #[sqlx(default)]
+    pub completed_at: Option<DateTime<Utc>>,
+    #[sqlx(default)]
+    pub milestone_id: Option<i64>,
+    /// Recurrence rule JSON, see `commands::recurrence::RecurrenceRule`
+    #[sqlx(default)]
+    pub recurrence: Option<serde_json::Value>,
+    /// The occurrence generated when this one was completed
+    #[sqlx(default)]
+    pub next_occurrence_id: Option<i64>,
+    /// Workflow column; `done` mirrors the column's `is_done`
+    #[sqlx(default)]
+    pub column_id: Option<i64>,
+    /// Position within the column on the board; `order` is the sibling order
+    #[sqlx(default)]
+    pub column_order: i64,
+    /// Computed: some blocked-by task is still open
+    #[sqlx(default)]
+    pub blocked: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A project task listed across projects.
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct ScheduledProjectTask {
+    #[sqlx(flatten)]
+    #[serde(flatten)]
+    pub task: ProjectTask,
+    pub project_name: String,
+}
+
+/// A task with its subtasks and the done state rolled up from its leaves.
+#[derive(Debug, Clone, Serialize)]
+pub struct ProjectTaskNode {
+    #[serde(flatten)]
+    pub task: ProjectTask,
+    pub children: Vec<ProjectTaskNode>,
+    /// Leaf tasks under this node (the node itself when it has no children)
+    pub total_leaves: i64,
+    pub done_leaves: i64,
+    /// 0-100
+    pub completion: f64,
+    /// Whether every leaf is done; same as `done` for a leaf
+    pub rolled_up_done: bool,
+}
+
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ProjectStatusChange {
+    pub id: i64,
+    pub project_id: i64,
+    /// `None` for the status a project was created with
+    pub from_status: Option<ProjectStatus>,
+    pub to_status: ProjectStatus,
+    pub reason: Option<String>,
+    pub changed_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ProjectColumn {
+    pub id: i64,
+    pub project_id: i64,
+    pub name: String,
+    pub position: i64,
+    pub wip_limit: Option<i64>,
+    /// Tasks in this column count as done
+    pub is_done: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ProjectMilestone {
+    pub id: i64,
+    pub project_id: i64,
+    pub name: String,
+    pub description: Option<String>,
+    /// YYYY-MM-DD
+    pub target_date: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ProjectTemplate {
+    pub id: i64,
+    pub name: String,
+    pub description: Option<String>,
+    /// Project structure, see `commands::project_templates::TemplateContent`
+    pub content: serde_json::Value,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ProjectStatus::*;
+
+    #[test]
+    fn status_transitions() {
+        assert!(Active.can_transition_to(Paused));
+        assert!(Active.can_transition_to(Completed));
+        assert!(Paused.can_transition_to(Active));
+        assert!(Completed.can_transition_to(Active));
+        assert!(Completed.can_transition_to(Archived));
+        assert!(!Completed.can_transition_to(Paused));
+    }
+
+    #[test]
+    fn archived_projects_only_leave_through_unarchive() {
+        for next in [Active, Paused, Completed] {
+            assert!(!Archived.can_transition_to(next), "{:?}", next);
+        }
+        assert!(Archived.can_transition_to(Archived));
+    }
+}
@@ -0,0 +1,23 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct TimeEntry {
+    pub id: i64,
+    pub project_id: i64,
+    pub task_id: Option<i64>,
+    pub started_at: DateTime<Utc>,
+    /// `None` while the timer is running
+    pub ended_at: Option<DateTime<Utc>>,
+    pub duration_seconds: Option<i64>,
+    pub notes: Option<String>,
+    /// "timer" or "manual"
+    pub source: String,
+    /// "manual", "app_exit" or "recovered" for timer entries
+    pub stop_reason: Option<String>,
+    /// Last time the running app saw this timer; used to close it after a crash
+    pub heartbeat_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
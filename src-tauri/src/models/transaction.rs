@@ -0,0 +1,45 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct WalletTransaction {
+    pub id: i64,
+    pub wallet_id: i64,
+    pub chain: String,
+    pub tx_hash: String,
+    pub block_number: Option<i64>,
+    pub timestamp: Option<DateTime<Utc>>,
+    pub from_address: Option<String>,
+    pub to_address: Option<String>,
+    /// Native amount transferred, as a decimal string
+    pub value: Option<String>,
+    /// Native fee paid, as a decimal string
+    pub fee: Option<String>,
+    pub method: Option<String>,
+    pub status: Option<String>,
+    pub airdrop_id: Option<i64>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A contract that belongs to one airdrop.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct AirdropContract {
+    pub id: i64,
+    pub airdrop_id: i64,
+    pub chain: Option<String>,
+    pub address: String,
+    pub label: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A contract known to belong to a campaign domain, matched against airdrop URLs.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct DomainContract {
+    pub id: i64,
+    pub domain: String,
+    pub chain: Option<String>,
+    pub address: String,
+    pub label: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
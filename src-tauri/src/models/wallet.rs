@@ -0,0 +1,73 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Wallet {
+    pub id: i64,
+    pub label: String,
+    pub address: String,
+    #[sqlx(json)]
+    pub chains: Vec<String>,
+    /// Wallets sharing a group may be linked; wallets in different (or no) groups must stay separate.
+    pub group_name: Option<String>,
+    /// Where the wallet's first funds came from (exchange, bridge or another address).
+    #[sqlx(default)]
+    pub funding_source: Option<String>,
+    /// Browser profile holding this wallet's extension
+    #[sqlx(default)]
+    pub browser_profile_id: Option<i64>,
+    pub notes: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Wallet {
+    /// Whether linking this wallet to `other` would join wallets meant to stay separate.
+    pub fn must_stay_separate_from(&self, other: &Wallet) -> bool {
+        if self.id == other.id {
+            return false;
+        }
+        match (&self.group_name, &other.group_name) {
+            (Some(a), Some(b)) => !a.eq_ignore_ascii_case(b),
+            _ => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wallet(id: i64, group_name: Option<&str>) -> Wallet {
+        Wallet {
+            id,
+            label: format!("Wallet {}", id),
+            address: format!("0x{:040x}", id),
+            chains: Vec::new(),
+            group_name: group_name.map(str::to_string),
+            funding_source: None,
+            browser_profile_id: None,
+            notes: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn wallets_may_only_be_linked_within_a_group() {
+        let main = wallet(1, Some("Main"));
+        let main_alt = wallet(2, Some("main"));
+        let farm = wallet(3, Some("Farm"));
+        let loner = wallet(4, None);
+        let other_loner = wallet(5, None);
+
+        assert!(!main.must_stay_separate_from(&main_alt));
+        assert!(main.must_stay_separate_from(&farm));
+        assert!(main.must_stay_separate_from(&loner));
+        assert!(loner.must_stay_separate_from(&main));
+        assert!(loner.must_stay_separate_from(&other_loner));
+        // A wallet is never separate from itself, grouped or not
+        assert!(!loner.must_stay_separate_from(&loner));
+    }
+}
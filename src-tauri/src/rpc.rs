@@ -0,0 +1,206 @@
+// Minimal EVM JSON-RPC client used by the gas watcher and balance snapshots.
+// Every call takes the endpoint URL so a local node (e.g. anvil) can stand in.
+
+use serde_json::{json, Value};
+use std::time::Duration;
+
+use crate::commands::transactions::{parse_quantity, MAX_TOKEN_DECIMALS};
+
+const RPC_TIMEOUT: Duration = Duration::from_secs(15);
+
+pub fn client() -> Result<reqwest::Client, String> {
+    reqwest::Client::builder()
+        .timeout(RPC_TIMEOUT)
+        .build()
+        .map_err(|e| format!("Failed to build RPC client: {}", e))
+}
+
+pub async fn call(
+    client: &reqwest::Client,
+    url: &str,
+    method: &str,
+    params: Value,
+) -> Result<Value, String> {
+    let response: Value = client
+        .post(url)
+        .json(&json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": method,
+            "params": params,
+        }))
+        .send()
+        .await
+        .map_err(|e| format!("{} request failed: {}", method, e))?
+        .json()
+        .await
+        .map_err(|e| format!("Invalid {} response: {}", method, e))?;
+
+    if let Some(error) = response.get("error") {
+        return Err(format!("{} returned an error: {}", method, error));
+    }
+
+    response
+        .get("result")
+        .cloned()
+        .ok_or_else(|| format!("{} response has no result", method))
+}
+
+fn quantity(value: &Value, method: &str) -> Result<u128, String> {
+    value
+        .as_str()
+        .and_then(parse_quantity)
+        .ok_or_else(|| format!("{} returned an invalid quantity: {}", method, value))
+}
+
+/// `eth_gasPrice`, in wei.
+pub async fn gas_price(client: &reqwest::Client, url: &str) -> Result<u128, String> {
+    let result = call(client, url, "eth_gasPrice", json!([])).await?;
+    quantity(&result, "eth_gasPrice")
+}
+
+/// Next block's base fee and the median priority fee over recent blocks, in wei.
+pub async fn fee_history(client: &reqwest::Client, url: &str) -> Result<(u128, Option<u128>), String> {
+    let result = call(client, url, "eth_feeHistory", json!(["0x5", "latest", [50]])).await?;
+
+    let base_fee = result
+        .get("baseFeePerGas")
+        .and_then(|fees| fees.as_array())
+        .and_then(|fees| fees.last())
+        .ok_or("eth_feeHistory response has no baseFeePerGas")?;
+    let base_fee = quantity(base_fee, "eth_feeHistory")?;
+
+    let mut rewards: Vec<u128> = result
+        .get("reward")
+        .and_then(|r| r.as_array())
+        .map(|blocks| {
+            blocks
+                .iter()
+                .filter_map(|block| block.get(0))
+                .filter_map(|reward| reward.as_str().and_then(parse_quantity))
+                .collect()
+        })
+        .unwrap_or_default();
+    rewards.sort_unstable();
+    let priority_fee = rewards.get(rewards.len() / 2).copied();
+
+    Ok((base_fee, priority_fee))
+}
+
+/// `eth_getBalance` at the latest block, in wei.
+pub async fn native_balance(client: &reqwest::Client, url: &str, address: &str) -> Result<u128, String> {
+    let result = call(client, url, "eth_getBalance", json!([address, "latest"])).await?;
+    quantity(&result, "eth_getBalance")
+}
+
+/// ERC-20 `balanceOf(address)` via `eth_call`, in token base units.
+pub async fn erc20_balance(
+    client: &reqwest::Client,
+    url: &str,
+    token: &str,
+    owner: &str,
+) -> Result<u128, String> {
+    let owner = owner.trim_start_matches("0x").to_lowercase();
+    // balanceOf(address) selector followed by the owner left-padded to 32 bytes
+    let data = format!("0x70a08231{:0>64}", owner);
+    let result = call(
+        client,
+        url,
+        "eth_call",
+        json!([{ "to": token, "data": data }, "latest"]),
+    )
+    .await?;
+
+    let raw = result.as_str().ok_or("eth_call returned a non-string result")?;
+    let hex = raw.trim_start_matches("0x").trim_start_matches('0');
+    if hex.is_empty() {
+        return Ok(0);
+    }
+    u128::from_str_radix(hex, 16).map_err(|_| format!("Token balance too large or invalid: {}", raw))
+}
+
+/// ERC-20 `decimals()` via `eth_call`.
+pub async fn erc20_decimals(client: &reqwest::Client, url: &str, token: &str) -> Result<u32, String> {
+    let result = call(
+        client,
+        url,
+        "eth_call",
+        json!([{ "to": token, "data": "0x313ce567" }, "latest"]),
+    )
+    .await?;
+
+    result
+        .as_str()
+        .and_then(parse_quantity)
+        .and_then(|d| u32::try_from(d).ok())
+        .filter(|d| *d <= MAX_TOKEN_DECIMALS)
+        .ok_or_else(|| format!("{} does not look like an ERC-20 token", token))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::rpc_stand_in;
+
+    const TOKEN: &str = "0x833589fcd6edb6e08f4c7c32d4f71b54bda02913";
+    const OWNER: &str = "0xF39Fd6e51aad88F6F4ce6aB8827279cffFb92266";
+
+    async fn node() -> String {
+        rpc_stand_in(|method, params| match method {
+            "eth_gasPrice" => Ok(json!("0x3b9aca00")),
+            "eth_feeHistory" => Ok(json!({
+                "oldestBlock": "0x10",
+                "baseFeePerGas": ["0x1", "0x2", "0x3", "0x4", "0x5", "0x77359400"],
+                "reward": [["0x5"], ["0x1"], ["0x3"], ["0x4"], ["0x2"]],
+            })),
+            "eth_getBalance" => Ok(json!("0xde0b6b3a7640000")),
+            "eth_call" => match params[0]["data"].as_str().unwrap_or_default() {
+                "0x313ce567" => Ok(json!(format!("0x{:064x}", 6))),
+                data if data.starts_with("0x70a08231") => {
+                    // balanceOf: owner is lowercased and left-padded
+                    assert_eq!(&data[10..], format!("{:0>64}", OWNER[2..].to_lowercase()));
+                    Ok(json!(format!("0x{:064x}", 1_234_567u128)))
+                }
+                _ => Ok(json!("0x")),
+            },
+            _ => Err(json!({ "code": -32601, "message": "method not found" })),
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn decodes_gas_price_and_fee_history() {
+        let url = node().await;
+        let client = client().unwrap();
+
+        assert_eq!(gas_price(&client, &url).await.unwrap(), 1_000_000_000);
+        // Latest base fee, median of the per-block priority fees
+        assert_eq!(fee_history(&client, &url).await.unwrap(), (2_000_000_000, Some(3)));
+    }
+
+    #[tokio::test]
+    async fn decodes_balances_and_decimals() {
+        let url = node().await;
+        let client = client().unwrap();
+
+        assert_eq!(native_balance(&client, &url, OWNER).await.unwrap(), 1_000_000_000_000_000_000);
+        assert_eq!(erc20_balance(&client, &url, TOKEN, OWNER).await.unwrap(), 1_234_567);
+        assert_eq!(erc20_decimals(&client, &url, TOKEN).await.unwrap(), 6);
+    }
+
+    #[tokio::test]
+    async fn surfaces_node_errors_and_bad_results() {
+        let url = rpc_stand_in(|method, _| match method {
+            "eth_gasPrice" => Ok(json!("not hex")),
+            "eth_call" => Ok(json!(format!("0x{:064x}", 255))),
+            _ => Err(json!({ "code": -32000, "message": "execution reverted" })),
+        })
+        .await;
+        let client = client().unwrap();
+
+        assert!(gas_price(&client, &url).await.unwrap_err().contains("invalid quantity"));
+        assert!(native_balance(&client, &url, OWNER).await.unwrap_err().contains("execution reverted"));
+        // 255 decimals is not a real token
+        assert!(erc20_decimals(&client, &url, TOKEN).await.is_err());
+    }
+}
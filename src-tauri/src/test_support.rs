@@ -0,0 +1,136 @@
+use serde_json::{json, Value};
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePool, SqlitePoolOptions};
+use std::str::FromStr;
+use std::sync::Arc;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+/// A fresh in-memory database with every migration applied.
+pub(crate) async fn memory_db() -> SqlitePool {
+    let options = SqliteConnectOptions::from_str("sqlite::memory:").expect("valid sqlite url");
+    // One connection, otherwise each connection would get its own empty database
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect_with(options)
+        .await
+        .expect("open in-memory database");
+    crate::database::migrate(&pool).await.expect("run migrations");
+    pool
+}
+
+pub(crate) struct StandInRequest {
+    pub method: String,
+    pub path: String,
+    pub body: String,
+}
+
+pub(crate) struct StandInReply {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl StandInReply {
+    pub fn ok(body: impl Into<String>) -> Self {
+        Self { status: 200, headers: Vec::new(), body: body.into() }
+    }
+
+    pub fn status(status: u16) -> Self {
+        Self { status, headers: Vec::new(), body: String::new() }
+    }
+
+    pub fn header(mut self, name: &str, value: impl Into<String>) -> Self {
+        self.headers.push((name.to_string(), value.into()));
+        self
+    }
+}
+
+/// Serves `handler` over plain HTTP on a random local port and returns the
+/// base URL (`http://127.0.0.1:<port>`). One request per connection.
+pub(crate) async fn http_stand_in<F>(handler: F) -> String
+where
+    F: Fn(&StandInRequest) -> StandInReply + Send + Sync + 'static,
+{
+    let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind stand-in");
+    let base_url = format!("http://{}", listener.local_addr().expect("stand-in address"));
+    let handler = Arc::new(handler);
+
+    tokio::spawn(async move {
+        while let Ok((mut socket, _)) = listener.accept().await {
+            let handler = handler.clone();
+            tokio::spawn(async move {
+                let Some(request) = read_request(&mut socket).await else {
+                    return;
+                };
+                let reply = handler(&request);
+
+                let mut head = format!("HTTP/1.1 {} Stand-in\r\n", reply.status);
+                for (name, value) in &reply.headers {
+                    head.push_str(&format!("{}: {}\r\n", name, value));
+                }
+                head.push_str(&format!("Content-Length: {}\r\nConnection: close\r\n\r\n", reply.body.len()));
+                let _ = socket.write_all(head.as_bytes()).await;
+                let _ = socket.write_all(reply.body.as_bytes()).await;
+                let _ = socket.shutdown().await;
+            });
+        }
+    });
+
+    base_url
+}
+
+async fn read_request(socket: &mut tokio::net::TcpStream) -> Option<StandInRequest> {
+    let mut buffer = Vec::new();
+    let mut chunk = [0u8; 4096];
+    let header_end = loop {
+        let read = socket.read(&mut chunk).await.ok()?;
+        if read == 0 {
+            return None;
+        }
+        buffer.extend_from_slice(&chunk[..read]);
+        if let Some(end) = buffer.windows(4).position(|w| w == b"\r\n\r\n") {
+            break end + 4;
+        }
+    };
+
+    let head = String::from_utf8_lossy(&buffer[..header_end]).to_string();
+    let content_length = head
+        .lines()
+        .filter_map(|line| line.split_once(':'))
+        .find(|(name, _)| name.trim().eq_ignore_ascii_case("content-length"))
+        .and_then(|(_, value)| value.trim().parse::<usize>().ok())
+        .unwrap_or(0);
+    while buffer.len() < header_end + content_length {
+        let read = socket.read(&mut chunk).await.ok()?;
+        if read == 0 {
+            break;
+        }
+        buffer.extend_from_slice(&chunk[..read]);
+    }
+
+    let mut request_line = head.lines().next()?.split_whitespace();
+    Some(StandInRequest {
+        method: request_line.next()?.to_string(),
+        path: request_line.next()?.to_string(),
+        body: String::from_utf8_lossy(&buffer[header_end..]).to_string(),
+    })
+}
+
+/// A JSON-RPC node stand-in. `handler` gets the method and params and returns
+/// the `result`, or `Err` with the JSON-RPC `error` object.
+pub(crate) async fn rpc_stand_in<F>(handler: F) -> String
+where
+    F: Fn(&str, &Value) -> Result<Value, Value> + Send + Sync + 'static,
+{
+    http_stand_in(move |request| {
+        let call: Value = serde_json::from_str(&request.body).unwrap_or(Value::Null);
+        let method = call.get("method").and_then(|m| m.as_str()).unwrap_or_default();
+        let params = call.get("params").cloned().unwrap_or(Value::Null);
+        let reply = match handler(method, &params) {
+            Ok(result) => json!({ "jsonrpc": "2.0", "id": call["id"], "result": result }),
+            Err(error) => json!({ "jsonrpc": "2.0", "id": call["id"], "error": error }),
+        };
+        StandInReply::ok(reply.to_string()).header("Content-Type", "application/json")
+    })
+    .await
+}
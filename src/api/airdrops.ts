@@ -9,14 +9,24 @@ export interface Airdrop {
   notes?: string;
   active: boolean;
   wallet_address?: string; // Manual wallet address input
+  expected_value?: number; // Estimated payout in the base currency
+  confidence?: number; // 0..1
+  snapshot_date?: string; // YYYY-MM-DD
+  template_version?: number; // Type version last synced into this airdrop
+  browser_profile_id?: number;
   created_at: string;
   updated_at: string;
 }
 
+export type ResetPolicy = "daily" | "weekly" | "once";
+
 export interface AirdropType {
   id: number;
   name: string;
   default_tasks: any;
+  reset_policy: ResetPolicy;
+  recommended_chains: string[];
+  version: number; // Bumped whenever default_tasks changes
   created_at: string;
   updated_at: string;
 }
@@ -56,6 +66,9 @@ export async function updateAirdrop(data: {
   notes?: string;
   active?: boolean;
   wallet_address?: string;
+  expected_value?: number;
+  confidence?: number;
+  snapshot_date?: string; // Empty string clears it
 }): Promise<void> {
   return invoke("update_airdrop", { req: data });
 }
@@ -71,10 +84,56 @@ export async function listAirdropTypes(): Promise<AirdropType[]> {
 export async function createAirdropType(data: {
   name: string;
   default_tasks: any;
+  reset_policy?: ResetPolicy;
+  recommended_chains?: string[];
 }): Promise<number> {
   return invoke("create_airdrop_type", { req: data });
 }
 
+export async function updateAirdropType(data: {
+  id: number;
+  name?: string;
+  default_tasks?: any;
+  reset_policy?: ResetPolicy;
+  recommended_chains?: string[];
+}): Promise<void> {
+  return invoke("update_airdrop_type", { req: data });
+}
+
+export type TemplateConflictPolicy = "rename" | "merge" | "skip";
+
+export interface TemplateImportResult {
+  name: string;
+  action: "created" | "renamed" | "merged" | "unchanged" | "skipped";
+  airdrop_type_id?: number;
+  imported_as?: string;
+  added_tasks: string[];
+}
+
+export interface TemplateSyncResult {
+  airdrop_id: number;
+  airdrop_name: string;
+  from_version?: number;
+  to_version: number;
+  added_tasks: string[];
+}
+
+export async function exportTemplatePack(typeIds?: number[]): Promise<string> {
+  return invoke("export_template_pack", { typeIds });
+}
+
+export async function importTemplatePack(data: {
+  content: string;
+  on_conflict: TemplateConflictPolicy;
+  dry_run: boolean;
+}): Promise<TemplateImportResult[]> {
+  return invoke("import_template_pack", { req: data });
+}
+
+export async function syncTemplateTasks(dryRun: boolean, airdropId?: number): Promise<TemplateSyncResult[]> {
+  return invoke("sync_template_tasks", { airdropId, dryRun });
+}
+
 export async function listAirdropDailyTasks(airdropId: number): Promise<AirdropDailyTask[]> {
   return invoke("list_airdrop_daily_tasks", { airdropId });
 }
@@ -91,11 +150,138 @@ export async function deleteAirdropDailyTask(id: number): Promise<void> {
   return invoke("delete_airdrop_daily_task", { id });
 }
 
-export async function markTaskDoneToday(taskId: number, airdropId: number): Promise<void> {
-  return invoke("mark_task_done_today", { taskId, airdropId });
+export async function markTaskDoneToday(
+  taskId: number,
+  airdropId: number,
+  walletId?: number
+): Promise<number> {
+  return invoke("mark_task_done_today", { taskId, airdropId, walletId });
+}
+
+export interface TaskCompletion {
+  id: number;
+  task_id: number;
+  airdrop_id: number;
+  wallet_id?: number;
+  completed_on: string;
+  completed_at: string;
+}
+
+export async function listTaskCompletions(airdropId: number): Promise<TaskCompletion[]> {
+  return invoke("list_task_completions", { airdropId });
+}
+
+export async function deleteTaskCompletion(id: number): Promise<void> {
+  return invoke("delete_task_completion", { id });
+}
+
+export type CompletionProofKind = "tx_hash" | "url" | "screenshot";
+
+export interface CompletionProof {
+  id: number;
+  completion_id: number;
+  kind: CompletionProofKind;
+  value: string;
+  chain?: string;
+  note?: string;
+  created_at: string;
+  file_path?: string; // Absolute screenshot path, for thumbnails
+}
+
+export async function attachCompletionProof(data: {
+  completion_id: number;
+  kind: CompletionProofKind;
+  value: string; // Hash, URL, or path of the screenshot to copy in
+  chain?: string;
+  note?: string;
+}): Promise<number> {
+  return invoke("attach_completion_proof", { req: data });
+}
+
+export async function listCompletionProofs(completionId: number): Promise<CompletionProof[]> {
+  return invoke("list_completion_proofs", { completionId });
+}
+
+export async function deleteCompletionProof(id: number): Promise<void> {
+  return invoke("delete_completion_proof", { id });
 }
 
 export async function reorderAirdrops(items: { id: number; position: number }[]): Promise<void> {
   return invoke("reorder_airdrops", { req: { items } });
 }
 
+
+export type ImportRowStatus = "created" | "would_create" | "duplicate" | "error";
+
+export interface ImportRowResult {
+  row: number;
+  name?: string;
+  url?: string;
+  status: ImportRowStatus;
+  message?: string;
+  airdrop_id?: number;
+  tasks: string[];
+}
+
+export interface ImportAirdropsReport {
+  dry_run: boolean;
+  total_rows: number;
+  created: number;
+  duplicates: number;
+  errors: number;
+  rows: ImportRowResult[];
+}
+
+export async function importAirdrops(data: {
+  format: "csv" | "json";
+  content: string;
+  column_mapping?: Record<string, string>;
+  task_separator?: string;
+  dry_run: boolean;
+}): Promise<ImportAirdropsReport> {
+  return invoke("import_airdrops", { req: data });
+}
+
+export async function exportAirdropsCsv(): Promise<string> {
+  return invoke("export_airdrops_csv");
+}
+
+export interface AirdropUrlHealth {
+  airdrop_id: number;
+  url: string;
+  status_code?: number;
+  final_url?: string;
+  page_title?: string;
+  error?: string;
+  is_dead: boolean;
+  is_redirected: boolean;
+  phishing_warning: boolean;
+  checked_at: string;
+}
+
+export async function checkAirdropUrls(data: {
+  airdrop_ids?: number[];
+  timeout_secs?: number;
+} = {}): Promise<AirdropUrlHealth[]> {
+  return invoke("check_airdrop_urls", { req: data });
+}
+
+/** Runs in the background; listen for `airdrop-url-check-progress` and `airdrop-url-check-done`. */
+export async function startAirdropUrlCheck(data: {
+  airdrop_ids?: number[];
+  timeout_secs?: number;
+} = {}): Promise<void> {
+  return invoke("start_airdrop_url_check", { req: data });
+}
+
+export async function listAirdropUrlHealth(): Promise<AirdropUrlHealth[]> {
+  return invoke("list_airdrop_url_health");
+}
+
+export async function listTrustedRedirectDomains(): Promise<string[]> {
+  return invoke("list_trusted_redirect_domains");
+}
+
+export async function trustRedirectDomain(domain: string): Promise<void> {
+  return invoke("trust_redirect_domain", { domain });
+}
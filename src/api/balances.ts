@@ -0,0 +1,46 @@
+import { invoke } from "@tauri-apps/api/core";
+
+export interface BalanceSnapshot {
+  id: number;
+  wallet_id: number;
+  chain_id: number;
+  token_address?: string; // Absent for the native asset
+  symbol: string;
+  raw_balance: string;
+  balance: number;
+  snapshot_date: string;
+  recorded_at: string;
+}
+
+export interface BalanceSnapshotRun {
+  snapshot_date: string;
+  snapshots: BalanceSnapshot[];
+  errors: { wallet_id: number; chain: string; token?: string; error: string }[];
+}
+
+export interface FundingNeed {
+  wallet_id: number;
+  wallet_label: string;
+  chain_id: number;
+  chain_name: string;
+  symbol: string;
+  balance: number;
+  min_native_balance: number;
+  snapshot_date: string;
+}
+
+export async function snapshotWalletBalances(walletId?: number): Promise<BalanceSnapshotRun> {
+  return invoke("snapshot_wallet_balances", { walletId });
+}
+
+export async function listBalanceSnapshots(filter?: {
+  wallet_id?: number;
+  chain_id?: number;
+  snapshot_date?: string;
+}): Promise<BalanceSnapshot[]> {
+  return invoke("list_balance_snapshots", { filter });
+}
+
+export async function listFundingNeeds(): Promise<FundingNeed[]> {
+  return invoke("list_funding_needs");
+}
@@ -0,0 +1,60 @@
+import { invoke } from "@tauri-apps/api/core";
+
+export type BrowserKind = "chromium" | "firefox" | "custom";
+
+export interface BrowserProfile {
+  id: number;
+  name: string;
+  kind: BrowserKind;
+  executable: string;
+  profile: string; // Chromium profile directory or Firefox profile name
+  args_template: string[]; // {url} and {profile} are substituted per argument
+  created_at: string;
+  updated_at: string;
+}
+
+export interface OpenAirdropResult {
+  browser_profile_id?: number;
+  executable?: string;
+  args: string[];
+}
+
+export async function listBrowserProfiles(): Promise<BrowserProfile[]> {
+  return invoke("list_browser_profiles");
+}
+
+export async function createBrowserProfile(data: {
+  name: string;
+  kind: BrowserKind;
+  executable: string;
+  profile: string;
+  args_template?: string[]; // Required for custom browsers
+}): Promise<number> {
+  return invoke("create_browser_profile", { req: data });
+}
+
+export async function updateBrowserProfile(data: {
+  id: number;
+  name?: string;
+  executable?: string;
+  profile?: string;
+  args_template?: string[];
+}): Promise<void> {
+  return invoke("update_browser_profile", { req: data });
+}
+
+export async function deleteBrowserProfile(id: number): Promise<void> {
+  return invoke("delete_browser_profile", { id });
+}
+
+export async function setAirdropBrowserProfile(airdropId: number, browserProfileId?: number): Promise<void> {
+  return invoke("set_airdrop_browser_profile", { airdropId, browserProfileId });
+}
+
+export async function setWalletBrowserProfile(walletId: number, browserProfileId?: number): Promise<void> {
+  return invoke("set_wallet_browser_profile", { walletId, browserProfileId });
+}
+
+export async function openAirdrop(airdropId: number, useDefaultBrowser?: boolean): Promise<OpenAirdropResult> {
+  return invoke("open_airdrop", { airdropId, useDefaultBrowser });
+}
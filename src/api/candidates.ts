@@ -0,0 +1,70 @@
+import { invoke } from "@tauri-apps/api/core";
+
+export interface CandidateSource {
+  id: number;
+  name: string;
+  location: string; // HTTP(S) URL, file:// URL or local path
+  last_fetched_at?: string;
+  last_error?: string;
+  created_at: string;
+  updated_at: string;
+}
+
+export type CandidateStatus = "new" | "accepted" | "dismissed";
+
+export interface AirdropCandidate {
+  id: number;
+  source_id?: number;
+  name: string;
+  url: string;
+  normalized_url: string;
+  chain?: string;
+  description?: string;
+  tasks: string[];
+  status: CandidateStatus;
+  airdrop_id?: number;
+  created_at: string;
+  updated_at: string;
+}
+
+// Also the payload of the "airdrop-candidates-new" event
+export interface CandidateFetchResult {
+  source_id: number;
+  new_candidates: number;
+  duplicates: number;
+  error?: string;
+}
+
+export async function listCandidateSources(): Promise<CandidateSource[]> {
+  return invoke("list_candidate_sources");
+}
+
+export async function createCandidateSource(data: { name: string; location: string }): Promise<number> {
+  return invoke("create_candidate_source", { req: data });
+}
+
+export async function deleteCandidateSource(id: number): Promise<void> {
+  return invoke("delete_candidate_source", { id });
+}
+
+export async function refreshCandidateSources(sourceId?: number): Promise<CandidateFetchResult[]> {
+  return invoke("refresh_candidate_sources", { sourceId });
+}
+
+export async function listAirdropCandidates(status?: CandidateStatus): Promise<AirdropCandidate[]> {
+  return invoke("list_airdrop_candidates", { status });
+}
+
+export async function acceptAirdropCandidate(data: {
+  id: number;
+  airdrop_type_id?: number;
+  name?: string;
+  chain?: string;
+  wallet_address?: string;
+}): Promise<number> {
+  return invoke("accept_airdrop_candidate", { req: data });
+}
+
+export async function dismissAirdropCandidate(id: number): Promise<void> {
+  return invoke("dismiss_airdrop_candidate", { id });
+}
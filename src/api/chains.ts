@@ -0,0 +1,110 @@
+import { invoke } from "@tauri-apps/api/core";
+
+export interface Chain {
+  id: number;
+  name: string;
+  chain_id?: number;
+  rpc_url: string;
+  native_symbol: string;
+  gas_threshold_gwei?: number;
+  gas_alerts_enabled: boolean;
+  min_native_balance?: number; // Wallets below this need topping up
+  created_at: string;
+  updated_at: string;
+}
+
+export interface ChainToken {
+  id: number;
+  chain_id: number;
+  symbol: string;
+  address: string;
+  decimals: number;
+  created_at: string;
+}
+
+export interface GasPrice {
+  id: number;
+  chain_id: number;
+  gas_price_wei: string;
+  base_fee_wei?: string;
+  priority_fee_wei?: string;
+  gas_price_gwei: number;
+  recorded_at: string;
+}
+
+export interface GasPollResult {
+  chain_id: number;
+  chain_name: string;
+  sample?: GasPrice;
+  error?: string;
+}
+
+// Payload of the "gas-price-alert" event
+export interface GasPriceAlert {
+  chain_id: number;
+  chain_name: string;
+  gas_price_gwei: number;
+  threshold_gwei: number;
+}
+
+export async function listChains(): Promise<Chain[]> {
+  return invoke("list_chains");
+}
+
+export async function createChain(data: {
+  name: string;
+  chain_id?: number;
+  rpc_url: string;
+  native_symbol?: string;
+  gas_threshold_gwei?: number;
+  gas_alerts_enabled?: boolean;
+  min_native_balance?: number;
+}): Promise<number> {
+  return invoke("create_chain", { req: data });
+}
+
+export async function updateChain(data: {
+  id: number;
+  name?: string;
+  chain_id?: number;
+  rpc_url?: string;
+  native_symbol?: string;
+  gas_threshold_gwei?: number; // Negative clears the threshold
+  gas_alerts_enabled?: boolean;
+  min_native_balance?: number; // Negative clears the minimum
+}): Promise<void> {
+  return invoke("update_chain", { req: data });
+}
+
+export async function deleteChain(id: number): Promise<void> {
+  return invoke("delete_chain", { id });
+}
+
+export async function listChainTokens(chainId?: number): Promise<ChainToken[]> {
+  return invoke("list_chain_tokens", { chainId });
+}
+
+export async function createChainToken(data: {
+  chain_id: number;
+  symbol: string;
+  address: string;
+  decimals?: number; // Read from the contract when omitted
+}): Promise<number> {
+  return invoke("create_chain_token", { req: data });
+}
+
+export async function deleteChainToken(id: number): Promise<void> {
+  return invoke("delete_chain_token", { id });
+}
+
+export async function pollGasPrices(chainId?: number): Promise<GasPollResult[]> {
+  return invoke("poll_gas_prices", { chainId });
+}
+
+export async function listGasPriceHistory(chainId: number, hours?: number): Promise<GasPrice[]> {
+  return invoke("list_gas_price_history", { chainId, hours });
+}
+
+export async function latestGasPrices(): Promise<GasPrice[]> {
+  return invoke("latest_gas_prices");
+}
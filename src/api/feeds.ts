@@ -0,0 +1,85 @@
+import { invoke } from "@tauri-apps/api/core";
+
+export interface AirdropFeed {
+  id: number;
+  airdrop_id: number;
+  url: string;
+  title?: string;
+  last_polled_at?: string;
+  last_error?: string;
+  created_at: string;
+  updated_at: string;
+}
+
+export interface FeedItem {
+  id: number;
+  feed_id: number;
+  airdrop_id: number;
+  guid: string;
+  title: string;
+  link?: string;
+  summary?: string;
+  published_at?: string;
+  read: boolean;
+  created_at: string;
+}
+
+export interface FeedSettings {
+  poll_interval_minutes: number;
+}
+
+export interface FeedPollResult {
+  feed_id: number;
+  airdrop_id: number;
+  new_items: number;
+  error?: string;
+}
+
+export interface FeedUnreadCount {
+  airdrop_id: number;
+  unread: number;
+}
+
+export async function listAirdropFeeds(airdropId?: number): Promise<AirdropFeed[]> {
+  return invoke("list_airdrop_feeds", { airdropId });
+}
+
+export async function createAirdropFeed(data: {
+  airdrop_id: number;
+  url: string;
+  title?: string;
+}): Promise<number> {
+  return invoke("create_airdrop_feed", { req: data });
+}
+
+export async function deleteAirdropFeed(id: number): Promise<void> {
+  return invoke("delete_airdrop_feed", { id });
+}
+
+export async function pollAirdropFeeds(feedId?: number): Promise<FeedPollResult[]> {
+  return invoke("poll_airdrop_feeds", { feedId });
+}
+
+export async function listFeedItems(airdropId?: number, unreadOnly?: boolean): Promise<FeedItem[]> {
+  return invoke("list_feed_items", { airdropId, unreadOnly });
+}
+
+export async function feedUnreadCounts(): Promise<FeedUnreadCount[]> {
+  return invoke("feed_unread_counts");
+}
+
+export async function markFeedItemsRead(data: {
+  item_ids?: number[];
+  airdrop_id?: number;
+  read?: boolean;
+}): Promise<void> {
+  return invoke("mark_feed_items_read", { req: data });
+}
+
+export async function getFeedSettings(): Promise<FeedSettings> {
+  return invoke("get_feed_settings");
+}
+
+export async function updateFeedSettings(settings: FeedSettings): Promise<void> {
+  return invoke("update_feed_settings", { settings });
+}
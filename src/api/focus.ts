@@ -0,0 +1,87 @@
+import { invoke } from "@tauri-apps/api/core";
+
+export interface FocusSettings {
+  focus_minutes: number;
+  short_break_minutes: number;
+  long_break_minutes: number;
+  long_break_every: number; // Long break after every Nth completed focus interval
+  auto_start_focus: boolean; // Otherwise the next focus interval starts paused
+}
+
+export type FocusPhase = "focus" | "short_break" | "long_break";
+
+// Payload of "focus-tick", "focus-phase-changed" and "focus-stopped"
+export interface FocusState {
+  phase: FocusPhase;
+  phase_seconds: number;
+  remaining_seconds: number;
+  paused: boolean;
+  completed_cycles: number;
+  task_id?: number;
+  airdrop_id?: number;
+  session_id?: number;
+}
+
+export interface FocusInterruption {
+  id: number;
+  session_id: number;
+  note: string;
+  noted_at: string;
+}
+
+export interface FocusSession {
+  id: number;
+  task_id?: number;
+  airdrop_id?: number;
+  planned_seconds: number;
+  focused_seconds: number;
+  completed: boolean;
+  started_at: string;
+  ended_at?: string;
+  interruptions: FocusInterruption[];
+}
+
+export async function getFocusSettings(): Promise<FocusSettings> {
+  return invoke("get_focus_settings");
+}
+
+export async function updateFocusSettings(settings: FocusSettings): Promise<void> {
+  return invoke("update_focus_settings", { settings });
+}
+
+// Call on mount to pick up a timer that kept running across a reload
+export async function getFocusState(): Promise<FocusState | null> {
+  return invoke("get_focus_state");
+}
+
+export async function startFocus(data: { task_id?: number; airdrop_id?: number } = {}): Promise<FocusState> {
+  return invoke("start_focus", { req: data });
+}
+
+export async function pauseFocus(): Promise<FocusState> {
+  return invoke("pause_focus");
+}
+
+export async function resumeFocus(): Promise<FocusState> {
+  return invoke("resume_focus");
+}
+
+export async function skipFocusPhase(): Promise<FocusState> {
+  return invoke("skip_focus_phase");
+}
+
+export async function stopFocus(): Promise<void> {
+  return invoke("stop_focus");
+}
+
+export async function addFocusInterruption(note: string): Promise<number> {
+  return invoke("add_focus_interruption", { note });
+}
+
+export async function listFocusSessions(filter?: {
+  task_id?: number;
+  airdrop_id?: number;
+  limit?: number;
+}): Promise<FocusSession[]> {
+  return invoke("list_focus_sessions", { filter });
+}
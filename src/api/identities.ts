@@ -0,0 +1,119 @@
+import { invoke } from "@tauri-apps/api/core";
+
+export interface SocialIdentity {
+  id: number;
+  platform: string; // "x", "discord", "telegram", "email", ...
+  handle: string;
+  notes?: string;
+  created_at: string;
+  updated_at: string;
+}
+
+export interface IdentityUsage {
+  link_id: number;
+  identity_id: number;
+  platform: string;
+  handle: string;
+  airdrop_id: number;
+  airdrop_name: string;
+  wallet_id?: number;
+  wallet_label?: string;
+  wallet_address?: string;
+}
+
+export interface IdentityReuseWarning {
+  identity_id: number;
+  platform: string;
+  handle: string;
+  wallets: { id: number; label: string; address: string }[];
+  airdrop_ids: number[];
+  message: string;
+}
+
+export interface AirdropReferral {
+  id: number;
+  airdrop_id: number;
+  wallet_id?: number;
+  code?: string;
+  link?: string;
+  notes?: string;
+  created_at: string;
+  updated_at: string;
+}
+
+export async function listSocialIdentities(): Promise<SocialIdentity[]> {
+  return invoke("list_social_identities");
+}
+
+export async function createSocialIdentity(data: {
+  platform: string;
+  handle: string;
+  notes?: string;
+}): Promise<number> {
+  return invoke("create_social_identity", { req: data });
+}
+
+export async function updateSocialIdentity(data: {
+  id: number;
+  platform?: string;
+  handle?: string;
+  notes?: string;
+}): Promise<void> {
+  return invoke("update_social_identity", { req: data });
+}
+
+export async function deleteSocialIdentity(id: number): Promise<void> {
+  return invoke("delete_social_identity", { id });
+}
+
+export async function linkAirdropIdentity(data: {
+  airdrop_id: number;
+  identity_id: number;
+  wallet_id?: number;
+}): Promise<IdentityReuseWarning[]> {
+  return invoke("link_airdrop_identity", { req: data });
+}
+
+export async function unlinkAirdropIdentity(id: number): Promise<void> {
+  return invoke("unlink_airdrop_identity", { id });
+}
+
+export async function listIdentityUsage(filter?: {
+  identity_id?: number;
+  wallet_id?: number;
+  airdrop_id?: number;
+}): Promise<IdentityUsage[]> {
+  return invoke("list_identity_usage", { filter });
+}
+
+export async function listIdentityReuseWarnings(): Promise<IdentityReuseWarning[]> {
+  return invoke("list_identity_reuse_warnings");
+}
+
+export async function listAirdropReferrals(airdropId?: number): Promise<AirdropReferral[]> {
+  return invoke("list_airdrop_referrals", { airdropId });
+}
+
+export async function createAirdropReferral(data: {
+  airdrop_id: number;
+  wallet_id?: number;
+  code?: string;
+  link?: string;
+  notes?: string;
+}): Promise<number> {
+  return invoke("create_airdrop_referral", { req: data });
+}
+
+export async function updateAirdropReferral(data: {
+  id: number;
+  wallet_id?: number;
+  code?: string;
+  link?: string;
+  notes?: string;
+}): Promise<void> {
+  return invoke("update_airdrop_referral", { req: data });
+}
+
+export async function deleteAirdropReferral(id: number): Promise<void> {
+  return invoke("delete_airdrop_referral", { id });
+}
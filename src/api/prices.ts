@@ -0,0 +1,101 @@
+import { invoke } from "@tauri-apps/api/core";
+
+export interface Price {
+  asset: string;
+  quote: string;
+  date: string; // YYYY-MM-DD
+  price: number; // 1 asset in quote currency
+  source?: string;
+  updated_at: string;
+}
+
+export interface CurrencySettings {
+  base_currency: string;
+  price_endpoint?: string; // URL template with {asset}, {quote} and {date}
+  price_json_path?: string; // Dotted path to the price in the response
+}
+
+export interface PriceImportReport {
+  imported: number;
+  errors: string[];
+}
+
+export interface PriceFetchResult {
+  asset: string;
+  price?: number;
+  error?: string;
+}
+
+export interface AirdropValuation {
+  airdrop_id: number;
+  airdrop_name: string;
+  expected_value?: number;
+  cost_to_date: number;
+  claimed_value: number;
+  realized_net: number;
+}
+
+export interface ProjectValuation {
+  project_id: number;
+  project_name: string;
+  budget?: number;
+  budget_currency?: string;
+  budget_in_base?: number;
+}
+
+export interface ValuationSummary {
+  base_currency: string;
+  airdrops: AirdropValuation[];
+  projects: ProjectValuation[];
+  warnings: string[];
+}
+
+export async function getCurrencySettings(): Promise<CurrencySettings> {
+  return invoke("get_currency_settings");
+}
+
+export async function updateCurrencySettings(settings: CurrencySettings): Promise<void> {
+  return invoke("update_currency_settings", { settings });
+}
+
+export async function listPrices(filter?: { asset?: string; quote?: string }): Promise<Price[]> {
+  return invoke("list_prices", { filter });
+}
+
+export async function upsertPrice(data: {
+  asset: string;
+  quote: string;
+  date: string;
+  price: number;
+}): Promise<void> {
+  return invoke("upsert_price", { req: data });
+}
+
+export async function deletePrice(asset: string, quote: string, date: string): Promise<void> {
+  return invoke("delete_price", { asset, quote, date });
+}
+
+export async function importPricesCsv(content: string): Promise<PriceImportReport> {
+  return invoke("import_prices_csv", { content });
+}
+
+export async function fetchPrices(data: {
+  assets: string[];
+  quote?: string;
+  date?: string;
+}): Promise<PriceFetchResult[]> {
+  return invoke("fetch_prices", { req: data });
+}
+
+export async function convertAmount(
+  amount: number,
+  from: string,
+  to?: string,
+  date?: string
+): Promise<number> {
+  return invoke("convert_amount", { amount, from, to, date });
+}
+
+export async function valuationSummary(): Promise<ValuationSummary> {
+  return invoke("valuation_summary");
+}
@@ -1,10 +1,14 @@
 import { invoke } from "@tauri-apps/api/core";
 
+export type ProjectStatus = "active" | "paused" | "completed" | "archived";
+
 export interface Project {
   id: number;
   name: string;
   description?: string;
-  status: "active" | "paused" | "completed" | "archived";
+  status: ProjectStatus;
+  budget?: number;
+  budget_currency?: string;
   created_at: string;
   updated_at: string;
 }
@@ -15,10 +19,69 @@ export interface ProjectTask {
   title: string;
   done: boolean;
   order: number;
+  parent_task_id?: number;
+  due_date?: string; // YYYY-MM-DD
+  start_date?: string; // YYYY-MM-DD
+  priority?: number; // 0 (P0) to 3 (P3)
+  estimated_minutes?: number;
+  completed_at?: string;
+  milestone_id?: number;
+  recurrence?: RecurrenceRule;
+  next_occurrence_id?: number; // Generated when this occurrence was completed
+  column_id?: number; // `done` mirrors the column's is_done
+  column_order: number; // position on the board; `order` is the sibling order
+  blocked: boolean; // Some blocked-by task is still open
   created_at: string;
   updated_at: string;
 }
 
+export interface ProjectColumn {
+  id: number;
+  project_id: number;
+  name: string;
+  position: number;
+  wip_limit?: number;
+  is_done: boolean;
+  created_at: string;
+  updated_at: string;
+}
+
+export interface BoardColumn extends ProjectColumn {
+  tasks: ProjectTask[];
+}
+
+export interface ProjectBoard {
+  columns: BoardColumn[];
+  unfiled: ProjectTask[]; // No column had room, e.g. Todo was at its WIP limit
+}
+
+export interface ScheduledProjectTask extends ProjectTask {
+  project_name: string;
+}
+
+export type RecurrenceRule =
+  | { kind: "daily" }
+  | { kind: "weekly"; weekdays: number[] } // 1 = Monday ... 7 = Sunday
+  | { kind: "monthly"; day: number } // Clamped to the month's last day
+  | { kind: "after_completion"; days: number };
+
+export interface ProjectTaskNode extends ProjectTask {
+  children: ProjectTaskNode[];
+  total_leaves: number;
+  done_leaves: number;
+  completion: number; // 0-100
+  rolled_up_done: boolean;
+}
+
+export interface ProjectStatusChange {
+  id: number;
+  project_id: number;
+  from_status?: ProjectStatus; // Unset for the initial status
+  to_status: ProjectStatus;
+  reason?: string;
+  changed_at: string;
+}
+
 export async function listProjects(): Promise<Project[]> {
   return invoke("list_projects");
 }
@@ -27,6 +90,8 @@ export async function createProject(data: {
   name: string;
   description?: string;
   status?: string;
+  budget?: number;
+  budget_currency?: string;
 }): Promise<number> {
   return invoke("create_project", { req: data });
 }
@@ -35,7 +100,10 @@ export async function updateProject(data: {
   id: number;
   name?: string;
   description?: string;
-  status?: string;
+  status?: ProjectStatus; // Archived projects need unarchiveProject
+  status_reason?: string;
+  budget?: number; // Negative clears it
+  budget_currency?: string; // Blank clears it
 }): Promise<void> {
   return invoke("update_project", { req: data });
 }
@@ -44,6 +112,14 @@ export async function deleteProject(id: number): Promise<void> {
   return invoke("delete_project", { id });
 }
 
+export async function unarchiveProject(id: number, reason?: string): Promise<void> {
+  return invoke("unarchive_project", { id, reason });
+}
+
+export async function listProjectStatusHistory(projectId: number): Promise<ProjectStatusChange[]> {
+  return invoke("list_project_status_history", { projectId });
+}
+
 export async function listProjectTasks(projectId: number): Promise<ProjectTask[]> {
   return invoke("list_project_tasks", { project_id: projectId, projectId });
 }
@@ -52,6 +128,11 @@ export async function createProjectTask(data: {
   project_id: number;
   title: string;
   order: number;
+  parent_task_id?: number;
+  due_date?: string;
+  start_date?: string;
+  priority?: number;
+  estimated_minutes?: number;
 }): Promise<number> {
   return invoke("create_project_task", { req: data });
 }
@@ -61,12 +142,233 @@ export async function updateProjectTask(data: {
   title?: string;
   done?: boolean;
   order?: number;
+  due_date?: string; // Empty string clears it
+  start_date?: string; // Empty string clears it
+  priority?: number; // Negative clears it
+  estimated_minutes?: number; // Negative clears it
 }): Promise<void> {
   return invoke("update_project_task", { req: data });
 }
 
-export async function deleteProjectTask(id: number): Promise<void> {
-  return invoke("delete_project_task", { id });
+export async function listProjectTaskTree(projectId: number): Promise<ProjectTaskNode[]> {
+  return invoke("list_project_task_tree", { projectId });
+}
+
+// Omit parentTaskId to move the task to the top level
+export async function moveProjectTask(
+  id: number,
+  parentTaskId?: number,
+  order?: number
+): Promise<void> {
+  return invoke("move_project_task", { id, parentTaskId, order });
+}
+
+// Without cascade, subtasks move up to the deleted task's parent
+export async function deleteProjectTask(id: number, cascade?: boolean): Promise<void> {
+  return invoke("delete_project_task", { id, cascade });
+}
+
+
+
+export async function listOverdueProjectTasks(): Promise<ScheduledProjectTask[]> {
+  return invoke("list_overdue_project_tasks");
+}
+
+export async function listProjectTasksDueThisWeek(): Promise<ScheduledProjectTask[]> {
+  return invoke("list_project_tasks_due_this_week");
+}
+
+export async function listProjectTasksByPriority(priority?: number): Promise<ScheduledProjectTask[]> {
+  return invoke("list_project_tasks_by_priority", { priority });
+}
+
+export interface TaskDependencies {
+  blocked_by: ScheduledProjectTask[];
+  blocking: ScheduledProjectTask[];
+}
+
+// Emitted as "project-tasks-unblocked" when completing a task frees others
+export type UnblockedTasksEvent = ScheduledProjectTask[];
+
+export async function listTaskDependencies(taskId: number): Promise<TaskDependencies> {
+  return invoke("list_task_dependencies", { taskId });
+}
+
+export async function addTaskDependency(taskId: number, blockedByTaskId: number): Promise<void> {
+  return invoke("add_task_dependency", { taskId, blockedByTaskId });
+}
+
+export async function removeTaskDependency(taskId: number, blockedByTaskId: number): Promise<void> {
+  return invoke("remove_task_dependency", { taskId, blockedByTaskId });
+}
+
+export async function listReadyProjectTasks(): Promise<ScheduledProjectTask[]> {
+  return invoke("list_ready_project_tasks");
+}
+
+export async function listProjectColumns(projectId: number): Promise<ProjectColumn[]> {
+  return invoke("list_project_columns", { projectId });
+}
+
+export async function getProjectBoard(projectId: number): Promise<ProjectBoard> {
+  return invoke("get_project_board", { projectId });
+}
+
+export async function createProjectColumn(data: {
+  project_id: number;
+  name: string;
+  position?: number;
+  wip_limit?: number;
+  is_done?: boolean;
+}): Promise<number> {
+  return invoke("create_project_column", { req: data });
+}
+
+export async function updateProjectColumn(data: {
+  id: number;
+  name?: string;
+  wip_limit?: number; // Zero or negative clears it
+  is_done?: boolean;
+}): Promise<void> {
+  return invoke("update_project_column", { req: data });
+}
+
+// Required when the column still has tasks
+export async function deleteProjectColumn(id: number, moveToColumnId?: number): Promise<void> {
+  return invoke("delete_project_column", { id, moveToColumnId });
+}
+
+export async function reorderProjectColumns(projectId: number, columnIds: number[]): Promise<void> {
+  return invoke("reorder_project_columns", { projectId, columnIds });
+}
+
+export async function moveTaskToColumn(taskId: number, columnId: number, position?: number): Promise<void> {
+  return invoke("move_task_to_column", { taskId, columnId, position });
+}
+
+export interface ProjectMilestone {
+  id: number;
+  project_id: number;
+  name: string;
+  description?: string;
+  target_date?: string; // YYYY-MM-DD
+  created_at: string;
+  updated_at: string;
+}
+
+// Counted over leaf tasks
+export interface ProgressTotals {
+  total_tasks: number;
+  done_tasks: number;
+  task_progress: number; // 0-100
+  estimated_minutes: number;
+  remaining_minutes: number;
+  estimate_progress?: number; // 0-100, unset when nothing is estimated
+  unestimated_tasks: number;
+}
+
+export interface MilestoneProgress extends ProjectMilestone, ProgressTotals {
+  days_left?: number;
+  available_minutes?: number;
+  at_risk: boolean;
+}
+
+export interface ProjectProgress extends ProgressTotals {
+  project_id: number;
+  project_name: string;
+  milestones: MilestoneProgress[];
+}
+
+export interface PlanningSettings {
+  daily_capacity_minutes: number;
+}
+
+export async function getPlanningSettings(): Promise<PlanningSettings> {
+  return invoke("get_planning_settings");
+}
+
+export async function updatePlanningSettings(settings: PlanningSettings): Promise<void> {
+  return invoke("update_planning_settings", { settings });
+}
+
+export async function listProjectMilestones(projectId: number): Promise<MilestoneProgress[]> {
+  return invoke("list_project_milestones", { projectId });
+}
+
+export async function createProjectMilestone(data: {
+  project_id: number;
+  name: string;
+  description?: string;
+  target_date?: string;
+}): Promise<number> {
+  return invoke("create_project_milestone", { req: data });
+}
+
+export async function updateProjectMilestone(data: {
+  id: number;
+  name?: string;
+  description?: string;
+  target_date?: string; // Empty string clears it
+}): Promise<void> {
+  return invoke("update_project_milestone", { req: data });
+}
+
+export async function deleteProjectMilestone(id: number): Promise<void> {
+  return invoke("delete_project_milestone", { id });
+}
+
+// Omit milestoneId to unassign
+export async function setTaskMilestone(taskId: number, milestoneId?: number): Promise<void> {
+  return invoke("set_task_milestone", { taskId, milestoneId });
+}
+
+// All non-archived projects when projectId is omitted
+export async function listProjectProgress(projectId?: number): Promise<ProjectProgress[]> {
+  return invoke("list_project_progress", { projectId });
+}
+
+// Omit rule to stop the task recurring
+export async function setTaskRecurrence(taskId: number, rule?: RecurrenceRule): Promise<void> {
+  return invoke("set_task_recurrence", { taskId, rule });
+}
+
+export interface ProjectTemplate {
+  id: number;
+  name: string;
+  description?: string;
+  content: unknown; // Task tree, milestones and columns with relative dates
+  created_at: string;
+  updated_at: string;
+}
+
+export async function listProjectTemplates(): Promise<ProjectTemplate[]> {
+  return invoke("list_project_templates");
 }
 
+export async function saveProjectAsTemplate(data: {
+  project_id: number;
+  name: string;
+  description?: string;
+}): Promise<number> {
+  return invoke("save_project_as_template", { req: data });
+}
+
+export async function deleteProjectTemplate(id: number): Promise<void> {
+  return invoke("delete_project_template", { id });
+}
 
+export async function instantiateProjectTemplate(data: {
+  template_id: number;
+  name: string;
+  start_date?: string; // YYYY-MM-DD, defaults to today
+}): Promise<number> {
+  return invoke("instantiate_project_template", { req: data });
+}
+
+export async function cloneProject(data: {
+  project_id: number;
+  name: string;
+  shift_days?: number; // Moves every date
+}): Promise<number> {
+  return invoke("clone_project", { req: data });
+}
@@ -0,0 +1,70 @@
+import { invoke } from "@tauri-apps/api/core";
+
+export interface AirdropExpense {
+  id: number;
+  airdrop_id: number;
+  amount: number;
+  currency: string;
+  description?: string;
+  spent_on: string;
+  created_at: string;
+}
+
+export interface ScoringWeights {
+  minutes_per_task: number;
+  default_confidence: number;
+  confidence_weight: number; // Exponent on confidence
+  cost_weight: number; // Share of cost to date counted against value
+  default_horizon_days: number;
+  drop_below_per_minute: number;
+  review_below_per_minute: number;
+}
+
+export type AirdropRecommendation = "keep" | "review" | "drop" | "needs_estimate" | "no_tasks";
+
+export interface AirdropScore {
+  airdrop_id: number;
+  airdrop_name: string;
+  expected_value?: number;
+  confidence: number;
+  cost_to_date: number; // In `currency`
+  unpriced_expenses: number; // Expenses left out for lack of a price
+  currency: string;
+  daily_tasks: number;
+  effort_minutes_per_day: number;
+  days_to_snapshot?: number;
+  remaining_effort_minutes: number;
+  expected_net?: number;
+  roi_per_minute?: number;
+  recommendation: AirdropRecommendation;
+}
+
+export async function listAirdropExpenses(airdropId?: number): Promise<AirdropExpense[]> {
+  return invoke("list_airdrop_expenses", { airdropId });
+}
+
+export async function createAirdropExpense(data: {
+  airdrop_id: number;
+  amount: number;
+  currency?: string;
+  description?: string;
+  spent_on?: string; // YYYY-MM-DD, defaults to today
+}): Promise<number> {
+  return invoke("create_airdrop_expense", { req: data });
+}
+
+export async function deleteAirdropExpense(id: number): Promise<void> {
+  return invoke("delete_airdrop_expense", { id });
+}
+
+export async function getScoringWeights(): Promise<ScoringWeights> {
+  return invoke("get_scoring_weights");
+}
+
+export async function updateScoringWeights(weights: ScoringWeights): Promise<void> {
+  return invoke("update_scoring_weights", { weights });
+}
+
+export async function rankAirdrops(): Promise<AirdropScore[]> {
+  return invoke("rank_airdrops");
+}
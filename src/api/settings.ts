@@ -0,0 +1,9 @@
+import { invoke } from "@tauri-apps/api/core";
+
+export async function getSetting<T = unknown>(key: string): Promise<T | null> {
+  return invoke("get_setting", { key });
+}
+
+export async function setSetting(key: string, value: unknown): Promise<void> {
+  return invoke("set_setting", { key, value });
+}
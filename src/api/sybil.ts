@@ -0,0 +1,32 @@
+import { invoke } from "@tauri-apps/api/core";
+
+export type SybilFindingKind =
+  | "synchronized_completions"
+  | "identical_task_sets"
+  | "shared_funding_source"
+  | "identity_reuse";
+
+export interface SybilFinding {
+  kind: SybilFindingKind;
+  points: number;
+  wallet_ids: number[];
+  explanation: string;
+}
+
+export interface AirdropSybilRisk {
+  airdrop_id: number;
+  airdrop_name: string;
+  score: number;
+  level: "low" | "medium" | "high";
+  wallet_ids: number[];
+  findings: SybilFinding[];
+}
+
+export interface SybilAuditReport {
+  generated_at: string;
+  airdrops: AirdropSybilRisk[];
+}
+
+export async function sybilAuditReport(airdropId?: number): Promise<SybilAuditReport> {
+  return invoke("sybil_audit_report", { airdropId });
+}
@@ -0,0 +1,143 @@
+import { invoke } from "@tauri-apps/api/core";
+
+export interface AirdropClaim {
+  id: number;
+  airdrop_id?: number;
+  wallet_id?: number;
+  token: string;
+  amount: number;
+  fair_value: number; // Total value when received
+  currency: string;
+  received_at: string;
+  tx_hash?: string;
+  notes?: string;
+  created_at: string;
+}
+
+export interface TokenDisposal {
+  id: number;
+  token: string;
+  amount: number;
+  proceeds: number;
+  fee: number;
+  currency: string;
+  disposed_at: string;
+  tx_hash?: string;
+  notes?: string;
+  created_at: string;
+}
+
+export interface DisposalLot {
+  disposal_id: number;
+  claim_id: number;
+  amount: number;
+}
+
+export type LotMethod = "fifo" | "specific_id";
+export type TaxCsvLayout = "generic" | "koinly" | "coin_tracking";
+
+export interface TaxReportRequest {
+  year: number; // Calendar year the fiscal year starts in
+  method: LotMethod;
+  fiscal_year_start_month?: number; // 1-12, defaults to January
+  currency?: string; // Report currency, defaults to the base currency
+}
+
+export interface TaxIncomeRow {
+  claim_id: number;
+  airdrop_name?: string;
+  token: string;
+  amount: number;
+  received_at: string;
+  fair_value: number;
+  currency: string;
+  tx_hash?: string;
+}
+
+export interface TaxDisposalRow {
+  disposal_id: number;
+  claim_id?: number;
+  token: string;
+  amount: number;
+  received_at?: string;
+  disposed_at: string;
+  cost_basis: number;
+  proceeds: number; // Net of fee
+  fee: number;
+  gain_loss: number;
+  holding_days?: number;
+  long_term?: boolean;
+  currency: string;
+  tx_hash?: string;
+}
+
+export interface TaxReport {
+  year: number;
+  method: LotMethod;
+  period_start: string;
+  period_end: string;
+  currency?: string;
+  income: TaxIncomeRow[];
+  disposals: TaxDisposalRow[];
+  total_income: number;
+  total_proceeds: number;
+  total_cost_basis: number;
+  total_gain_loss: number;
+  warnings: string[];
+}
+
+export async function listAirdropClaims(airdropId?: number): Promise<AirdropClaim[]> {
+  return invoke("list_airdrop_claims", { airdropId });
+}
+
+export async function createAirdropClaim(data: {
+  airdrop_id?: number;
+  wallet_id?: number;
+  token: string;
+  amount: number;
+  fair_value: number;
+  currency?: string;
+  received_at: string; // RFC 3339 or YYYY-MM-DD
+  tx_hash?: string;
+  notes?: string;
+}): Promise<number> {
+  return invoke("create_airdrop_claim", { req: data });
+}
+
+export async function deleteAirdropClaim(id: number): Promise<void> {
+  return invoke("delete_airdrop_claim", { id });
+}
+
+export async function listTokenDisposals(): Promise<TokenDisposal[]> {
+  return invoke("list_token_disposals");
+}
+
+export async function createTokenDisposal(data: {
+  token: string;
+  amount: number;
+  proceeds: number;
+  fee?: number;
+  currency?: string;
+  disposed_at: string; // RFC 3339 or YYYY-MM-DD
+  tx_hash?: string;
+  notes?: string;
+  lots?: { claim_id: number; amount: number }[]; // For specific-ID matching
+}): Promise<number> {
+  return invoke("create_token_disposal", { req: data });
+}
+
+export async function deleteTokenDisposal(id: number): Promise<void> {
+  return invoke("delete_token_disposal", { id });
+}
+
+export async function listDisposalLots(disposalId: number): Promise<DisposalLot[]> {
+  return invoke("list_disposal_lots", { disposalId });
+}
+
+export async function taxReport(req: TaxReportRequest): Promise<TaxReport> {
+  return invoke("tax_report", { req });
+}
+
+export async function exportTaxReportCsv(req: TaxReportRequest, layout: TaxCsvLayout): Promise<string> {
+  return invoke("export_tax_report_csv", { req, layout });
+}
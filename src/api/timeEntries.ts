@@ -0,0 +1,100 @@
+import { invoke } from "@tauri-apps/api/core";
+
+export interface TimeEntry {
+  id: number;
+  project_id: number;
+  task_id?: number;
+  started_at: string;
+  ended_at?: string; // Unset while the timer runs
+  duration_seconds?: number;
+  notes?: string;
+  source: "timer" | "manual";
+  stop_reason?: "manual" | "app_exit" | "recovered";
+  heartbeat_at?: string;
+  created_at: string;
+  updated_at: string;
+}
+
+export interface TimeEntryFilter {
+  project_id?: number;
+  task_id?: number;
+  from?: string; // YYYY-MM-DD or RFC 3339
+  to?: string; // Exclusive
+}
+
+export type TimeReportGroup = "project" | "task" | "day" | "week";
+
+export interface TimeReportRow {
+  key: string; // Project/task id, YYYY-MM-DD or YYYY-Www
+  label: string;
+  seconds: number;
+  entries: number;
+}
+
+export interface EstimateComparison {
+  task_id: number;
+  task_title: string;
+  project_id: number;
+  project_name: string;
+  done: boolean;
+  estimated_minutes?: number;
+  actual_minutes: number;
+}
+
+// Emitted as "time-entry-recovered" on launch for timers a crash left running
+export type TimeEntryRecoveredEvent = TimeEntry;
+
+// Stops any running timer first
+export async function startTimeEntry(data: {
+  project_id?: number;
+  task_id?: number;
+  notes?: string;
+}): Promise<TimeEntry> {
+  return invoke("start_time_entry", { req: data });
+}
+
+export async function stopTimeEntry(): Promise<TimeEntry | null> {
+  return invoke("stop_time_entry");
+}
+
+export async function getRunningTimeEntry(): Promise<TimeEntry | null> {
+  return invoke("get_running_time_entry");
+}
+
+export async function listTimeEntries(filter?: TimeEntryFilter): Promise<TimeEntry[]> {
+  return invoke("list_time_entries", { filter });
+}
+
+export async function createTimeEntry(data: {
+  project_id?: number;
+  task_id?: number;
+  started_at: string;
+  ended_at: string;
+  notes?: string;
+}): Promise<number> {
+  return invoke("create_time_entry", { req: data });
+}
+
+export async function updateTimeEntry(data: {
+  id: number;
+  started_at?: string;
+  ended_at?: string;
+  notes?: string;
+}): Promise<void> {
+  return invoke("update_time_entry", { req: data });
+}
+
+export async function deleteTimeEntry(id: number): Promise<void> {
+  return invoke("delete_time_entry", { id });
+}
+
+export async function timeReport(
+  groupBy: TimeReportGroup,
+  filter: TimeEntryFilter = {}
+): Promise<TimeReportRow[]> {
+  return invoke("time_report", { req: { group_by: groupBy, ...filter } });
+}
+
+export async function estimateVsActual(projectId?: number): Promise<EstimateComparison[]> {
+  return invoke("estimate_vs_actual", { projectId });
+}
@@ -0,0 +1,106 @@
+import { invoke } from "@tauri-apps/api/core";
+
+export interface WalletTransaction {
+  id: number;
+  wallet_id: number;
+  chain: string;
+  tx_hash: string;
+  block_number?: number;
+  timestamp?: string;
+  from_address?: string;
+  to_address?: string;
+  value?: string;
+  fee?: string;
+  method?: string;
+  status?: string;
+  airdrop_id?: number;
+  created_at: string;
+}
+
+export interface AirdropContract {
+  id: number;
+  airdrop_id: number;
+  chain?: string;
+  address: string;
+  label?: string;
+  created_at: string;
+}
+
+export interface DomainContract {
+  id: number;
+  domain: string;
+  chain?: string;
+  address: string;
+  label?: string;
+  created_at: string;
+}
+
+export interface ImportWalletTransactionsReport {
+  total_rows: number;
+  imported: number;
+  duplicates: number;
+  matched: number;
+  errors: { row: number; message: string }[];
+}
+
+export async function importWalletTransactions(data: {
+  wallet_id: number;
+  chain: string;
+  format: "csv" | "json";
+  content: string;
+}): Promise<ImportWalletTransactionsReport> {
+  return invoke("import_wallet_transactions", { req: data });
+}
+
+export async function listWalletTransactions(filter?: {
+  wallet_id?: number;
+  airdrop_id?: number;
+  chain?: string;
+}): Promise<WalletTransaction[]> {
+  return invoke("list_wallet_transactions", { filter });
+}
+
+export async function deleteWalletTransaction(id: number): Promise<void> {
+  return invoke("delete_wallet_transaction", { id });
+}
+
+export async function rematchWalletTransactions(): Promise<number> {
+  return invoke("rematch_wallet_transactions");
+}
+
+export async function listContractMappings(): Promise<{
+  airdrop_contracts: AirdropContract[];
+  domain_contracts: DomainContract[];
+}> {
+  return invoke("list_contract_mappings");
+}
+
+export async function createContractMapping(data: {
+  airdrop_id?: number;
+  domain?: string;
+  chain?: string;
+  address: string;
+  label?: string;
+}): Promise<number> {
+  return invoke("create_contract_mapping", { req: data });
+}
+
+export async function deleteAirdropContract(id: number): Promise<void> {
+  return invoke("delete_airdrop_contract", { id });
+}
+
+export async function deleteDomainContract(id: number): Promise<void> {
+  return invoke("delete_domain_contract", { id });
+}
+
+export async function linkCompletionTransaction(completionId: number, transactionId: number): Promise<void> {
+  return invoke("link_completion_transaction", { completionId, transactionId });
+}
+
+export async function unlinkCompletionTransaction(completionId: number, transactionId: number): Promise<void> {
+  return invoke("unlink_completion_transaction", { completionId, transactionId });
+}
+
+export async function listCompletionTransactions(completionId: number): Promise<WalletTransaction[]> {
+  return invoke("list_completion_transactions", { completionId });
+}
@@ -0,0 +1,45 @@
+import { invoke } from "@tauri-apps/api/core";
+
+export interface Wallet {
+  id: number;
+  label: string;
+  address: string;
+  chains: string[];
+  group_name?: string; // Wallets in the same group may be linked
+  funding_source?: string;
+  browser_profile_id?: number; // Profile holding this wallet's extension
+  notes?: string;
+  created_at: string;
+  updated_at: string;
+}
+
+export async function listWallets(): Promise<Wallet[]> {
+  return invoke("list_wallets");
+}
+
+export async function createWallet(data: {
+  label: string;
+  address: string;
+  chains?: string[];
+  group_name?: string;
+  funding_source?: string;
+  notes?: string;
+}): Promise<number> {
+  return invoke("create_wallet", { req: data });
+}
+
+export async function updateWallet(data: {
+  id: number;
+  label?: string;
+  address?: string;
+  chains?: string[];
+  group_name?: string;
+  funding_source?: string;
+  notes?: string;
+}): Promise<void> {
+  return invoke("update_wallet", { req: data });
+}
+
+export async function deleteWallet(id: number): Promise<void> {
+  return invoke("delete_wallet", { id });
+}